@@ -0,0 +1,167 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License, v2.0.
+
+use crate::event::Event;
+use std::fmt;
+use std::rc::Rc;
+use winit::event::{ModifiersState, VirtualKeyCode};
+
+pub type Command = Rc<dyn Fn()>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub key: VirtualKeyCode,
+    pub modifiers: ModifiersState,
+}
+
+impl KeyChord {
+    pub fn new(key: VirtualKeyCode, modifiers: ModifiersState) -> Self {
+        Self { key, modifiers }
+    }
+
+    // Parses chords like "Ctrl+S", "Ctrl+Shift+Z" or "Alt+F4". "Cmd", "Super"
+    // and "Logo" all mean the logo key.
+    pub fn parse(chord: &str) -> Option<Self> {
+        let mut modifiers = ModifiersState::empty();
+        let mut key = None;
+
+        for part in chord.split('+').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= ModifiersState::CTRL,
+                "shift" => modifiers |= ModifiersState::SHIFT,
+                "alt" | "option" => modifiers |= ModifiersState::ALT,
+                "cmd" | "super" | "logo" => modifiers |= ModifiersState::LOGO,
+                _ => key = Some(key_from_name(part)?),
+            }
+        }
+
+        key.map(|key| Self { key, modifiers })
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        match event {
+            Event::KeyDown { key: Some(key), modifiers } => *key == self.key && *modifiers == self.modifiers,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.ctrl() {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.alt() {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.shift() {
+            write!(f, "Shift+")?;
+        }
+        if self.modifiers.logo() {
+            write!(f, "Super+")?;
+        }
+        match char_for_key(self.key) {
+            Some(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            None => write!(f, "{:?}", self.key),
+        }
+    }
+}
+
+// Letters and digits, which is all mnemonics need.
+pub fn char_for_key(key: VirtualKeyCode) -> Option<char> {
+    use VirtualKeyCode::*;
+    let c = match key {
+        A => 'a', B => 'b', C => 'c', D => 'd', E => 'e', F => 'f', G => 'g',
+        H => 'h', I => 'i', J => 'j', K => 'k', L => 'l', M => 'm', N => 'n',
+        O => 'o', P => 'p', Q => 'q', R => 'r', S => 's', T => 't', U => 'u',
+        V => 'v', W => 'w', X => 'x', Y => 'y', Z => 'z',
+        Key0 => '0', Key1 => '1', Key2 => '2', Key3 => '3', Key4 => '4',
+        Key5 => '5', Key6 => '6', Key7 => '7', Key8 => '8', Key9 => '9',
+        _ => return None,
+    };
+    Some(c)
+}
+
+fn key_from_name(name: &str) -> Option<VirtualKeyCode> {
+    use VirtualKeyCode::*;
+
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        let c = c.to_ascii_lowercase();
+        return [
+            A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+            Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9,
+        ]
+        .into_iter()
+        .find(|key| char_for_key(*key) == Some(c))
+        .or(match c {
+            ',' => Some(Comma),
+            '.' => Some(Period),
+            '/' => Some(Slash),
+            '-' => Some(Minus),
+            '=' => Some(Equals),
+            ';' => Some(Semicolon),
+            _ => None,
+        });
+    }
+
+    let key = match name.to_ascii_lowercase().as_str() {
+        "f1" => F1, "f2" => F2, "f3" => F3, "f4" => F4, "f5" => F5, "f6" => F6,
+        "f7" => F7, "f8" => F8, "f9" => F9, "f10" => F10, "f11" => F11, "f12" => F12,
+        "enter" | "return" => Return,
+        "esc" | "escape" => Escape,
+        "tab" => Tab,
+        "space" => Space,
+        "backspace" => Back,
+        "delete" | "del" => Delete,
+        "insert" | "ins" => Insert,
+        "home" => Home,
+        "end" => End,
+        "pageup" => PageUp,
+        "pagedown" => PageDown,
+        "left" => Left,
+        "right" => Right,
+        "up" => Up,
+        "down" => Down,
+        "plus" => Plus,
+        _ => return None,
+    };
+    Some(key)
+}
+
+// Key chords that trigger commands no matter which widget has focus.
+#[derive(Default, Clone)]
+pub struct Accelerators {
+    bindings: Vec<(KeyChord, Command)>,
+}
+
+impl Accelerators {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Rebinding a chord replaces whatever it triggered before.
+    pub fn bind(&mut self, chord: KeyChord, command: Command) {
+        self.unbind(chord);
+        self.bindings.push((chord, command));
+    }
+
+    pub fn unbind(&mut self, chord: KeyChord) {
+        self.bindings.retain(|(bound, _)| *bound != chord);
+    }
+
+    pub fn is_bound(&self, chord: KeyChord) -> bool {
+        self.bindings.iter().any(|(bound, _)| *bound == chord)
+    }
+
+    // Runs the command bound to a KeyDown event, if any.
+    pub fn handle(&self, event: &Event) -> bool {
+        match self.bindings.iter().find(|(chord, _)| chord.matches(event)) {
+            Some((_, command)) => {
+                command();
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -1,35 +1,75 @@
 // Copyright 2025 Bluegill Studios.
 // Licensed under the GNU General Public License, v2.0. 
 
-use winit::event::{WindowEvent, KeyboardInput, ElementState, MouseButton, ModifiersState, VirtualKeyCode};
-use crate::event::types::Event;
+use winit::event::{WindowEvent, ElementState, ModifiersState, MouseScrollDelta};
+use crate::event::types::{Event, ScrollDelta};
+
+// What winit only reports as separate events: the current modifier keys and
+// the last known cursor position, which mouse button events don't carry.
+// Winit reports physical pixels; everything handed to widgets is divided by
+// `scale_factor` into logical units.
+#[derive(Debug, Clone, Copy)]
+pub struct InputState {
+    pub modifiers: ModifiersState,
+    pub cursor: (f32, f32),
+    pub scale_factor: f64,
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self {
+            modifiers: ModifiersState::default(),
+            cursor: (0.0, 0.0),
+            scale_factor: 1.0,
+        }
+    }
+}
 
 pub fn translate_event(
     event: &WindowEvent<'_>,
-    modifiers: ModifiersState,
+    input: &mut InputState,
 ) -> Option<Event> {
+    let modifiers = input.modifiers;
+    let (x, y) = input.cursor;
+    let scale = input.scale_factor;
+
     match event {
+        WindowEvent::ModifiersChanged(new_modifiers) => {
+            input.modifiers = *new_modifiers;
+            None
+        }
         WindowEvent::CursorMoved { position, .. } => {
-            Some(Event::MouseMove {
-                x: position.x as f32,
-                y: position.y as f32,
-                modifiers,
-            })
+            let (x, y) = ((position.x / scale) as f32, (position.y / scale) as f32);
+            input.cursor = (x, y);
+            Some(Event::MouseMove { x, y, modifiers })
         }
         WindowEvent::MouseInput { state, button, .. } => match state {
             ElementState::Pressed => Some(Event::MouseDown {
-                x: 0.0,
-                y: 0.0,
+                x,
+                y,
                 button: *button,
                 modifiers,
             }),
             ElementState::Released => Some(Event::MouseUp {
-                x: 0.0,
-                y: 0.0,
+                x,
+                y,
                 button: *button,
                 modifiers,
             }),
         },
+        WindowEvent::MouseWheel { delta, phase, .. } => Some(Event::Scroll {
+            x,
+            y,
+            delta: match delta {
+                MouseScrollDelta::LineDelta(dx, dy) => ScrollDelta::Lines { x: *dx, y: *dy },
+                MouseScrollDelta::PixelDelta(pos) => ScrollDelta::Pixels {
+                    x: (pos.x / scale) as f32,
+                    y: (pos.y / scale) as f32,
+                },
+            },
+            phase: *phase,
+            modifiers,
+        }),
         WindowEvent::KeyboardInput { input, .. } => {
             match input.state {
                 ElementState::Pressed => Some(Event::KeyDown {
@@ -45,11 +85,13 @@ pub fn translate_event(
         WindowEvent::ReceivedCharacter(c) => Some(Event::CharInput(*c)),
         WindowEvent::Focused(true) => Some(Event::FocusGained),
         WindowEvent::Focused(false) => Some(Event::FocusLost),
-        WindowEvent::Resized(size) => Some(Event::Resized {
-            width: size.width,
-            height: size.height,
-        }),
-        WindowEvent::RedrawRequested => Some(Event::RedrawRequested),
+        WindowEvent::Resized(size) => {
+            let size = size.to_logical::<u32>(scale);
+            Some(Event::Resized {
+                width: size.width,
+                height: size.height,
+            })
+        }
         _ => None,
     }
 }
@@ -1,8 +1,10 @@
 // Copyright 2025 Bluegill Studios.
 // Licensed under the GNU General Public License, v2.0.
 
+pub mod accel;
 pub mod input;
 pub mod types;
 
-pub use types::Event;
-pub use input::translate_event;
+pub use accel::{Accelerators, KeyChord};
+pub use types::{Event, ScrollDelta};
+pub use input::{translate_event, InputState};
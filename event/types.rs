@@ -1,13 +1,23 @@
 // Copyright 2025 Bluegill Studios.
 // Licensed under the GNU General Public License, v2.0.
 
-use winit::event::{MouseButton, VirtualKeyCode, ModifiersState};
+use skia_safe::Rect;
+use winit::event::{MouseButton, VirtualKeyCode, ModifiersState, TouchPhase};
+
+// Positive y scrolls up (content moves down), matching winit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollDelta {
+    Lines { x: f32, y: f32 },
+    Pixels { x: f32, y: f32 },
+}
 
 #[derive(Debug, Clone)]
 pub enum Event {
     MouseDown { x: f32, y: f32, button: MouseButton, modifiers: ModifiersState },
     MouseUp { x: f32, y: f32, button: MouseButton, modifiers: ModifiersState },
     MouseMove { x: f32, y: f32, modifiers: ModifiersState },
+    // Touchpads report a phase; mouse wheels always send `Moved`.
+    Scroll { x: f32, y: f32, delta: ScrollDelta, phase: TouchPhase, modifiers: ModifiersState },
     KeyDown { key: Option<VirtualKeyCode>, modifiers: ModifiersState },
     KeyUp { key: Option<VirtualKeyCode>, modifiers: ModifiersState },
     CharInput(char),
@@ -15,4 +25,6 @@ pub enum Event {
     FocusLost,
     Resized { width: u32, height: u32 },
     RedrawRequested,
+    // Asks enclosing scroll views to bring a window-space rect into view.
+    ScrollIntoView(Rect),
 }
@@ -1,55 +1,269 @@
 // Copyright 2025 Bluegill Studios.
 // Licensed under the GNU General Public License v2.0.
 
+use crate::inspect::{Command, InspectorServer, WindowSummary};
+use crate::options::WindowOptions;
 use crate::window::Window;
-use crate::widget::Widget; 
+use crate::widget::Widget;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::time::Instant;
 use winit::{
     event::{Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoop},
-    window::WindowBuilder,
+    event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopWindowTarget},
+    window::WindowId,
 };
 
+// Sent to the event loop from other threads.
+enum UserEvent {
+    // A tool sent the inspector a command.
+    InspectorRequest,
+}
+
+enum AppCommand {
+    OpenWindow {
+        options: WindowOptions,
+        root_widget: Box<dyn Widget>,
+    },
+    UpdateWindow(WindowId, Box<dyn FnOnce(&mut Window)>),
+    CloseWindow(WindowId),
+    Quit,
+}
+
+// Cheap to clone, so widgets can keep one around and open or close windows
+// from their event handlers. Commands are applied once the current batch of
+// events has been handled.
+#[derive(Clone)]
+pub struct AppHandle {
+    commands: Rc<RefCell<VecDeque<AppCommand>>>,
+    current_window: Rc<Cell<Option<WindowId>>>,
+}
+
+impl AppHandle {
+    fn new() -> Self {
+        Self {
+            commands: Rc::new(RefCell::new(VecDeque::new())),
+            current_window: Rc::new(Cell::new(None)),
+        }
+    }
+
+    pub fn open_window(&self, options: WindowOptions, root_widget: Box<dyn Widget>) {
+        self.commands
+            .borrow_mut()
+            .push_back(AppCommand::OpenWindow { options, root_widget });
+    }
+
+    // Runs `f` against the window once it is no longer busy with events,
+    // e.g. `handle.update_window(id, |w| w.set_title("Saved"))`.
+    pub fn update_window<F>(&self, window_id: WindowId, f: F)
+    where
+        F: FnOnce(&mut Window) + 'static,
+    {
+        self.commands
+            .borrow_mut()
+            .push_back(AppCommand::UpdateWindow(window_id, Box::new(f)));
+    }
+
+    pub fn close_window(&self, window_id: WindowId) {
+        self.commands.borrow_mut().push_back(AppCommand::CloseWindow(window_id));
+    }
+
+    // The window whose event is being handled right now, if any.
+    pub fn current_window(&self) -> Option<WindowId> {
+        self.current_window.get()
+    }
+
+    pub fn close_current_window(&self) {
+        if let Some(window_id) = self.current_window() {
+            self.close_window(window_id);
+        }
+    }
+
+    pub fn quit(&self) {
+        self.commands.borrow_mut().push_back(AppCommand::Quit);
+    }
+
+    fn take_commands(&self) -> VecDeque<AppCommand> {
+        std::mem::take(&mut *self.commands.borrow_mut())
+    }
+}
+
+type WindowError = Box<dyn FnMut(&str)>;
+
 pub struct App {
-    event_loop: Option<EventLoop<()>>,
+    event_loop: Option<EventLoop<UserEvent>>,
+    windows: HashMap<WindowId, Window>,
+    // Window ids in the order they were opened; the first is the main one.
+    order: Vec<WindowId>,
+    inspector: Option<InspectorServer>,
+    on_window_error: Option<WindowError>,
+    handle: AppHandle,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl App {
     pub fn new() -> Self {
-        Self {
-            event_loop: Some(EventLoop::new()),
+        let mut app = Self {
+            event_loop: Some(EventLoopBuilder::with_user_event().build()),
+            windows: HashMap::new(),
+            order: Vec::new(),
+            inspector: None,
+            on_window_error: None,
+            handle: AppHandle::new(),
+        };
+        if let Some(port) = std::env::var("COCOA_INSPECTOR").ok().and_then(|port| port.trim().parse().ok()) {
+            app.start_inspector(port);
         }
+        app
     }
 
-    pub fn run(mut self, mut root_widget: Box<dyn Widget>) {
-        if let Some(event_loop) = self.event_loop.take() {
-            let winit_window = WindowBuilder::new()
-                .with_title("Cocoa GUI")
-                .build(&event_loop)
-                .unwrap();
+    pub fn handle(&self) -> AppHandle {
+        self.handle.clone()
+    }
+
+    // Serves every window's widget tree on 127.0.0.1:`port` (see `inspect`).
+    // Port 0 picks a free one; `inspector_port` says which.
+    // `COCOA_INSPECTOR=<port>` in the environment takes precedence.
+    pub fn with_inspector(mut self, port: u16) -> Self {
+        if self.inspector.is_none() {
+            self.start_inspector(port);
+        }
+        self
+    }
+
+    // Where the inspector is listening, if it is.
+    pub fn inspector_port(&self) -> Option<u16> {
+        self.inspector.as_ref().map(|server| server.port())
+    }
+
+    // Called with the reason when a window can't be opened, e.g. because
+    // the backend it asked for is unavailable. Without a handler that
+    // panics, since there may be no window left to report it in.
+    pub fn on_window_error<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&str) + 'static,
+    {
+        self.on_window_error = Some(Box::new(f));
+        self
+    }
+
+    fn start_inspector(&mut self, port: u16) {
+        let proxy = match &self.event_loop {
+            Some(event_loop) => Mutex::new(event_loop.create_proxy()),
+            None => return,
+        };
+        let wake = move || {
+            if let Ok(proxy) = proxy.lock() {
+                let _ = proxy.send_event(UserEvent::InspectorRequest);
+            }
+        };
+        match InspectorServer::start(port, wake) {
+            Ok(server) => self.inspector = Some(server),
+            Err(err) => eprintln!("cocoa: can't start the inspector on port {}: {}", port, err),
+        }
+    }
+
+    fn answer_inspector(&mut self) {
+        let server = match &self.inspector {
+            Some(server) => server,
+            None => return,
+        };
+        server.poll(|command| match command {
+            Command::Windows => {
+                let windows: Vec<WindowSummary> = self
+                    .order
+                    .iter()
+                    .filter_map(|id| self.windows.get(id).map(|w| WindowSummary { id: (*id).into(), title: w.title() }))
+                    .collect();
+                serde_json::to_string(&windows).expect("Window list always serializes")
+            }
+            Command::Window(id, command) => {
+                let id = id.map(WindowId::from).or_else(|| self.order.first().copied());
+                match id.and_then(|id| self.windows.get_mut(&id)) {
+                    Some(window) => window.inspector_command(command),
+                    None => "error: no such window".to_string(),
+                }
+            }
+            Command::Unknown(line) => format!("error: unknown command {:?}", line),
+        });
+    }
+
+    fn remove_window(&mut self, window_id: WindowId) {
+        self.windows.remove(&window_id);
+        self.order.retain(|id| *id != window_id);
+    }
 
-            let mut window = Window::new(winit_window, root_widget);
+    pub fn run(self, root_widget: Box<dyn Widget>) {
+        self.run_with_options(WindowOptions::default(), root_widget);
+    }
+
+    pub fn run_with_options(mut self, options: WindowOptions, root_widget: Box<dyn Widget>) {
+        if let Some(event_loop) = self.event_loop.take() {
+            // The main window goes first so it is created before anything
+            // queued through the handle ahead of `run`.
+            self.handle
+                .commands
+                .borrow_mut()
+                .push_front(AppCommand::OpenWindow { options, root_widget });
+            self.apply_commands(&event_loop);
 
-            event_loop.run(move |event, _, control_flow| {
+            event_loop.run(move |event, event_loop, control_flow| {
                 *control_flow = ControlFlow::Wait;
 
                 match event {
                     Event::WindowEvent {
                         event: WindowEvent::CloseRequested,
                         window_id,
-                    } if window_id == window.id() => {
-                        *control_flow = ControlFlow::Exit;
+                    } => {
+                        self.remove_window(window_id);
                     }
 
-                    Event::WindowEvent { event, window_id } if window_id == window.id() => {
-                        window.handle_event(&event);
+                    Event::WindowEvent { event, window_id } => {
+                        if let Some(window) = self.windows.get_mut(&window_id) {
+                            self.handle.current_window.set(Some(window_id));
+                            window.handle_event(&event);
+                            self.handle.current_window.set(None);
+                        }
                     }
 
-                    Event::RedrawRequested(window_id) if window_id == window.id() => {
-                        window.render();
+                    Event::RedrawRequested(window_id) => {
+                        if let Some(window) = self.windows.get_mut(&window_id) {
+                            window.render();
+                        }
                     }
 
+                    Event::UserEvent(UserEvent::InspectorRequest) => self.answer_inspector(),
+
                     Event::MainEventsCleared => {
-                        // window.request_redraw();
+                        // Quit, or the last window went away.
+                        let running = self.apply_commands(event_loop);
+                        if !running || self.windows.is_empty() {
+                            *control_flow = ControlFlow::Exit;
+                            return;
+                        }
+
+                        let now = Instant::now();
+                        for window in self.windows.values_mut() {
+                            window.request_frame_if_due(now);
+                        }
+                    }
+
+                    // Last event of each loop iteration, so the control flow
+                    // set here is the one that sticks. Only wake up again
+                    // when some window has a frame waiting for its slot;
+                    // inspector commands wake the loop themselves.
+                    Event::RedrawEventsCleared => {
+                        let deadline = self.windows.values().filter_map(|w| w.frame_deadline()).min();
+                        if let Some(deadline) = deadline {
+                            *control_flow = ControlFlow::WaitUntil(deadline);
+                        }
                     }
 
                     _ => {}
@@ -57,4 +271,38 @@ impl App {
             });
         }
     }
+
+    // Returns false once the app has been asked to quit.
+    fn apply_commands(&mut self, event_loop: &EventLoopWindowTarget<UserEvent>) -> bool {
+        for command in self.handle.take_commands() {
+            match command {
+                AppCommand::OpenWindow { options, root_widget } => {
+                    match Window::new(event_loop, &options, root_widget) {
+                        Ok(window) => {
+                            self.order.push(window.id());
+                            self.windows.insert(window.id(), window);
+                        }
+                        Err(err) => match &mut self.on_window_error {
+                            Some(on_window_error) => on_window_error(&err),
+                            None => panic!("cocoa: can't open window {:?}: {}", options.title, err),
+                        },
+                    }
+                }
+                AppCommand::UpdateWindow(window_id, f) => {
+                    if let Some(window) = self.windows.get_mut(&window_id) {
+                        f(window);
+                    }
+                }
+                AppCommand::CloseWindow(window_id) => {
+                    self.remove_window(window_id);
+                }
+                AppCommand::Quit => {
+                    self.windows.clear();
+                    self.order.clear();
+                    return false;
+                }
+            }
+        }
+        true
+    }
 }
@@ -0,0 +1,73 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+// Requests widgets make while handling an event. Widgets don't hold a
+// reference to their window, so they queue work here and the window that is
+// dispatching picks it up once the event has been handled.
+
+use crate::event::accel::{Command, KeyChord};
+use crate::widget::dialog::Dialog;
+use crate::widget::overlay::{Popup, PopupId};
+use skia_safe::Rect;
+use std::cell::RefCell;
+use winit::window::CursorIcon;
+
+#[derive(Default)]
+pub(crate) struct Requests {
+    pub redraw: bool,
+    pub animate: bool,
+    pub scroll_into_view: Vec<Rect>,
+    pub popups: Vec<Popup>,
+    pub dismissed: Vec<PopupId>,
+    pub accelerators: Vec<(KeyChord, Command)>,
+    pub cursor: Option<CursorIcon>,
+}
+
+thread_local! {
+    static REQUESTS: RefCell<Requests> = RefCell::new(Requests::default());
+}
+
+pub fn request_redraw() {
+    REQUESTS.with(|r| r.borrow_mut().redraw = true);
+}
+
+// Redraws on the next frame. Animating widgets call this from `draw` for as
+// long as they are moving; once nobody asks, the window goes back to sleep.
+pub fn request_animation_frame() {
+    REQUESTS.with(|r| r.borrow_mut().animate = true);
+}
+
+// Asks the scroll views around `rect` (window coordinates) to bring it into
+// view, e.g. when a widget takes focus.
+pub fn scroll_into_view(rect: Rect) {
+    REQUESTS.with(|r| r.borrow_mut().scroll_into_view.push(rect));
+}
+
+// Opens `dialog` modally over the window handling the current event.
+pub fn show_dialog(dialog: Dialog) {
+    show_popup(Popup::modal(dialog.id(), Box::new(dialog)));
+}
+
+pub fn show_popup(popup: Popup) {
+    REQUESTS.with(|r| r.borrow_mut().popups.push(popup));
+}
+
+pub fn dismiss_popup(id: PopupId) {
+    REQUESTS.with(|r| r.borrow_mut().dismissed.push(id));
+}
+
+// Binds `chord` in the window handling the current event (or being laid out).
+pub fn bind_accelerator(chord: KeyChord, command: Command) {
+    REQUESTS.with(|r| r.borrow_mut().accelerators.push((chord, command)));
+}
+
+// Sets the mouse cursor. Every pointer move starts from the default arrow,
+// so a widget that wants a different cursor asks again on each move while
+// the pointer is over it.
+pub fn set_cursor(cursor: CursorIcon) {
+    REQUESTS.with(|r| r.borrow_mut().cursor = Some(cursor));
+}
+
+pub(crate) fn take_requests() -> Requests {
+    REQUESTS.with(|r| std::mem::take(&mut *r.borrow_mut()))
+}
@@ -0,0 +1,255 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+// Developer overlay drawn over a window's widgets: layout outlines, flashes
+// where widgets changed, a frame-time graph and details of the widget under
+// the pointer. Toggled with `WindowOptions::debug_overlay_chord`.
+
+use crate::frame::FrameStats;
+use crate::inspect::find_bounds;
+use crate::widget::overlay::Overlay;
+use crate::widget::Widget;
+use skia_safe::{Canvas, Color, Font, Paint, PaintStyle, Point, Rect, Typeface};
+use std::time::{Duration, Instant};
+
+// How long a changed widget stays highlighted.
+const FLASH: Duration = Duration::from_millis(500);
+const FONT_SIZE: f32 = 11.0;
+const LINE_HEIGHT: f32 = 14.0;
+const PADDING: f32 = 6.0;
+// Pixels per frame in the graph; `FrameStats` keeps 120 frames.
+const BAR_WIDTH: f32 = 2.0;
+const GRAPH_HEIGHT: f32 = 48.0;
+// Frame time at the top of the graph, as a multiple of the refresh interval.
+const GRAPH_RANGE: f32 = 3.0;
+
+// Outline colours, cycled by depth so nested rects can be told apart.
+const DEPTH_COLORS: [Color; 6] = [
+    Color::from_rgb(255, 59, 48),
+    Color::from_rgb(0, 122, 255),
+    Color::from_rgb(52, 199, 89),
+    Color::from_rgb(255, 149, 0),
+    Color::from_rgb(175, 82, 222),
+    Color::from_rgb(90, 200, 250),
+];
+
+// A widget as the overlay saw it in the last frame.
+struct Node {
+    depth: usize,
+    type_name: &'static str,
+    bounds: Rect,
+    properties: Vec<(&'static str, String)>,
+}
+
+impl Node {
+    fn same_widget(&self, other: &Node) -> bool {
+        self.depth == other.depth && self.type_name == other.type_name
+    }
+
+    fn changed_from(&self, other: &Node) -> bool {
+        self.bounds != other.bounds || self.properties != other.properties
+    }
+}
+
+// Walks the tree depth first, parents before their children.
+fn collect(widget: &dyn Widget, depth: usize, nodes: &mut Vec<Node>) {
+    nodes.push(Node {
+        depth,
+        type_name: widget.type_name(),
+        bounds: widget.bounds(),
+        properties: widget.debug_properties(),
+    });
+    widget.visit_children(&mut |child| collect(child, depth + 1, nodes));
+}
+
+pub(crate) struct DebugOverlay {
+    enabled: bool,
+    nodes: Vec<Node>,
+    flashes: Vec<(Rect, Instant)>,
+    // Widget id picked in the inspector, outlined even with the overlay off.
+    highlight: Option<String>,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            nodes: Vec::new(),
+            flashes: Vec::new(),
+            highlight: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.nodes.clear();
+        self.flashes.clear();
+    }
+
+    pub fn set_highlight(&mut self, id: Option<String>) {
+        self.highlight = id;
+    }
+
+    // Draws on top of everything else, in the same logical units as the
+    // widgets. Returns true while flashes are still fading out and the
+    // window should keep drawing.
+    pub fn draw(
+        &mut self,
+        canvas: &mut Canvas,
+        root: &dyn Widget,
+        popups: &Overlay,
+        cursor: (f32, f32),
+        stats: &FrameStats,
+        bounds: Rect,
+    ) -> bool {
+        if let Some(rect) = self.highlight.as_deref().and_then(|id| find_bounds(root, popups, id)) {
+            draw_highlight(canvas, rect);
+        }
+        if !self.enabled {
+            return false;
+        }
+        let now = Instant::now();
+        let mut nodes = Vec::new();
+        collect(root, 0, &mut nodes);
+        popups.visit_popups(&mut |popup| collect(popup, 0, &mut nodes));
+
+        // The whole tree is repainted every frame, so flash what actually
+        // changed: nodes whose bounds or properties differ from last frame,
+        // matched by their position in the walk.
+        let first_frame = self.nodes.is_empty();
+        for (index, node) in nodes.iter().enumerate() {
+            let changed = match self.nodes.get(index) {
+                Some(old) => !old.same_widget(node) || old.changed_from(node),
+                None => true,
+            };
+            if changed && !first_frame && !node.bounds.is_empty() {
+                self.flashes.push((node.bounds, now));
+            }
+        }
+        self.flashes.retain(|(_, start)| now - *start < FLASH);
+        self.nodes = nodes;
+
+        let font = Font::new(Typeface::default(), FONT_SIZE);
+        self.draw_outlines(canvas);
+        self.draw_flashes(canvas, now);
+        self.draw_hovered(canvas, &font, cursor, bounds);
+        draw_stats(canvas, &font, stats, bounds);
+        !self.flashes.is_empty()
+    }
+
+    fn draw_outlines(&self, canvas: &mut Canvas) {
+        let mut paint = Paint::default();
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+        for node in &self.nodes {
+            if node.bounds.is_empty() {
+                continue;
+            }
+            paint.set_color(DEPTH_COLORS[node.depth % DEPTH_COLORS.len()].with_a(160));
+            canvas.draw_rect(node.bounds.with_inset((0.5, 0.5)), &paint);
+        }
+    }
+
+    fn draw_flashes(&self, canvas: &mut Canvas, now: Instant) {
+        let mut paint = Paint::default();
+        for (rect, start) in &self.flashes {
+            let fade = 1.0 - (now - *start).as_secs_f32() / FLASH.as_secs_f32();
+            paint.set_color(Color::from_argb((fade.max(0.0) * 90.0) as u8, 255, 0, 255));
+            canvas.draw_rect(*rect, &paint);
+        }
+    }
+
+    // The innermost widget under the pointer: the last node containing it,
+    // since children and popups come after what they cover.
+    fn draw_hovered(&self, canvas: &mut Canvas, font: &Font, (x, y): (f32, f32), bounds: Rect) {
+        let node = match self.nodes.iter().rev().find(|n| n.bounds.contains(Point::new(x, y))) {
+            Some(node) => node,
+            None => return,
+        };
+
+        draw_highlight(canvas, node.bounds);
+
+        let b = node.bounds;
+        let mut lines = vec![
+            node.type_name.to_string(),
+            format!("{} x {} at ({}, {})", b.width(), b.height(), b.left, b.top),
+        ];
+        lines.extend(node.properties.iter().map(|(name, value)| format!("{}: {}", name, value)));
+
+        // Below and right of the pointer, flipped to stay inside the window.
+        let width = lines.iter().map(|line| font.measure_str(line, None).0).fold(0.0, f32::max) + PADDING * 2.0;
+        let height = lines.len() as f32 * LINE_HEIGHT + PADDING * 2.0;
+        let left = if x + 16.0 + width > bounds.right { x - 8.0 - width } else { x + 16.0 };
+        let top = if y + 16.0 + height > bounds.bottom { y - 8.0 - height } else { y + 16.0 };
+        draw_panel(canvas, font, Rect::from_xywh(left.max(bounds.left), top.max(bounds.top), width, height), &lines);
+    }
+}
+
+// FPS and timings in the top right corner, over a graph of recent frame
+// times. Bars above the refresh line are frames that took too long.
+fn draw_stats(canvas: &mut Canvas, font: &Font, stats: &FrameStats, bounds: Rect) {
+    let lines = [
+        format!("{:.0} fps, {:.1} ms avg", stats.fps(), ms(stats.average_frame_time())),
+        format!("paint {:.1} ms, layout {:.1} ms", ms(stats.paint_time), ms(stats.layout_time)),
+        format!("{} frames, {} dropped", stats.frames, stats.dropped_frames),
+    ];
+    let width = 120.0 * BAR_WIDTH + PADDING * 2.0;
+    let height = lines.len() as f32 * LINE_HEIGHT + GRAPH_HEIGHT + PADDING * 3.0;
+    let panel = Rect::from_xywh(bounds.right - width - 8.0, bounds.top + 8.0, width, height);
+    draw_panel(canvas, font, panel, &lines);
+
+    let graph = Rect::from_xywh(
+        panel.left + PADDING,
+        panel.bottom - PADDING - GRAPH_HEIGHT,
+        panel.width() - PADDING * 2.0,
+        GRAPH_HEIGHT,
+    );
+    let refresh = ms(stats.refresh_interval).max(1.0);
+    let max = refresh * GRAPH_RANGE;
+    let mut paint = Paint::default();
+    for (i, time) in stats.recent_frame_times().enumerate() {
+        let time = ms(time);
+        let bar = (time / max).min(1.0) * graph.height();
+        let color = if time > refresh * 1.5 {
+            Color::from_rgb(255, 59, 48)
+        } else {
+            Color::from_rgb(52, 199, 89)
+        };
+        paint.set_color(color);
+        let left = graph.left + i as f32 * BAR_WIDTH;
+        canvas.draw_rect(Rect::from_ltrb(left, graph.bottom - bar, left + BAR_WIDTH, graph.bottom), &paint);
+    }
+    paint.set_color(Color::from_argb(160, 255, 255, 255));
+    let y = graph.bottom - graph.height() / GRAPH_RANGE;
+    canvas.draw_line((graph.left, y), (graph.right, y), &paint);
+}
+
+fn draw_highlight(canvas: &mut Canvas, rect: Rect) {
+    let mut paint = Paint::default();
+    paint.set_color(Color::from_argb(50, 0, 122, 255));
+    canvas.draw_rect(rect, &paint);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_stroke_width(2.0);
+    paint.set_color(Color::from_rgb(0, 122, 255));
+    canvas.draw_rect(rect, &paint);
+}
+
+fn draw_panel(canvas: &mut Canvas, font: &Font, rect: Rect, lines: &[String]) {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_color(Color::from_argb(210, 30, 30, 30));
+    canvas.draw_round_rect(rect, 4.0, 4.0, &paint);
+    paint.set_color(Color::WHITE);
+    for (i, line) in lines.iter().enumerate() {
+        let baseline = rect.top + PADDING + i as f32 * LINE_HEIGHT + FONT_SIZE;
+        canvas.draw_str(line, (rect.left + PADDING, baseline), font, &paint);
+    }
+}
+
+fn ms(duration: Duration) -> f32 {
+    duration.as_secs_f32() * 1000.0
+}
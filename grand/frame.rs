@@ -0,0 +1,204 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// Used when the monitor doesn't report its refresh rate.
+const DEFAULT_REFRESH: Duration = Duration::from_micros(16_667);
+// Frame times kept for averages and graphs.
+const HISTORY: usize = 120;
+
+// Redraw requests are only noted here; the app asks for the actual frame
+// once a refresh interval has passed since the last one, so any number of
+// requests in between become a single frame.
+pub(crate) struct FrameScheduler {
+    interval: Duration,
+    last_frame: Option<Instant>,
+    pending: Cell<bool>,
+}
+
+impl FrameScheduler {
+    pub fn new(interval: Option<Duration>) -> Self {
+        Self {
+            interval: interval.unwrap_or(DEFAULT_REFRESH),
+            last_frame: None,
+            pending: Cell::new(false),
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn set_interval(&mut self, interval: Option<Duration>) {
+        self.interval = interval.unwrap_or(DEFAULT_REFRESH);
+    }
+
+    pub fn last_frame(&self) -> Option<Instant> {
+        self.last_frame
+    }
+
+    pub fn request(&self) {
+        self.pending.set(true);
+    }
+
+    // When the wanted frame may be drawn, if one is wanted.
+    pub fn deadline(&self) -> Option<Instant> {
+        if !self.pending.get() {
+            return None;
+        }
+        Some(match self.last_frame {
+            Some(last) => last + self.interval,
+            None => Instant::now(),
+        })
+    }
+
+    pub fn frame_started(&mut self, now: Instant) {
+        self.pending.set(false);
+        self.last_frame = Some(now);
+    }
+}
+
+// Timings of a window's recent frames, from `Window::frame_stats`.
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    pub frames: u64,
+    // Refreshes missed while the window was animating.
+    pub dropped_frames: u64,
+    // Time between the starts of the last two frames.
+    pub frame_time: Duration,
+    // Drawing the widget tree in the last frame, without presenting.
+    pub paint_time: Duration,
+    // The last layout pass.
+    pub layout_time: Duration,
+    pub refresh_interval: Duration,
+    recent: VecDeque<Duration>,
+}
+
+impl FrameStats {
+    // Frame times, oldest first, for up to the last 120 frames.
+    pub fn recent_frame_times(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.recent.iter().copied()
+    }
+
+    pub fn average_frame_time(&self) -> Duration {
+        if self.recent.is_empty() {
+            return Duration::ZERO;
+        }
+        self.recent.iter().sum::<Duration>() / self.recent.len() as u32
+    }
+
+    pub fn fps(&self) -> f32 {
+        let average = self.average_frame_time().as_secs_f32();
+        if average > 0.0 {
+            1.0 / average
+        } else {
+            0.0
+        }
+    }
+
+    // `continuous` says whether the previous frame wanted this one right
+    // away; only then does a late frame count as dropped refreshes, rather
+    // than the window simply having been idle.
+    pub(crate) fn record_frame(&mut self, since_last: Option<Duration>, continuous: bool, refresh: Duration) {
+        self.frames += 1;
+        self.refresh_interval = refresh;
+        let since_last = match since_last {
+            Some(since_last) => since_last,
+            None => return,
+        };
+        self.frame_time = since_last;
+        if continuous {
+            let refreshes = (since_last.as_secs_f64() / refresh.as_secs_f64()).round() as u64;
+            self.dropped_frames += refreshes.saturating_sub(1);
+            if self.recent.len() == HISTORY {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(since_last);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REFRESH: Duration = Duration::from_millis(10);
+
+    #[test]
+    fn requests_between_frames_share_one_deadline() {
+        let mut scheduler = FrameScheduler::new(Some(REFRESH));
+        assert_eq!(scheduler.deadline(), None);
+
+        // The first frame is due straight away.
+        scheduler.request();
+        assert!(scheduler.deadline().unwrap() <= Instant::now());
+
+        let start = Instant::now();
+        scheduler.frame_started(start);
+        assert_eq!(scheduler.deadline(), None);
+        for _ in 0..3 {
+            scheduler.request();
+            assert_eq!(scheduler.deadline(), Some(start + REFRESH));
+        }
+
+        scheduler.frame_started(start + REFRESH);
+        assert_eq!(scheduler.deadline(), None);
+        assert_eq!(scheduler.last_frame(), Some(start + REFRESH));
+    }
+
+    #[test]
+    fn missing_refresh_rates_use_the_default() {
+        let mut scheduler = FrameScheduler::new(None);
+        assert_eq!(scheduler.interval(), DEFAULT_REFRESH);
+        scheduler.set_interval(Some(REFRESH));
+        assert_eq!(scheduler.interval(), REFRESH);
+    }
+
+    #[test]
+    fn late_continuous_frames_count_missed_refreshes() {
+        let mut stats = FrameStats::default();
+        stats.record_frame(None, false, REFRESH);
+        stats.record_frame(Some(REFRESH), true, REFRESH);
+        assert_eq!(stats.dropped_frames, 0);
+
+        // Three refreshes for one frame: two were missed.
+        stats.record_frame(Some(REFRESH * 3), true, REFRESH);
+        assert_eq!(stats.dropped_frames, 2);
+        // A little jitter isn't a drop.
+        stats.record_frame(Some(REFRESH + Duration::from_millis(2)), true, REFRESH);
+        assert_eq!(stats.dropped_frames, 2);
+
+        assert_eq!(stats.frames, 4);
+        assert_eq!(stats.frame_time, Duration::from_millis(12));
+        assert_eq!(stats.recent_frame_times().count(), 3);
+    }
+
+    #[test]
+    fn idle_gaps_are_not_drops() {
+        let mut stats = FrameStats::default();
+        stats.record_frame(Some(REFRESH), true, REFRESH);
+        stats.record_frame(Some(Duration::from_secs(5)), false, REFRESH);
+        assert_eq!(stats.dropped_frames, 0);
+        assert_eq!(stats.frame_time, Duration::from_secs(5));
+        // Nor do they drag the average down.
+        assert_eq!(stats.recent_frame_times().collect::<Vec<_>>(), vec![REFRESH]);
+        assert_eq!(stats.average_frame_time(), REFRESH);
+        assert_eq!(stats.fps(), 100.0);
+    }
+
+    #[test]
+    fn history_keeps_the_last_120_frames() {
+        let mut stats = FrameStats::default();
+        assert_eq!(stats.fps(), 0.0);
+        for ms in 1..=130 {
+            stats.record_frame(Some(Duration::from_millis(ms)), true, Duration::from_millis(1000));
+        }
+        let recent: Vec<_> = stats.recent_frame_times().collect();
+        assert_eq!(recent.len(), HISTORY);
+        assert_eq!(recent[0], Duration::from_millis(11));
+        assert_eq!(recent[HISTORY - 1], Duration::from_millis(130));
+    }
+}
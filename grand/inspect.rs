@@ -0,0 +1,360 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+// A window's widget tree as plain data, from `Window::inspect`, and the
+// local server that hands the app's windows to external tools.
+
+use crate::widget::overlay::Overlay;
+use crate::widget::theme::{color_hex, Theme};
+use crate::widget::Widget;
+use serde::Serialize;
+use skia_safe::Rect;
+use std::collections::BTreeMap;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WidgetInfo {
+    // Child indices from the root, e.g. "0.2.1". The root is "0"; popups
+    // are "p0", "p1", ... from the bottom. Stable while the tree's shape is.
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    // x, y, width, height in logical units.
+    pub bounds: [f32; 4],
+    pub style: BTreeMap<String, String>,
+    pub state: BTreeMap<String, String>,
+    pub children: Vec<WidgetInfo>,
+}
+
+impl WidgetInfo {
+    pub fn describe(widget: &dyn Widget, id: String) -> Self {
+        let mut children = Vec::new();
+        widget.visit_children(&mut |child| {
+            let child_id = format!("{}.{}", id, children.len());
+            children.push(WidgetInfo::describe(child, child_id));
+        });
+        let b = widget.bounds();
+        Self {
+            id,
+            type_name: widget.type_name().to_string(),
+            bounds: [b.left, b.top, b.width(), b.height()],
+            style: to_map(widget.debug_style()),
+            state: to_map(widget.debug_properties()),
+            children,
+        }
+    }
+
+    pub fn find(&self, id: &str) -> Option<&WidgetInfo> {
+        if self.id == id {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(id))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowInfo {
+    pub title: String,
+    // Logical units.
+    pub size: [f32; 2],
+    pub scale_factor: f64,
+    pub backend: String,
+    pub theme: BTreeMap<String, String>,
+    pub root: WidgetInfo,
+    pub popups: Vec<WidgetInfo>,
+}
+
+impl WindowInfo {
+    pub fn find(&self, id: &str) -> Option<&WidgetInfo> {
+        self.root.find(id).or_else(|| self.popups.iter().find_map(|popup| popup.find(id)))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Widget info always serializes")
+    }
+}
+
+// One line of the `windows` listing.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct WindowSummary {
+    pub id: u64,
+    pub title: String,
+}
+
+fn to_map(pairs: Vec<(&'static str, String)>) -> BTreeMap<String, String> {
+    pairs.into_iter().map(|(name, value)| (name.to_string(), value)).collect()
+}
+
+pub(crate) fn theme_info(theme: &Theme) -> BTreeMap<String, String> {
+    let colors = [
+        ("background", theme.background),
+        ("surface", theme.surface),
+        ("surface_hover", theme.surface_hover),
+        ("surface_pressed", theme.surface_pressed),
+        ("border", theme.border),
+        ("foreground", theme.foreground),
+        ("disabled_foreground", theme.disabled_foreground),
+        ("accent", theme.accent),
+        ("accent_foreground", theme.accent_foreground),
+        ("success", theme.success),
+        ("warning", theme.warning),
+        ("error", theme.error),
+        ("backdrop", theme.backdrop),
+    ];
+    let mut info: BTreeMap<String, String> =
+        colors.iter().map(|(name, color)| (name.to_string(), color_hex(*color))).collect();
+    info.insert("corner_radius".to_string(), theme.corner_radius.to_string());
+    info.insert("font_size".to_string(), theme.font_size.to_string());
+    info
+}
+
+// Bounds of the widget with `id` (see `WidgetInfo::id`), found without
+// describing the whole tree.
+pub(crate) fn find_bounds(root: &dyn Widget, popups: &Overlay, id: &str) -> Option<Rect> {
+    let mut parts = id.split('.');
+    let first = parts.next()?;
+    let path = parts.map(|part| part.parse().ok()).collect::<Option<Vec<usize>>>()?;
+    if first == "0" {
+        return bounds_at(root, &path);
+    }
+    let popup: usize = first.strip_prefix('p')?.parse().ok()?;
+    let mut found = None;
+    let mut index = 0;
+    popups.visit_popups(&mut |widget| {
+        if index == popup {
+            found = bounds_at(widget, &path);
+        }
+        index += 1;
+    });
+    found
+}
+
+fn bounds_at(widget: &dyn Widget, path: &[usize]) -> Option<Rect> {
+    let (&wanted, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return Some(widget.bounds()),
+    };
+    let mut found = None;
+    let mut index = 0;
+    widget.visit_children(&mut |child| {
+        if index == wanted {
+            found = bounds_at(child, rest);
+        }
+        index += 1;
+    });
+    found
+}
+
+pub(crate) enum WindowCommand {
+    Tree,
+    Highlight(String),
+    Clear,
+}
+
+pub(crate) enum Command {
+    Windows,
+    // Aimed at the window with that id, or the main window for `None`.
+    Window(Option<u64>, WindowCommand),
+    Unknown(String),
+}
+
+impl Command {
+    fn parse(line: &str) -> Self {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let window = |word: Option<&&str>| match word {
+            None => Some(None),
+            Some(word) => word.parse().ok().map(Some),
+        };
+        let command = match words.as_slice() {
+            ["windows"] => Some(Command::Windows),
+            ["tree", rest @ ..] if rest.len() <= 1 => {
+                window(rest.first()).map(|w| Command::Window(w, WindowCommand::Tree))
+            }
+            ["clear", rest @ ..] if rest.len() <= 1 => {
+                window(rest.first()).map(|w| Command::Window(w, WindowCommand::Clear))
+            }
+            ["highlight", id, rest @ ..] if rest.len() <= 1 => {
+                window(rest.first()).map(|w| Command::Window(w, WindowCommand::Highlight(id.to_string())))
+            }
+            _ => None,
+        };
+        command.unwrap_or_else(|| Command::Unknown(line.trim().to_string()))
+    }
+}
+
+// A command waiting for the event loop, and where its answer goes.
+struct Request {
+    command: Command,
+    reply: Sender<String>,
+}
+
+// Longest command line a client may send; anything longer drops it.
+const MAX_LINE: usize = 4096;
+
+// Listens on 127.0.0.1 only, so the tree isn't visible off the machine.
+// Tools send one command per line and get one line back:
+//
+//   windows                  the open windows as JSON, `[{"id", "title"}]`
+//   tree [window]            a window as JSON (`WindowInfo`)
+//   highlight <id> [window]  outlines that widget in the window, "ok"
+//   clear [window]           removes the highlight, "ok"
+//
+// Without a window id, commands go to the main window. Failures answer
+// "error: ...".
+//
+// Connections are read on their own threads, which hand each command to the
+// event loop and call `wake` so it gets answered without polling. Replies
+// are written from the connection's thread, so a slow tool never holds up
+// the window.
+pub(crate) struct InspectorServer {
+    port: u16,
+    requests: Receiver<Request>,
+}
+
+impl InspectorServer {
+    pub fn start<F: Fn() + Send + Sync + 'static>(port: u16, wake: F) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        let port = listener.local_addr()?.port();
+        let (sender, requests) = mpsc::channel();
+        let wake: Arc<dyn Fn() + Send + Sync> = Arc::new(wake);
+        thread::Builder::new().name("cocoa-inspector".to_string()).spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (sender, wake) = (sender.clone(), wake.clone());
+                // A connection that fails to get a thread is just dropped.
+                let _ = thread::Builder::new()
+                    .name("cocoa-inspector-client".to_string())
+                    .spawn(move || serve(stream, &sender, wake.as_ref()));
+            }
+        })?;
+        Ok(Self { port, requests })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    // Answers every command that has arrived since the last call.
+    pub fn poll(&self, mut answer: impl FnMut(Command) -> String) {
+        while let Ok(request) = self.requests.try_recv() {
+            // The client may have hung up in the meantime.
+            let _ = request.reply.send(answer(request.command));
+        }
+    }
+}
+
+fn serve(mut stream: TcpStream, requests: &Sender<Request>, wake: &(dyn Fn() + Send + Sync)) -> io::Result<()> {
+    let mut pending = Vec::new();
+    let mut buf = [0u8; 1024];
+    loop {
+        while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            let (reply, answer) = mpsc::channel();
+            let command = Command::parse(&String::from_utf8_lossy(&line));
+            let closed = || io::Error::from(ErrorKind::BrokenPipe);
+            requests.send(Request { command, reply }).map_err(|_| closed())?;
+            wake();
+            let mut answer = answer.recv().map_err(|_| closed())?;
+            answer.push('\n');
+            stream.write_all(answer.as_bytes())?;
+        }
+        if pending.len() > MAX_LINE {
+            return Err(ErrorKind::InvalidData.into());
+        }
+        match stream.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(n) => pending.extend_from_slice(&buf[..n]),
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    fn describe(command: Command) -> String {
+        match command {
+            Command::Windows => "windows".to_string(),
+            Command::Window(window, WindowCommand::Tree) => format!("tree {:?}", window),
+            Command::Window(window, WindowCommand::Highlight(id)) => format!("highlight {} {:?}", id, window),
+            Command::Window(window, WindowCommand::Clear) => format!("clear {:?}", window),
+            Command::Unknown(line) => format!("unknown {}", line),
+        }
+    }
+
+    #[test]
+    fn commands_parse_with_an_optional_window() {
+        assert_eq!(describe(Command::parse("windows\n")), "windows");
+        assert_eq!(describe(Command::parse("tree")), "tree None");
+        assert_eq!(describe(Command::parse("tree 42\r\n")), "tree Some(42)");
+        assert_eq!(describe(Command::parse("highlight 0.1.2")), "highlight 0.1.2 None");
+        assert_eq!(describe(Command::parse("highlight p0 7")), "highlight p0 Some(7)");
+        assert_eq!(describe(Command::parse("clear 7")), "clear Some(7)");
+        assert_eq!(describe(Command::parse("tree main")), "unknown tree main");
+        assert_eq!(describe(Command::parse("highlight")), "unknown highlight");
+        assert_eq!(describe(Command::parse("clear 1 2")), "unknown clear 1 2");
+    }
+
+    // Answers requests until `count` have been seen or a second has passed.
+    fn answer(server: &InspectorServer, count: usize, reply: &str) -> Vec<String> {
+        let mut seen = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while seen.len() < count && Instant::now() < deadline {
+            server.poll(|command| {
+                seen.push(describe(command));
+                reply.to_string()
+            });
+            thread::sleep(Duration::from_millis(5));
+        }
+        seen
+    }
+
+    #[test]
+    fn commands_wake_the_loop_and_get_answered() {
+        let wakes = Arc::new(AtomicUsize::new(0));
+        let counter = wakes.clone();
+        let server = InspectorServer::start(0, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, server.port())).unwrap();
+        stream.write_all(b"tree\nclear 3\n").unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+
+        assert_eq!(answer(&server, 1, "first"), vec!["tree None"]);
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "first\n");
+        assert_eq!(answer(&server, 1, "second"), vec!["clear Some(3)"]);
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "second\n");
+        assert_eq!(wakes.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn clients_that_never_end_a_line_are_dropped() {
+        let server = InspectorServer::start(0, || {}).unwrap();
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, server.port())).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        // The server may hang up before all of it is written.
+        let _ = stream.write_all(&[b'x'; MAX_LINE * 2]);
+        let mut buf = [0u8; 16];
+        let closed = match stream.read(&mut buf) {
+            Ok(0) => true,
+            Err(err) => err.kind() == ErrorKind::ConnectionReset,
+            Ok(_) => false,
+        };
+        assert!(closed);
+        assert!(answer(&server, 1, "").is_empty());
+    }
+}
@@ -2,11 +2,22 @@
 // Licensed under the GNU General Public License v2.0.
 
 mod app;
+pub mod context;
+mod debug;
+mod frame;
+pub mod inspect;
+mod options;
 mod window;
+#[path = "../widget/mod.rs"]
 pub mod widget;
 pub mod renderer;
+#[path = "../event/mod.rs"]
+pub mod event;
 
-pub use app::App;
+pub use app::{App, AppHandle};
+pub use frame::FrameStats;
+pub use inspect::{WidgetInfo, WindowInfo};
+pub use options::{FullscreenMode, WindowOptions};
 pub use window::Window;
 pub use widget::{button::Button, label::Label, Widget};
-pub use renderer::Renderer;
\ No newline at end of file
+pub use renderer::{Backend, MemoryBackend, PresentMode, RenderBackend, Renderer};
\ No newline at end of file
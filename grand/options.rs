@@ -0,0 +1,239 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::event::accel::KeyChord;
+use crate::renderer::{Backend, PresentMode};
+use skia_safe::{AlphaType, ColorType, Data, Image, ImageInfo};
+use winit::{
+    dpi::{LogicalPosition, LogicalSize},
+    event_loop::EventLoopWindowTarget,
+    monitor::MonitorHandle,
+    window::{Fullscreen, Icon, WindowBuilder, WindowLevel},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FullscreenMode {
+    Windowed,
+    Borderless,
+    // Switches the monitor to its native resolution at the highest refresh
+    // rate it offers there.
+    Exclusive,
+}
+
+#[derive(Debug, Clone)]
+pub struct WindowOptions {
+    pub title: String,
+    pub size: (u32, u32),
+    pub min_size: Option<(u32, u32)>,
+    pub max_size: Option<(u32, u32)>,
+    pub position: Option<(i32, i32)>,
+    pub resizable: bool,
+    pub decorations: bool,
+    pub always_on_top: bool,
+    pub transparent: bool,
+    pub fullscreen: FullscreenMode,
+    pub icon: Option<Vec<u8>>,
+    pub backend: Backend,
+    pub present_mode: PresentMode,
+    // Toggles the debug overlay; Ctrl+Shift+D unless changed.
+    pub debug_overlay_chord: Option<KeyChord>,
+}
+
+impl Default for WindowOptions {
+    fn default() -> Self {
+        Self {
+            title: "Cocoa GUI".to_string(),
+            size: (800, 600),
+            min_size: None,
+            max_size: None,
+            position: None,
+            resizable: true,
+            decorations: true,
+            always_on_top: false,
+            transparent: false,
+            fullscreen: FullscreenMode::Windowed,
+            icon: None,
+            backend: Backend::Auto,
+            present_mode: PresentMode::Vsync,
+            debug_overlay_chord: KeyChord::parse("Ctrl+Shift+D"),
+        }
+    }
+}
+
+impl WindowOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    pub fn min_size(mut self, width: u32, height: u32) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    pub fn max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.position = Some((x, y));
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    pub fn always_on_top(mut self, always_on_top: bool) -> Self {
+        self.always_on_top = always_on_top;
+        self
+    }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    pub fn fullscreen(mut self, mode: FullscreenMode) -> Self {
+        self.fullscreen = mode;
+        self
+    }
+
+    // Encoded image bytes (PNG, JPEG, ...). Decoded when the window is built.
+    pub fn icon_from_bytes(mut self, bytes: &[u8]) -> Self {
+        self.icon = Some(bytes.to_vec());
+        self
+    }
+
+    // `COCOA_BACKEND` in the environment takes precedence.
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn present_mode(mut self, mode: PresentMode) -> Self {
+        self.present_mode = mode;
+        self
+    }
+
+    // `None` leaves the overlay to `Window::set_debug_overlay`, e.g. for
+    // release builds.
+    pub fn debug_overlay_chord(mut self, chord: Option<KeyChord>) -> Self {
+        self.debug_overlay_chord = chord;
+        self
+    }
+
+    pub(crate) fn resolved_backend(&self) -> Backend {
+        Backend::from_env().unwrap_or(self.backend)
+    }
+
+    pub(crate) fn to_builder<T>(&self, event_loop: &EventLoopWindowTarget<T>) -> WindowBuilder {
+        let mut builder = WindowBuilder::new()
+            .with_title(self.title.clone())
+            .with_inner_size(LogicalSize::new(self.size.0, self.size.1))
+            .with_resizable(self.resizable)
+            .with_decorations(self.decorations)
+            .with_transparent(self.transparent)
+            .with_window_level(window_level(self.always_on_top))
+            .with_fullscreen(resolve_fullscreen(event_loop, self.fullscreen));
+
+        if let Some((width, height)) = self.min_size {
+            builder = builder.with_min_inner_size(LogicalSize::new(width, height));
+        }
+        if let Some((width, height)) = self.max_size {
+            builder = builder.with_max_inner_size(LogicalSize::new(width, height));
+        }
+        if let Some((x, y)) = self.position {
+            builder = builder.with_position(LogicalPosition::new(x, y));
+        }
+        if let Some(bytes) = &self.icon {
+            builder = builder.with_window_icon(decode_icon(bytes));
+        }
+
+        builder
+    }
+}
+
+pub(crate) fn window_level(always_on_top: bool) -> WindowLevel {
+    if always_on_top {
+        WindowLevel::AlwaysOnTop
+    } else {
+        WindowLevel::Normal
+    }
+}
+
+pub(crate) fn resolve_fullscreen<T>(
+    event_loop: &EventLoopWindowTarget<T>,
+    mode: FullscreenMode,
+) -> Option<Fullscreen> {
+    match mode {
+        FullscreenMode::Windowed => None,
+        FullscreenMode::Borderless => Some(Fullscreen::Borderless(None)),
+        FullscreenMode::Exclusive => event_loop.primary_monitor().and_then(|monitor| exclusive_fullscreen(&monitor)),
+    }
+}
+
+// The mode matching the monitor's current size with the highest refresh
+// rate, or the largest mode if none matches.
+pub(crate) fn exclusive_fullscreen(monitor: &MonitorHandle) -> Option<Fullscreen> {
+    let size = monitor.size();
+    let native = monitor
+        .video_modes()
+        .filter(|mode| mode.size() == size)
+        .max_by_key(|mode| (mode.refresh_rate_millihertz(), mode.bit_depth()));
+    native
+        .or_else(|| {
+            monitor.video_modes().max_by_key(|mode| {
+                let size = mode.size();
+                (size.width as u64 * size.height as u64, mode.refresh_rate_millihertz())
+            })
+        })
+        .map(Fullscreen::Exclusive)
+}
+
+// Returns None for anything Skia can't decode rather than failing the window.
+pub(crate) fn decode_icon(bytes: &[u8]) -> Option<Icon> {
+    let image = Image::from_encoded(Data::new_copy(bytes))?;
+    let (width, height) = (image.width(), image.height());
+    let info = ImageInfo::new((width, height), ColorType::RGBA8888, AlphaType::Unpremul, None);
+
+    let mut rgba = vec![0u8; (width * height * 4) as usize];
+    if !image.read_pixels(
+        &info,
+        &mut rgba,
+        (width * 4) as usize,
+        (0, 0),
+        skia_safe::image::CachingHint::Disallow,
+    ) {
+        return None;
+    }
+
+    Icon::from_rgba(rgba, width as u32, height as u32).ok()
+}
+
+// `COCOA_SCALE_FACTOR=2` (or 1.5, ...) renders as if on a display with that
+// scale, so HiDPI layouts can be checked on a normal screen.
+pub(crate) fn scale_factor_override() -> Option<f64> {
+    std::env::var("COCOA_SCALE_FACTOR")
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|scale| scale.is_finite() && *scale > 0.0)
+}
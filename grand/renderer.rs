@@ -1,11 +1,196 @@
 // Copyright 2025 Bluegill Studios.
 // Licensed under the GNU General Public License v2.0.
 
-use skia_safe::{Surface, Canvas, Color};
+#[path = "../renderer/glskiarender.rs"]
+pub mod glskiarender;
+#[path = "../renderer/memory.rs"]
+pub mod memory;
+#[path = "../renderer/software.rs"]
+pub mod software;
+#[cfg(feature = "vulkan")]
+#[path = "../renderer/vulkan.rs"]
+pub mod vulkan;
+
+use skia_safe::{AlphaType, Canvas, Color, ColorType, Font, ImageInfo, Paint, PaintStyle, Rect, Surface, Typeface};
 use winit::window::Window;
-use skia_safe::{Canvas, Paint, PaintStyle, Color, Rect, Font, Typeface};
+use crate::widget::icon::{draw_icon, icon_label_layout};
+use crate::widget::Widget;
+
+pub use glskiarender::GpuSkiaRenderer;
+pub use memory::MemoryBackend;
+use software::SoftwarePresenter;
+#[cfg(feature = "vulkan")]
+pub use vulkan::VulkanRenderer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    // GL if it works, otherwise software.
+    #[default]
+    Auto,
+    Gl,
+    Software,
+    // Needs the `vulkan` cargo feature.
+    #[cfg(feature = "vulkan")]
+    Vulkan,
+    // Draws into memory and shows nothing, for tests.
+    Memory,
+}
+
+impl Backend {
+    // `COCOA_BACKEND=gl|software|memory` overrides what the app asked for,
+    // to rule the GPU in or out when debugging in the field.
+    pub(crate) fn from_env() -> Option<Backend> {
+        match std::env::var("COCOA_BACKEND").ok()?.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Backend::Auto),
+            "gl" => Some(Backend::Gl),
+            "software" => Some(Backend::Software),
+            #[cfg(feature = "vulkan")]
+            "vulkan" => Some(Backend::Vulkan),
+            "memory" => Some(Backend::Memory),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PresentMode {
+    // Waits for vertical blank: no tearing, at most one frame per refresh.
+    #[default]
+    Vsync,
+    // Like `Vsync`, but a frame that misses its refresh is shown right away
+    // (and may tear) instead of waiting for the next one.
+    Adaptive,
+    // Never waits. Lowest latency; may tear.
+    Immediate,
+}
+
+// Where frames are drawn and how they reach the screen. Every frame is
+// `begin_frame`, drawing on `canvas`, then `present`.
+pub trait RenderBackend {
+    fn name(&self) -> &'static str;
+    // Physical pixels.
+    fn size(&self) -> (u32, u32);
+    fn resize(&mut self, width: u32, height: u32);
+    // Starts a frame, cleared to white.
+    fn begin_frame(&mut self);
+    fn canvas(&mut self) -> &mut Canvas;
+    // Shows what was drawn since `begin_frame`.
+    fn present(&mut self);
+
+    // Backends that don't present to a display ignore this.
+    fn set_present_mode(&mut self, _mode: PresentMode) {}
+}
+
+// Creates the window's surface with `backend`. `Auto` falls back to
+// software rendering when GL setup fails (`Renderer::backend_name` says
+// which one it got); asking for GL or Vulkan explicitly fails instead.
+pub fn create_backend(window: &Window, backend: Backend) -> Result<Box<dyn RenderBackend>, String> {
+    match backend {
+        Backend::Auto => match GpuSkiaRenderer::new(window) {
+            Ok(renderer) => Ok(Box::new(renderer)),
+            Err(gl_err) => create_backend(window, Backend::Software)
+                .map_err(|err| format!("no OpenGL ({}) and no software rendering ({})", gl_err, err)),
+        },
+        Backend::Gl => match GpuSkiaRenderer::new(window) {
+            Ok(renderer) => Ok(Box::new(renderer)),
+            Err(err) => Err(format!("OpenGL rendering was requested but is unavailable: {}", err)),
+        },
+        Backend::Software => match SkiaRenderer::new(window) {
+            Ok(renderer) => Ok(Box::new(renderer)),
+            Err(err) => Err(format!("software rendering is unavailable: {}", err)),
+        },
+        #[cfg(feature = "vulkan")]
+        Backend::Vulkan => match VulkanRenderer::new(window) {
+            Ok(renderer) => Ok(Box::new(renderer)),
+            Err(err) => Err(format!("Vulkan rendering was requested but is unavailable: {}", err)),
+        },
+        Backend::Memory => {
+            let size = window.inner_size();
+            Ok(Box::new(MemoryBackend::new(size.width, size.height)))
+        }
+    }
+}
+
+// BGRA so frames can be copied to softbuffer without swizzling.
+pub(crate) fn raster_surface(width: u32, height: u32) -> Option<Surface> {
+    let info = ImageInfo::new(
+        (width.max(1) as i32, height.max(1) as i32),
+        ColorType::BGRA8888,
+        AlphaType::Premul,
+        None,
+    );
+    Surface::new_raster(&info, None, None)
+}
+
+// Draws a window's frames through whichever backend it was created with,
+// plus the drawing helpers that go with them.
+pub struct Renderer {
+    backend: Box<dyn RenderBackend>,
+    in_frame: bool,
+    present_mode: PresentMode,
+}
 
 impl Renderer {
+    pub fn new(backend: Box<dyn RenderBackend>) -> Self {
+        Self {
+            backend,
+            in_frame: false,
+            present_mode: PresentMode::Vsync,
+        }
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.backend.size()
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.backend.resize(width, height);
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    pub fn set_present_mode(&mut self, mode: PresentMode) {
+        self.present_mode = mode;
+        self.backend.set_present_mode(mode);
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.backend.begin_frame();
+        self.in_frame = true;
+    }
+
+    // The frame's canvas, between `begin_frame` and `end_frame`.
+    pub fn canvas(&mut self) -> Option<&mut Canvas> {
+        if self.in_frame {
+            Some(self.backend.canvas())
+        } else {
+            None
+        }
+    }
+
+    pub fn end_frame(&mut self) {
+        if self.in_frame {
+            self.in_frame = false;
+            self.backend.present();
+        }
+    }
+
+    pub fn draw_with<F: FnOnce(&mut Canvas)>(&mut self, paint: F) {
+        self.begin_frame();
+        paint(self.backend.canvas());
+        self.end_frame();
+    }
+
+    pub fn draw(&mut self, root_widget: &mut dyn Widget) {
+        self.draw_with(|canvas| root_widget.draw(canvas));
+    }
+
     pub fn draw_button(
         &mut self,
         x: f32,
@@ -15,7 +200,13 @@ impl Renderer {
         label: &str,
         is_pressed: bool,
     ) {
-        if let Some(canvas) = self.canvas.as_mut() {
+        self.draw_button_with_icon(Rect::from_xywh(x, y, width, height), None, label, is_pressed);
+    }
+
+    // Same as `draw_button`, with a bundled icon (see `widget::icon`) drawn
+    // to the left of the label. The icon and label are centred together.
+    pub fn draw_button_with_icon(&mut self, button_rect: Rect, icon: Option<&str>, label: &str, is_pressed: bool) {
+        if let Some(canvas) = self.canvas() {
             let mut bg_paint = Paint::default();
             bg_paint.set_anti_alias(true);
 
@@ -28,8 +219,7 @@ impl Renderer {
             bg_paint.set_color(Color::from_rgb(r, g, b));
             bg_paint.set_style(PaintStyle::Fill);
 
-            let button_rect = Rect::from_xywh(x, y, width, height);
-            canvas.draw_rounded_rect(button_rect, 6.0, 6.0, &bg_paint);
+            canvas.draw_round_rect(button_rect, 6.0, 6.0, &bg_paint);
 
             // Draw border
             let mut border_paint = Paint::default();
@@ -37,7 +227,7 @@ impl Renderer {
             border_paint.set_color(Color::from_rgb(160, 160, 160));
             border_paint.set_style(PaintStyle::Stroke);
             border_paint.set_stroke_width(1.0);
-            canvas.draw_rounded_rect(button_rect, 6.0, 6.0, &border_paint);
+            canvas.draw_round_rect(button_rect, 6.0, 6.0, &border_paint);
 
             // Draw label
             let typeface = Typeface::default();
@@ -46,45 +236,71 @@ impl Renderer {
             text_paint.set_anti_alias(true);
             text_paint.set_color(Color::BLACK);
 
-            let text_x = x + (width - font.measure_str(label, Some(&text_paint)).1.width) / 2.0;
-            let text_y = y + height / 2.0 + 5.0;
+            let text_width = font.measure_str(label, Some(&text_paint)).0;
+            let (icon_rect, text_x) = icon_label_layout(button_rect, icon.is_some(), text_width);
+            if let (Some(name), Some(icon_rect)) = (icon, icon_rect) {
+                draw_icon(canvas, name, icon_rect, Color::BLACK);
+            }
+
+            let text_y = button_rect.center_y() + 5.0;
             canvas.draw_str(label, (text_x, text_y), &font, &text_paint);
         }
     }
 }
 
+// Draws on the CPU into a raster surface and hands each frame to softbuffer.
 pub struct SkiaRenderer {
     surface: Surface,
+    presenter: SoftwarePresenter,
     width: u32,
     height: u32,
 }
 
 impl SkiaRenderer {
-    pub fn new(window: &Window) -> Self {
+    pub fn new(window: &Window) -> Result<Self, String> {
         let size = window.inner_size();
-        let width = size.width;
-        let height = size.height;
-        // Create a new Skia surface.
-        let surface = Surface::new_raster_n32_premul((width as i32, height as i32))
-            .expect("Failed to create Skia surface");
+        let surface = raster_surface(size.width, size.height)
+            .ok_or_else(|| "failed to create Skia raster surface".to_string())?;
+        let presenter = SoftwarePresenter::new(window)?;
 
-        Self { surface, width, height }
+        Ok(Self {
+            surface,
+            presenter,
+            width: size.width,
+            height: size.height,
+        })
     }
+}
 
-    pub fn resize(&mut self, width: u32, height: u32) {
+impl RenderBackend for SkiaRenderer {
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
         if width == self.width && height == self.height {
             return;
         }
-        self.surface = Surface::new_raster_n32_premul((width as i32, height as i32))
-            .expect("Failed to resize Skia surface");
-        self.width = width;
-        self.height = height;
+        if let Some(surface) = raster_surface(width, height) {
+            self.surface = surface;
+            self.width = width;
+            self.height = height;
+        }
+    }
+
+    fn begin_frame(&mut self) {
+        self.surface.canvas().clear(Color::WHITE);
+    }
+
+    fn canvas(&mut self) -> &mut Canvas {
+        self.surface.canvas()
     }
 
-    pub fn draw(&mut self, widget: &mut dyn crate::widget::Widget) {
-        let canvas: &mut Canvas = self.surface.canvas();
-        canvas.clear(Color::WHITE);
-        widget.draw(canvas);
-        self.surface.flush_and_submit();
+    fn present(&mut self) {
+        self.presenter.present(&mut self.surface);
     }
 }
@@ -2,187 +2,103 @@
 // Licensed under the GNU General Public License v2.0.
 
 use winit::{
-    dpi::{LogicalSize, PhysicalSize},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
     event::WindowEvent,
-    window::{Window as WinitWindow, WindowBuilder},
+    window::{CursorIcon, Fullscreen, Window as WinitWindow},
     event_loop::EventLoopWindowTarget,
 };
 
-use crate::widget::Widget;
-
-use glutin::{
-    ContextBuilder, PossiblyCurrent, window::WindowBuilder as GlutinWindowBuilder, ContextWrapper,
-};
-use skia_safe::{
-    gpu::{BackendRenderTarget, DirectContext, SurfaceOrigin},
-    ColorType, Surface, Color, Paint, PaintStyle, Rect, Font, Typeface,
-};
-use winit::event_loop::EventLoop;
-
-pub struct GpuSkiaRenderer {
-    context: ContextWrapper<PossiblyCurrent, winit::window::Window>,
-    gr_context: DirectContext,
-    surface: Surface,
-    width: u32,
-    height: u32,
-}
-
-impl GpuSkiaRenderer {
-    pub fn new(winit_window: &winit::window::Window) -> Self {
-        let size = winit_window.inner_size();
-        let event_loop = winit_window
-            .event_loop()
-            .expect("Failed to get event loop from window");
-
-        // Since we need the EventLoop to build Glutin context, 
-        // it's better to pass the EventLoop separately to Window::new (see below).
-        // For now, this function assumes the caller provides it.
-
-        panic!("Use Window::new_with_event_loop instead to create GpuSkiaRenderer"); // If this ever goes off, it's fucked.
-    }
-
-    pub fn new_with_event_loop<T>(
-        event_loop: &EventLoopWindowTarget<T>,
-        title: &str,
-        width: u32,
-        height: u32,
-    ) -> (Self, winit::window::Window) {
-        // Build Glutin context with window
-        let wb = GlutinWindowBuilder::new()
-            .with_title(title)
-            .with_inner_size(PhysicalSize::new(width, height));
-
-        let windowed_context = ContextBuilder::new()
-            .with_vsync(true)
-            .build_windowed(wb, event_loop)
-            .expect("Failed to build windowed context")
-            .make_current()
-            .expect("Failed to make context current");
-
-        let winit_window = windowed_context.window().clone();
-
-        let gl = glow::Context::from_loader_function(|s| {
-            windowed_context.get_proc_address(s) as *const _
-        });
-
-        let gr_context = DirectContext::new_gl(Some(&gl), None)
-            .expect("Failed to create GrDirectContext");
-
-        let fb_info = {
-            use glutin::platform::ContextTraitExt;
-            let fboid = unsafe { gl.GetInteger(glow::FRAMEBUFFER_BINDING) };
-            skia_safe::gpu::gl::FramebufferInfo {
-                fboid: fboid.try_into().unwrap(),
-                format: skia_safe::gpu::gl::Format::RGBA8.into(),
-            }
-        };
-
-        let backend_render_target = BackendRenderTarget::new_gl(
-            (width as i32, height as i32),
-            0,
-            8,
-            fb_info,
-        );
-
-        let surface = Surface::from_backend_render_target(
-            &gr_context,
-            &backend_render_target,
-            SurfaceOrigin::BottomLeft,
-            ColorType::RGBA8888,
-            None,
-            None,
-        )
-        .expect("Failed to create Skia Surface");
-
-        (
-            Self {
-                context: windowed_context,
-                gr_context,
-                surface,
-                width,
-                height,
-            },
-            winit_window,
-        )
-    }
-
-    pub fn resize(&mut self, width: u32, height: u32) {
-        if width == 0 || height == 0 {
-            return; // Ignore zero-sized windows
-        }
-
-        self.width = width;
-        self.height = height;
-
-        let fb_info = {
-            use glutin::platform::ContextTraitExt;
-            let gl = glow::Context::from_loader_function(|s| {
-                self.context.get_proc_address(s) as *const _
-            });
-            let fboid = unsafe { gl.GetInteger(glow::FRAMEBUFFER_BINDING) };
-            skia_safe::gpu::gl::FramebufferInfo {
-                fboid: fboid.try_into().unwrap(),
-                format: skia_safe::gpu::gl::Format::RGBA8.into(),
-            }
-        };
-
-        let backend_render_target = BackendRenderTarget::new_gl(
-            (width as i32, height as i32),
-            0,
-            8,
-            fb_info,
-        );
-
-        self.surface = Surface::from_backend_render_target(
-            &mut self.gr_context,
-            &backend_render_target,
-            SurfaceOrigin::BottomLeft,
-            ColorType::RGBA8888,
-            None,
-            None,
-        )
-        .expect("Failed to recreate Skia Surface");
-    }
-
-    pub fn draw(&mut self, root_widget: &mut dyn Widget) {
-        let canvas = self.surface.canvas();
-
-        // Clear background white
-        canvas.clear(Color::WHITE);
-
-        // Draw the widget tree on the canvas
-        root_widget.render(canvas);
-
-        // Flush drawing commands and swap buffers
-        self.surface.flush_and_submit();
-        self.context.swap_buffers().unwrap();
-    }
+use crate::context;
+use crate::debug::DebugOverlay;
+use crate::event::accel::{Accelerators, KeyChord};
+use crate::event::{translate_event, InputState};
+use crate::frame::{FrameScheduler, FrameStats};
+use crate::inspect::{find_bounds, theme_info, WidgetInfo, WindowCommand, WindowInfo};
+use crate::options::{decode_icon, exclusive_fullscreen, scale_factor_override, window_level, FullscreenMode, WindowOptions};
+use crate::renderer::{create_backend, PresentMode, Renderer};
+use crate::widget::dialog::Dialog;
+use crate::widget::overlay::{Overlay, Popup};
+use crate::widget::{theme, Widget};
+
+use skia_safe::Rect;
+use std::time::{Duration, Instant};
+
+// One refresh of the monitor the window is on, if it says.
+fn refresh_interval(window: &WinitWindow) -> Option<Duration> {
+    let millihertz = window.current_monitor()?.refresh_rate_millihertz()?;
+    (millihertz > 0).then(|| Duration::from_secs_f64(1000.0 / millihertz as f64))
 }
 
 pub struct Window {
     winit_window: WinitWindow,
-    renderer: GpuSkiaRenderer,
+    renderer: Renderer,
     root_widget: Box<dyn Widget>,
+    overlay: Overlay,
+    accelerators: Accelerators,
+    input: InputState,
+    animating: bool,
+    cursor: CursorIcon,
+    // Physical pixels; widgets are laid out in logical units, i.e. this
+    // divided by `scale_factor`.
     size: PhysicalSize<u32>,
+    scale_factor: f64,
+    scheduler: FrameScheduler,
+    stats: FrameStats,
+    debug: DebugOverlay,
+    debug_chord: Option<KeyChord>,
 }
 
 impl Window {
     pub fn new<T>(
         event_loop: &EventLoopWindowTarget<T>,
-        title: &str,
-        width: u32,
-        height: u32,
+        options: &WindowOptions,
         root_widget: Box<dyn Widget>,
-    ) -> Self {
-        let (renderer, winit_window) = GpuSkiaRenderer::new_with_event_loop(event_loop, title, width, height);
+    ) -> Result<Self, String> {
+        let winit_window = options
+            .to_builder(event_loop)
+            .build(event_loop)
+            .map_err(|err| format!("failed to create window: {}", err))?;
+        let mut renderer = Renderer::new(create_backend(&winit_window, options.resolved_backend())?);
+        renderer.set_present_mode(options.present_mode);
+        let scheduler = FrameScheduler::new(refresh_interval(&winit_window));
         let size = winit_window.inner_size();
+        let scale_factor = scale_factor_override().unwrap_or_else(|| winit_window.scale_factor());
 
-        Self {
+        let mut window = Self {
             winit_window,
             renderer,
             root_widget,
+            overlay: Overlay::new(),
+            accelerators: Accelerators::new(),
+            input: InputState {
+                scale_factor,
+                ..InputState::default()
+            },
+            animating: false,
+            cursor: CursorIcon::Default,
             size,
-        }
+            scale_factor,
+            scheduler,
+            stats: FrameStats::default(),
+            debug: DebugOverlay::new(),
+            debug_chord: options.debug_overlay_chord,
+        };
+        window.layout();
+        Ok(window)
+    }
+
+    fn bounds(&self) -> Rect {
+        let (width, height) = self.logical_size();
+        Rect::from_wh(width, height)
+    }
+
+    fn layout(&mut self) {
+        let start = Instant::now();
+        let bounds = self.bounds();
+        self.root_widget.layout(bounds);
+        self.overlay.layout(bounds);
+        self.stats.layout_time = start.elapsed();
+        self.apply_requests();
     }
 
     pub fn id(&self) -> winit::window::WindowId {
@@ -190,31 +106,342 @@ impl Window {
     }
 
     pub fn handle_event(&mut self, event: &WindowEvent) {
-        use winit::event::WindowEvent;
-
         match event {
             WindowEvent::Resized(new_size) => {
                 self.size = *new_size;
                 self.renderer.resize(new_size.width, new_size.height);
-                self.winit_window.request_redraw();
+                self.layout();
+                self.scheduler.request();
+            }
+            WindowEvent::Moved(_) => {
+                // Possibly onto a monitor with another refresh rate.
+                self.scheduler.set_interval(refresh_interval(&self.winit_window));
             }
-            WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+            WindowEvent::ScaleFactorChanged { scale_factor, new_inner_size } => {
+                if scale_factor_override().is_none() {
+                    self.scale_factor = *scale_factor;
+                    self.input.scale_factor = *scale_factor;
+                }
                 self.size = **new_inner_size;
                 self.renderer.resize(new_inner_size.width, new_inner_size.height);
-                self.winit_window.request_redraw();
+                self.layout();
+                self.scheduler.request();
             }
             _ => {
-                // Forward other events to the widget tree
-                self.root_widget.handle_event(event);
+                if let Some(event) = translate_event(event, &mut self.input) {
+                    self.dispatch(&event);
+                }
+            }
+        }
+    }
+
+    fn dispatch(&mut self, event: &crate::event::Event) -> bool {
+        if self.debug_chord.is_some_and(|chord| chord.matches(event)) {
+            self.set_debug_overlay(!self.debug.is_enabled());
+            return true;
+        }
+        // Accelerators fire regardless of focus, unless a modal popup is up.
+        // After that, popups are hit-tested before the main tree.
+        if let crate::event::Event::MouseMove { .. } = event {
+            context::set_cursor(CursorIcon::Default);
+            // The overlay describes whatever is under the pointer.
+            if self.debug.is_enabled() {
+                self.scheduler.request();
+            }
+        }
+        let handled = (!self.overlay.has_modal() && self.accelerators.handle(event))
+            || self.overlay.on_event(event)
+            || self.root_widget.on_event(event);
+        self.apply_requests();
+        if handled {
+            self.scheduler.request();
+        }
+        handled
+    }
+
+    fn apply_requests(&mut self) {
+        let requests = context::take_requests();
+        if requests.redraw {
+            self.scheduler.request();
+        }
+        if requests.animate {
+            self.animating = true;
+            self.scheduler.request();
+        }
+        for (chord, command) in requests.accelerators {
+            self.accelerators.bind(chord, command);
+        }
+        for id in requests.dismissed {
+            self.overlay.dismiss(id);
+            self.scheduler.request();
+        }
+        for popup in requests.popups {
+            self.show_popup(popup);
+        }
+        if let Some(cursor) = requests.cursor {
+            if cursor != self.cursor {
+                self.cursor = cursor;
+                self.winit_window.set_cursor_icon(cursor);
             }
         }
+        for rect in requests.scroll_into_view {
+            self.dispatch(&crate::event::Event::ScrollIntoView(rect));
+        }
+    }
+
+    pub fn show_popup(&mut self, popup: Popup) {
+        self.overlay.show(popup);
+        self.scheduler.request();
+    }
+
+    pub fn show_dialog(&mut self, dialog: Dialog) {
+        self.show_popup(Popup::modal(dialog.id(), Box::new(dialog)));
+    }
+
+    pub fn bind_accelerator<F: Fn() + 'static>(&mut self, chord: KeyChord, f: F) {
+        self.accelerators.bind(chord, std::rc::Rc::new(f));
+    }
+
+    pub fn unbind_accelerator(&mut self, chord: KeyChord) {
+        self.accelerators.unbind(chord);
+    }
+
+    pub fn has_modal(&self) -> bool {
+        self.overlay.has_modal()
     }
 
     pub fn render(&mut self) {
-        self.renderer.draw(self.root_widget.as_mut());
+        let now = Instant::now();
+        let since_last = self.scheduler.last_frame().map(|last| now - last);
+        self.stats.record_frame(since_last, self.animating, self.scheduler.interval());
+        self.scheduler.frame_started(now);
+        // Widgets that are still animating ask again while drawing.
+        self.animating = false;
+
+        let bounds = self.bounds();
+        let root_widget = self.root_widget.as_mut();
+        let overlay = &mut self.overlay;
+        let debug = &mut self.debug;
+        let stats = &self.stats;
+        let cursor = self.input.cursor;
+        let scale = self.scale_factor as f32;
+        let mut paint_time = Duration::ZERO;
+        let mut debug_animating = false;
+        self.renderer.draw_with(|canvas| {
+            let start = Instant::now();
+            // Widgets draw in logical units; the surface is physical pixels.
+            canvas.save();
+            canvas.scale((scale, scale));
+            root_widget.draw(canvas);
+            overlay.draw(canvas);
+            // Not counting the debug overlay's own drawing.
+            paint_time = start.elapsed();
+            debug_animating = debug.draw(canvas, root_widget, overlay, cursor, stats, bounds);
+            canvas.restore();
+        });
+        self.stats.paint_time = paint_time;
+        if debug_animating {
+            context::request_animation_frame();
+        }
+        self.apply_requests();
+    }
+
+    pub fn debug_overlay(&self) -> bool {
+        self.debug.is_enabled()
+    }
+
+    pub fn set_debug_overlay(&mut self, enabled: bool) {
+        self.debug.set_enabled(enabled);
+        self.scheduler.request();
+    }
+
+    // The widget tree as it was last laid out, for debugging tools.
+    pub fn inspect(&self) -> WindowInfo {
+        let (width, height) = self.logical_size();
+        let mut popups = Vec::new();
+        self.overlay.visit_popups(&mut |popup| {
+            let id = format!("p{}", popups.len());
+            popups.push(WidgetInfo::describe(popup, id));
+        });
+        WindowInfo {
+            title: self.title(),
+            size: [width, height],
+            scale_factor: self.scale_factor,
+            backend: self.backend_name().to_string(),
+            theme: theme_info(&theme::current()),
+            root: WidgetInfo::describe(self.root_widget.as_ref(), "0".to_string()),
+            popups,
+        }
+    }
+
+    // Answers an inspector command aimed at this window.
+    pub(crate) fn inspector_command(&mut self, command: WindowCommand) -> String {
+        match command {
+            WindowCommand::Tree => self.inspect().to_json(),
+            WindowCommand::Highlight(id) => {
+                if find_bounds(self.root_widget.as_ref(), &self.overlay, &id).is_none() {
+                    return format!("error: no widget {}", id);
+                }
+                self.debug.set_highlight(Some(id));
+                self.scheduler.request();
+                "ok".to_string()
+            }
+            WindowCommand::Clear => {
+                self.debug.set_highlight(None);
+                self.scheduler.request();
+                "ok".to_string()
+            }
+        }
+    }
+
+    pub fn frame_stats(&self) -> FrameStats {
+        self.stats.clone()
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        self.renderer.present_mode()
+    }
+
+    pub fn set_present_mode(&mut self, mode: PresentMode) {
+        self.renderer.set_present_mode(mode);
+        self.scheduler.request();
     }
 
+    // When the app should wake up to draw this window, if it wants a frame.
+    pub(crate) fn frame_deadline(&self) -> Option<Instant> {
+        self.scheduler.deadline()
+    }
+
+    // Asks winit for the wanted frame once its refresh slot has come.
+    pub(crate) fn request_frame_if_due(&self, now: Instant) {
+        if self.scheduler.deadline().is_some_and(|deadline| deadline <= now) {
+            self.winit_window.request_redraw();
+        }
+    }
+
+    // "gl", "vulkan", "software" or "memory".
+    pub fn backend_name(&self) -> &'static str {
+        self.renderer.backend_name()
+    }
+
+    pub fn wants_animation_frame(&self) -> bool {
+        self.animating
+    }
+
+    // Coalesced: however often it is called, the window draws at most once
+    // per refresh.
     pub fn request_redraw(&self) {
-        self.winit_window.request_redraw();
+        self.scheduler.request();
+    }
+
+    pub fn title(&self) -> String {
+        self.winit_window.title()
+    }
+
+    pub fn set_title(&self, title: &str) {
+        self.winit_window.set_title(title);
+    }
+
+    // Sizes and positions below are in logical units, the ones widgets are
+    // laid out in: physical pixels divided by `scale_factor`, so they follow
+    // `COCOA_SCALE_FACTOR` too.
+    pub fn inner_size(&self) -> (u32, u32) {
+        let (width, height) = self.logical_size();
+        (width.round() as u32, height.round() as u32)
+    }
+
+    // Physical pixels per logical unit, from the display or
+    // `COCOA_SCALE_FACTOR`. May be fractional.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    // The size widgets are laid out in.
+    pub fn logical_size(&self) -> (f32, f32) {
+        let scale = self.scale_factor as f32;
+        (self.size.width as f32 / scale, self.size.height as f32 / scale)
+    }
+
+    // The drawable size in device pixels.
+    pub fn physical_size(&self) -> PhysicalSize<u32> {
+        self.size
+    }
+
+    pub fn set_inner_size(&self, width: u32, height: u32) {
+        self.winit_window.set_inner_size(self.to_physical(width, height));
+    }
+
+    pub fn set_min_size(&self, size: Option<(u32, u32)>) {
+        self.winit_window
+            .set_min_inner_size(size.map(|(w, h)| self.to_physical(w, h)));
+    }
+
+    pub fn set_max_size(&self, size: Option<(u32, u32)>) {
+        self.winit_window
+            .set_max_inner_size(size.map(|(w, h)| self.to_physical(w, h)));
+    }
+
+    pub fn position(&self) -> Option<(i32, i32)> {
+        let position = self.winit_window.outer_position().ok()?;
+        let position = position.to_logical::<i32>(self.scale_factor);
+        Some((position.x, position.y))
+    }
+
+    pub fn set_position(&self, x: i32, y: i32) {
+        let position: PhysicalPosition<i32> = LogicalPosition::new(x, y).to_physical(self.scale_factor);
+        self.winit_window.set_outer_position(position);
+    }
+
+    // Goes through our own scale factor rather than winit's, which doesn't
+    // know about `COCOA_SCALE_FACTOR`.
+    fn to_physical(&self, width: u32, height: u32) -> PhysicalSize<u32> {
+        LogicalSize::new(width, height).to_physical(self.scale_factor)
+    }
+
+    pub fn is_resizable(&self) -> bool {
+        self.winit_window.is_resizable()
+    }
+
+    pub fn set_resizable(&self, resizable: bool) {
+        self.winit_window.set_resizable(resizable);
+    }
+
+    pub fn is_decorated(&self) -> bool {
+        self.winit_window.is_decorated()
+    }
+
+    pub fn set_decorations(&self, decorations: bool) {
+        self.winit_window.set_decorations(decorations);
+    }
+
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        self.winit_window.set_window_level(window_level(always_on_top));
+    }
+
+    pub fn set_transparent(&self, transparent: bool) {
+        self.winit_window.set_transparent(transparent);
+    }
+
+    pub fn fullscreen(&self) -> FullscreenMode {
+        match self.winit_window.fullscreen() {
+            None => FullscreenMode::Windowed,
+            Some(Fullscreen::Borderless(_)) => FullscreenMode::Borderless,
+            Some(Fullscreen::Exclusive(_)) => FullscreenMode::Exclusive,
+        }
+    }
+
+    pub fn set_fullscreen(&self, mode: FullscreenMode) {
+        let fullscreen = match mode {
+            FullscreenMode::Windowed => None,
+            FullscreenMode::Borderless => Some(Fullscreen::Borderless(None)),
+            FullscreenMode::Exclusive => {
+                self.winit_window.current_monitor().and_then(|monitor| exclusive_fullscreen(&monitor))
+            }
+        };
+        self.winit_window.set_fullscreen(fullscreen);
+    }
+
+    pub fn set_icon_from_bytes(&self, bytes: &[u8]) {
+        self.winit_window.set_window_icon(decode_icon(bytes));
     }
 }
@@ -2,38 +2,64 @@
 // Licensed under the GNU General Public License v2.0.
 
 use glutin::{
-    config::{ConfigTemplateBuilder, Config},
+    config::ConfigTemplateBuilder,
     context::{ContextApi, ContextAttributesBuilder, PossiblyCurrentContext},
     display::{Display, DisplayApiPreference},
     prelude::*,
-    surface::{Surface, SurfaceAttributesBuilder, WindowSurface},
+    surface::{Surface, SurfaceAttributesBuilder, SwapInterval, WindowSurface},
 };
-use skia_safe::{
-    gpu, Color, Surface as SkSurface, Canvas,
-};
-use winit::{
-    dpi::PhysicalSize,
-    window::Window,
-};
-use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use skia_safe::{gpu, Canvas, Color, Surface as SkSurface};
+use std::ffi::CString;
+use std::num::NonZeroU32;
+use winit::window::Window;
+
+use crate::renderer::{PresentMode, RenderBackend};
 
 pub struct GpuSkiaRenderer {
     gl_context: PossiblyCurrentContext,
-    surface: Surface,
+    surface: Surface<WindowSurface>,
     gr_context: gpu::DirectContext,
     skia_surface: SkSurface,
     width: i32,
     height: i32,
+    // The last swap failed; rebuild the surfaces before drawing again.
+    stale: bool,
+}
+
+// Zero-sized windows (e.g. minimised) still need a surface; GL gets 1x1.
+fn non_zero(value: u32) -> NonZeroU32 {
+    NonZeroU32::new(value.max(1)).expect("value is at least 1")
+}
+
+fn skia_surface(gr_context: &mut gpu::DirectContext, width: i32, height: i32) -> Option<SkSurface> {
+    let fb_info = gpu::gl::FramebufferInfo {
+        fboid: 0, // default framebuffer
+        format: gpu::gl::Format::RGBA8.into(),
+    };
+    let backend_render_target = gpu::BackendRenderTarget::new_gl(
+        (width, height),
+        0, // sample count
+        8, // stencil bits
+        fb_info,
+    );
+    SkSurface::from_backend_render_target(
+        gr_context,
+        &backend_render_target,
+        gpu::SurfaceOrigin::BottomLeft,
+        skia_safe::ColorType::RGBA8888,
+        None,
+        None,
+    )
 }
 
 impl GpuSkiaRenderer {
-    pub fn new(window: &Window) -> Self {
+    // Fails rather than panicking when the machine has no usable GL (VMs,
+    // remote desktops), so the caller can fall back to software rendering.
+    pub fn new(window: &Window) -> Result<Self, String> {
         // Create display (platform-dependent)
-        let raw_display_handle = window.raw_display_handle();
-        let display = unsafe {
-            Display::new(raw_display_handle, DisplayApiPreference::Egl)
-                .expect("Failed to create glutin Display")
-        };
+        let display = unsafe { Display::new(window.raw_display_handle(), DisplayApiPreference::Egl) }
+            .map_err(|err| format!("no EGL display: {}", err))?;
 
         // Choose OpenGL config
         let template = ConfigTemplateBuilder::new()
@@ -42,12 +68,10 @@ impl GpuSkiaRenderer {
             .with_stencil_size(8)
             .with_transparency(false)
             .build();
-        let config = unsafe {
-            display.find_configs(template)
-                .unwrap()
-                .next()
-                .expect("Failed to find a matching GL config")
-        };
+        let config = unsafe { display.find_configs(template) }
+            .map_err(|err| format!("no GL configs: {}", err))?
+            .next()
+            .ok_or_else(|| "no matching GL config".to_string())?;
 
         // Create OpenGL context attributes
         let raw_window_handle = window.raw_window_handle();
@@ -59,114 +83,109 @@ impl GpuSkiaRenderer {
             .build(Some(raw_window_handle));
 
         // Create GL context
-        let not_current_gl_context = unsafe {
-            display.create_context(&config, &context_attributes)
-                .expect("Failed to create GL context")
-        };
+        let not_current_gl_context = unsafe { display.create_context(&config, &context_attributes) }
+            .map_err(|err| format!("failed to create GL context: {}", err))?;
 
-        // Create window surface attributes
+        // Create GL surface for window
         let size = window.inner_size();
         let surface_attributes = SurfaceAttributesBuilder::<WindowSurface>::new().build(
             raw_window_handle,
-            size.width as u32,
-            size.height as u32,
+            non_zero(size.width),
+            non_zero(size.height),
         );
-
-        // Create GL surface for window
-        let gl_surface = unsafe {
-            display.create_window_surface(&config, &surface_attributes)
-                .expect("Failed to create GL window surface")
-        };
+        let gl_surface = unsafe { display.create_window_surface(&config, &surface_attributes) }
+            .map_err(|err| format!("failed to create GL window surface: {}", err))?;
 
         // Make context current
-        let gl_context = not_current_gl_context.make_current(&gl_surface)
-            .expect("Failed to make GL context current");
+        let gl_context = not_current_gl_context
+            .make_current(&gl_surface)
+            .map_err(|err| format!("failed to make GL context current: {}", err))?;
 
         // Create Skia DirectContext for GPU
-        let interface = gpu::gl::Interface::new_load_with(|s| {
-            gl_context.get_proc_address(s) as *const _
-        }).expect("Failed to create GL interface");
-
-        let gr_context = gpu::DirectContext::new_gl(Some(interface), None)
-            .expect("Failed to create Skia GPU DirectContext");
-
-        // Create Skia Surface from GL framebuffer
-        let fb_info = {
-            use skia_safe::gpu::gl::FramebufferInfo;
-            FramebufferInfo {
-                fboid: 0, // default framebuffer
-                format: skia_safe::gpu::gl::Format::RGBA8.into(),
-            }
-        };
-
-        let backend_render_target = gpu::BackendRenderTarget::new_gl(
-            (size.width as i32, size.height as i32),
-            0, // sample count
-            8, // stencil bits
-            fb_info,
-        );
-
-        let skia_surface = SkSurface::from_backend_render_target(
-            &gr_context,
-            &backend_render_target,
-            gpu::SurfaceOrigin::BottomLeft,
-            skia_safe::ColorType::RGBA8888,
-            None,
-            None,
-        ).expect("Failed to create Skia surface");
-
-        Self {
+        let interface = gpu::gl::Interface::new_load_with(|name| match CString::new(name) {
+            Ok(name) => display.get_proc_address(&name),
+            Err(_) => std::ptr::null(),
+        })
+        .ok_or_else(|| "failed to create GL interface".to_string())?;
+        let mut gr_context = gpu::DirectContext::new_gl(Some(interface), None)
+            .ok_or_else(|| "failed to create Skia GPU context".to_string())?;
+
+        let (width, height) = (size.width.max(1) as i32, size.height.max(1) as i32);
+        let skia_surface = skia_surface(&mut gr_context, width, height)
+            .ok_or_else(|| "failed to create Skia GPU surface".to_string())?;
+
+        Ok(Self {
             gl_context,
             surface: gl_surface,
             gr_context,
             skia_surface,
-            width: size.width as i32,
-            height: size.height as i32,
-        }
+            width,
+            height,
+            stale: false,
+        })
     }
 
-    pub fn resize(&mut self, width: u32, height: u32) {
-        if width as i32 == self.width && height as i32 == self.height {
-            return;
+    fn rebuild(&mut self) {
+        let (width, height) = (self.width as u32, self.height as u32);
+        self.surface.resize(&self.gl_context, non_zero(width), non_zero(height));
+        if let Some(skia_surface) = skia_surface(&mut self.gr_context, self.width, self.height) {
+            self.skia_surface = skia_surface;
+            self.stale = false;
         }
-        self.surface.resize(width, height);
-
-        let fb_info = {
-            use skia_safe::gpu::gl::FramebufferInfo;
-            FramebufferInfo {
-                fboid: 0,
-                format: skia_safe::gpu::gl::Format::RGBA8.into(),
-            }
-        };
+    }
+}
 
-        let backend_render_target = gpu::BackendRenderTarget::new_gl(
-            (width as i32, height as i32),
-            0,
-            8,
-            fb_info,
-        );
+impl RenderBackend for GpuSkiaRenderer {
+    fn name(&self) -> &'static str {
+        "gl"
+    }
 
-        self.skia_surface = SkSurface::from_backend_render_target(
-            &self.gr_context,
-            &backend_render_target,
-            gpu::SurfaceOrigin::BottomLeft,
-            skia_safe::ColorType::RGBA8888,
-            None,
-            None,
-        ).expect("Failed to recreate Skia surface");
-
-        self.width = width as i32;
-        self.height = height as i32;
+    fn size(&self) -> (u32, u32) {
+        (self.width as u32, self.height as u32)
     }
 
-    pub fn draw(&mut self, widget: &mut dyn crate::widget::Widget) {
-        let canvas = self.skia_surface.canvas();
-        canvas.clear(skia_safe::colors::WHITE);
+    fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 || (width as i32 == self.width && height as i32 == self.height) {
+            return;
+        }
+        self.surface.resize(&self.gl_context, non_zero(width), non_zero(height));
+        // Keep the old surface if Skia can't make one at the new size; the
+        // next resize will try again.
+        if let Some(skia_surface) = skia_surface(&mut self.gr_context, width as i32, height as i32) {
+            self.skia_surface = skia_surface;
+            self.width = width as i32;
+            self.height = height as i32;
+        }
+    }
 
-        widget.draw(canvas);
+    fn begin_frame(&mut self) {
+        if self.stale {
+            self.rebuild();
+        }
+        self.skia_surface.canvas().clear(Color::WHITE);
+    }
 
+    fn canvas(&mut self) -> &mut Canvas {
+        self.skia_surface.canvas()
+    }
+
+    fn present(&mut self) {
         self.skia_surface.flush_and_submit();
-        self.gl_context.swap_buffers(&self.surface).expect("Failed to swap buffers");
+        // Drivers fail swaps when the surface goes away under them (display
+        // changes, lost contexts). Drop this frame and start over with fresh
+        // surfaces rather than taking the window down.
+        if self.surface.swap_buffers(&self.gl_context).is_err() {
+            self.stale = true;
+        }
     }
-}
 
+    fn set_present_mode(&mut self, mode: PresentMode) {
+        // EGL has no adaptive swap interval, so that is plain vsync here.
+        let interval = match mode {
+            PresentMode::Vsync | PresentMode::Adaptive => SwapInterval::Wait(non_zero(1)),
+            PresentMode::Immediate => SwapInterval::DontWait,
+        };
+        // Some drivers refuse; they keep whatever they default to.
+        let _ = self.surface.set_swap_interval(&self.gl_context, interval);
+    }
+}
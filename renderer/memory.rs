@@ -0,0 +1,109 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use skia_safe::{Canvas, Color, EncodedImageFormat, Image, Surface};
+
+use crate::renderer::{raster_surface, RenderBackend};
+
+// Draws into a raster surface that never reaches the screen, so tests can
+// render widgets without a window or GPU and look at the pixels.
+pub struct MemoryBackend {
+    surface: Surface,
+    width: u32,
+    height: u32,
+    frames: u64,
+}
+
+impl MemoryBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            surface: raster_surface(width, height).expect("Failed to create Skia raster surface"),
+            width,
+            height,
+            frames: 0,
+        }
+    }
+
+    // Frames presented so far.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    // The last presented frame (or the one being drawn).
+    pub fn snapshot(&mut self) -> Image {
+        self.surface.image_snapshot()
+    }
+
+    pub fn pixel(&mut self, x: i32, y: i32) -> Option<Color> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        self.surface.peek_pixels().map(|pixmap| pixmap.get_color((x, y)))
+    }
+
+    pub fn to_png(&mut self) -> Option<Vec<u8>> {
+        self.snapshot()
+            .encode_to_data(EncodedImageFormat::PNG)
+            .map(|data| data.as_bytes().to_vec())
+    }
+}
+
+impl RenderBackend for MemoryBackend {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if width == self.width && height == self.height {
+            return;
+        }
+        if let Some(surface) = raster_surface(width, height) {
+            self.surface = surface;
+            self.width = width;
+            self.height = height;
+        }
+    }
+
+    fn begin_frame(&mut self) {
+        self.surface.canvas().clear(Color::WHITE);
+    }
+
+    fn canvas(&mut self) -> &mut Canvas {
+        self.surface.canvas()
+    }
+
+    fn present(&mut self) {
+        self.frames += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_start_white_and_count_presents() {
+        let mut backend = MemoryBackend::new(16, 8);
+        backend.begin_frame();
+        assert_eq!(backend.pixel(0, 0), Some(Color::WHITE));
+        assert_eq!(backend.pixel(15, 7), Some(Color::WHITE));
+        assert_eq!(backend.pixel(16, 0), None);
+        assert_eq!(backend.pixel(-1, 0), None);
+        backend.present();
+        backend.present();
+        assert_eq!(backend.frames(), 2);
+    }
+
+    #[test]
+    fn resize_replaces_the_surface() {
+        let mut backend = MemoryBackend::new(16, 8);
+        backend.resize(32, 24);
+        assert_eq!(backend.size(), (32, 24));
+        backend.begin_frame();
+        assert_eq!(backend.pixel(31, 23), Some(Color::WHITE));
+    }
+}
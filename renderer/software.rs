@@ -0,0 +1,61 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use skia_safe::Surface;
+use std::num::NonZeroU32;
+use winit::window::Window;
+
+// Copies frames drawn by the raster renderer into the window through
+// softbuffer, which works wherever the platform can show pixels at all: no
+// GL driver needed.
+pub struct SoftwarePresenter {
+    // Kept alive for `surface`.
+    _context: softbuffer::Context,
+    surface: softbuffer::Surface,
+}
+
+impl SoftwarePresenter {
+    pub fn new(window: &Window) -> Result<Self, String> {
+        let context = unsafe { softbuffer::Context::new(window) }
+            .map_err(|err| format!("failed to create softbuffer context: {}", err))?;
+        let surface = unsafe { softbuffer::Surface::new(&context, window) }
+            .map_err(|err| format!("failed to create softbuffer surface: {}", err))?;
+        Ok(Self {
+            _context: context,
+            surface,
+        })
+    }
+
+    // `frame` must be a BGRA8888 raster surface, which is how `SkiaRenderer`
+    // makes them: each pixel's bytes are then already softbuffer's 0RGB in
+    // little-endian order.
+    pub fn present(&mut self, frame: &mut Surface) {
+        let (width, height) = (frame.width() as u32, frame.height() as u32);
+        let (w, h) = match (NonZeroU32::new(width), NonZeroU32::new(height)) {
+            (Some(w), Some(h)) => (w, h),
+            _ => return,
+        };
+        if self.surface.resize(w, h).is_err() {
+            return;
+        }
+        let pixmap = match frame.peek_pixels() {
+            Some(pixmap) => pixmap,
+            None => return,
+        };
+        let (pixels, row_bytes) = match pixmap.bytes() {
+            Some(pixels) => (pixels, pixmap.row_bytes()),
+            None => return,
+        };
+        let mut buffer = match self.surface.buffer_mut() {
+            Ok(buffer) => buffer,
+            Err(_) => return,
+        };
+        for (y, row) in buffer.chunks_exact_mut(width as usize).enumerate() {
+            let src = &pixels[y * row_bytes..y * row_bytes + width as usize * 4];
+            for (dst, bgra) in row.iter_mut().zip(src.chunks_exact(4)) {
+                *dst = u32::from_le_bytes([bgra[0], bgra[1], bgra[2], 0]);
+            }
+        }
+        let _ = buffer.present();
+    }
+}
@@ -0,0 +1,490 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use ash::extensions::{ext, khr};
+use ash::prelude::VkResult;
+use ash::vk::{self, Handle};
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use skia_safe::gpu::{self, vk as skvk};
+use skia_safe::surface::BackendSurfaceAccess;
+use skia_safe::{Canvas, Color, ColorType, Surface};
+use std::os::raw::c_char;
+use std::ptr;
+use winit::window::Window;
+
+use crate::renderer::{raster_surface, PresentMode, RenderBackend};
+
+// Draws with Skia's Vulkan backend and presents through a swapchain. Works
+// with any conformant driver, including software ones like lavapipe.
+pub struct VulkanRenderer {
+    surfaces: Vec<Option<Surface>>,
+    gr_context: gpu::DirectContext,
+    // Drawn into when no swapchain image could be acquired (e.g. while the
+    // window is minimised); never presented.
+    scratch: Surface,
+    // Keeps the Vulkan loader loaded.
+    _entry: ash::Entry,
+    instance: ash::Instance,
+    surface_loader: khr::Surface,
+    surface: vk::SurfaceKHR,
+    physical_device: vk::PhysicalDevice,
+    device: ash::Device,
+    queue: vk::Queue,
+    swapchain_loader: khr::Swapchain,
+    swapchain: vk::SwapchainKHR,
+    images: Vec<vk::Image>,
+    format: vk::SurfaceFormatKHR,
+    acquire_fence: vk::Fence,
+    present_mode: PresentMode,
+    current: Option<u32>,
+    width: u32,
+    height: u32,
+    // Set when the swapchain no longer matches the window (resized, out of
+    // date or suboptimal); rebuilt at the start of the next frame.
+    stale: bool,
+}
+
+fn color_type(format: vk::Format) -> ColorType {
+    match format {
+        vk::Format::R8G8B8A8_UNORM => ColorType::RGBA8888,
+        _ => ColorType::BGRA8888,
+    }
+}
+
+// Skia has its own copy of the Vulkan enums.
+fn skia_format(format: vk::Format) -> skvk::Format {
+    match format {
+        vk::Format::R8G8B8A8_UNORM => skvk::Format::R8G8B8A8_UNORM,
+        _ => skvk::Format::B8G8R8A8_UNORM,
+    }
+}
+
+impl VulkanRenderer {
+    pub fn new(window: &Window) -> Result<Self, String> {
+        let extensions = ash_window::enumerate_required_extensions(window.raw_display_handle())
+            .map_err(|err| format!("no Vulkan surface support: {}", err))?;
+        let size = window.inner_size();
+        Self::with_surface(extensions, (size.width, size.height), |entry, instance| unsafe {
+            ash_window::create_surface(
+                entry,
+                instance,
+                window.raw_display_handle(),
+                window.raw_window_handle(),
+                None,
+            )
+        })
+    }
+
+    // Presents to a VK_EXT_headless_surface instead of a window, so the
+    // swapchain handling can run without a display (e.g. on lavapipe).
+    pub fn headless(width: u32, height: u32) -> Result<Self, String> {
+        let extensions = [khr::Surface::name().as_ptr(), ext::HeadlessSurface::name().as_ptr()];
+        Self::with_surface(&extensions, (width, height), |entry, instance| unsafe {
+            ext::HeadlessSurface::new(entry, instance)
+                .create_headless_surface(&vk::HeadlessSurfaceCreateInfoEXT::default(), None)
+        })
+    }
+
+    fn with_surface(
+        extensions: &[*const c_char],
+        (width, height): (u32, u32),
+        create_surface: impl FnOnce(&ash::Entry, &ash::Instance) -> VkResult<vk::SurfaceKHR>,
+    ) -> Result<Self, String> {
+        let entry = unsafe { ash::Entry::load() }.map_err(|err| format!("no Vulkan loader: {}", err))?;
+
+        let app_info = vk::ApplicationInfo::builder().api_version(vk::API_VERSION_1_1);
+        let instance_info = vk::InstanceCreateInfo::builder()
+            .application_info(&app_info)
+            .enabled_extension_names(extensions);
+        let instance = unsafe { entry.create_instance(&instance_info, None) }
+            .map_err(|err| format!("failed to create Vulkan instance: {}", err))?;
+
+        let surface = create_surface(&entry, &instance)
+            .map_err(|err| format!("failed to create Vulkan surface: {}", err))?;
+        let surface_loader = khr::Surface::new(&entry, &instance);
+
+        // The first device with a queue that can both draw and present here.
+        let devices = unsafe { instance.enumerate_physical_devices() }
+            .map_err(|err| format!("failed to list Vulkan devices: {}", err))?;
+        let (physical_device, queue_family) = devices
+            .iter()
+            .find_map(|&device| {
+                let families = unsafe { instance.get_physical_device_queue_family_properties(device) };
+                families.iter().enumerate().find_map(|(index, family)| {
+                    let index = index as u32;
+                    let presents = unsafe {
+                        surface_loader.get_physical_device_surface_support(device, index, surface)
+                    }
+                    .unwrap_or(false);
+                    (family.queue_flags.contains(vk::QueueFlags::GRAPHICS) && presents).then_some((device, index))
+                })
+            })
+            .ok_or_else(|| "no Vulkan device can present to this window".to_string())?;
+
+        let priorities = [1.0];
+        let queue_info = [vk::DeviceQueueCreateInfo::builder()
+            .queue_family_index(queue_family)
+            .queue_priorities(&priorities)
+            .build()];
+        let device_extensions = [khr::Swapchain::name().as_ptr()];
+        let device_info = vk::DeviceCreateInfo::builder()
+            .queue_create_infos(&queue_info)
+            .enabled_extension_names(&device_extensions);
+        let device = unsafe { instance.create_device(physical_device, &device_info, None) }
+            .map_err(|err| format!("failed to create Vulkan device: {}", err))?;
+        let queue = unsafe { device.get_device_queue(queue_family, 0) };
+
+        let gr_context = {
+            let get_proc = |of: skvk::GetProcOf| unsafe {
+                let function = match of {
+                    skvk::GetProcOf::Instance(instance, name) => {
+                        entry.get_instance_proc_addr(vk::Instance::from_raw(instance as _), name)
+                    }
+                    skvk::GetProcOf::Device(device, name) => {
+                        (instance.fp_v1_0().get_device_proc_addr)(vk::Device::from_raw(device as _), name)
+                    }
+                };
+                match function {
+                    Some(function) => function as _,
+                    None => ptr::null(),
+                }
+            };
+            let backend_context = unsafe {
+                skvk::BackendContext::new(
+                    instance.handle().as_raw() as _,
+                    physical_device.as_raw() as _,
+                    device.handle().as_raw() as _,
+                    (queue.as_raw() as _, queue_family as usize),
+                    &get_proc,
+                )
+            };
+            gpu::DirectContext::new_vulkan(&backend_context, None)
+                .ok_or_else(|| "failed to create Skia Vulkan context".to_string())?
+        };
+
+        let acquire_fence = unsafe { device.create_fence(&vk::FenceCreateInfo::default(), None) }
+            .map_err(|err| format!("failed to create Vulkan fence: {}", err))?;
+        let swapchain_loader = khr::Swapchain::new(&instance, &device);
+
+        let mut renderer = Self {
+            surfaces: Vec::new(),
+            gr_context,
+            scratch: raster_surface(1, 1).expect("Failed to create Skia raster surface"),
+            _entry: entry,
+            instance,
+            surface_loader,
+            surface,
+            physical_device,
+            device,
+            queue,
+            swapchain_loader,
+            swapchain: vk::SwapchainKHR::null(),
+            images: Vec::new(),
+            format: vk::SurfaceFormatKHR::default(),
+            acquire_fence,
+            present_mode: PresentMode::Vsync,
+            current: None,
+            width,
+            height,
+            stale: true,
+        };
+        renderer.recreate_swapchain()?;
+        Ok(renderer)
+    }
+
+    fn recreate_swapchain(&mut self) -> Result<(), String> {
+        unsafe { self.device.device_wait_idle() }.map_err(|err| err.to_string())?;
+        // Kept in step: `surface_for` indexes one by the other.
+        self.surfaces.clear();
+        self.images.clear();
+
+        let caps = unsafe {
+            self.surface_loader
+                .get_physical_device_surface_capabilities(self.physical_device, self.surface)
+        }
+        .map_err(|err| err.to_string())?;
+        let formats = unsafe {
+            self.surface_loader
+                .get_physical_device_surface_formats(self.physical_device, self.surface)
+        }
+        .map_err(|err| err.to_string())?;
+        self.format = formats
+            .iter()
+            .copied()
+            .find(|f| f.format == vk::Format::B8G8R8A8_UNORM || f.format == vk::Format::R8G8B8A8_UNORM)
+            .or_else(|| formats.first().copied())
+            .ok_or_else(|| "Vulkan surface has no formats".to_string())?;
+
+        // A current extent of u32::MAX means the window follows the swapchain.
+        let extent = if caps.current_extent.width != u32::MAX {
+            caps.current_extent
+        } else {
+            vk::Extent2D {
+                width: self.width.clamp(caps.min_image_extent.width, caps.max_image_extent.width),
+                height: self.height.clamp(caps.min_image_extent.height, caps.max_image_extent.height),
+            }
+        };
+        if extent.width == 0 || extent.height == 0 {
+            // Minimised. `acquire` waits for `resize` to report a size again
+            // rather than rebuilding every frame.
+            self.width = 0;
+            self.height = 0;
+            return Ok(());
+        }
+
+        let present_modes = unsafe {
+            self.surface_loader
+                .get_physical_device_surface_present_modes(self.physical_device, self.surface)
+        }
+        .map_err(|err| err.to_string())?;
+        // FIFO is the only mode every driver must support.
+        let wanted: &[vk::PresentModeKHR] = match self.present_mode {
+            PresentMode::Vsync => &[],
+            PresentMode::Adaptive => &[vk::PresentModeKHR::FIFO_RELAXED],
+            PresentMode::Immediate => &[vk::PresentModeKHR::IMMEDIATE, vk::PresentModeKHR::MAILBOX],
+        };
+        let present_mode = wanted
+            .iter()
+            .copied()
+            .find(|mode| present_modes.contains(mode))
+            .unwrap_or(vk::PresentModeKHR::FIFO);
+
+        let mut image_count = caps.min_image_count + 1;
+        if caps.max_image_count > 0 {
+            image_count = image_count.min(caps.max_image_count);
+        }
+        let old_swapchain = self.swapchain;
+        let info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(self.surface)
+            .min_image_count(image_count)
+            .image_format(self.format.format)
+            .image_color_space(self.format.color_space)
+            .image_extent(extent)
+            .image_array_layers(1)
+            .image_usage(
+                vk::ImageUsageFlags::COLOR_ATTACHMENT
+                    | vk::ImageUsageFlags::TRANSFER_SRC
+                    | vk::ImageUsageFlags::TRANSFER_DST,
+            )
+            .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .pre_transform(caps.current_transform)
+            .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
+            .present_mode(present_mode)
+            .clipped(true)
+            .old_swapchain(old_swapchain);
+        self.swapchain = unsafe { self.swapchain_loader.create_swapchain(&info, None) }
+            .map_err(|err| format!("failed to create swapchain: {}", err))?;
+        if old_swapchain != vk::SwapchainKHR::null() {
+            unsafe { self.swapchain_loader.destroy_swapchain(old_swapchain, None) };
+        }
+
+        self.images = unsafe { self.swapchain_loader.get_swapchain_images(self.swapchain) }
+            .map_err(|err| err.to_string())?;
+        self.surfaces = self.images.iter().map(|_| None).collect();
+        self.width = extent.width;
+        self.height = extent.height;
+        self.stale = false;
+        Ok(())
+    }
+
+    // The Skia surface wrapping swapchain image `index`, made on first use.
+    // Skia tracks each image's layout from then on.
+    fn surface_for(&mut self, index: usize) -> Option<&mut Surface> {
+        if self.surfaces[index].is_none() {
+            let image_info = unsafe {
+                skvk::ImageInfo::new(
+                    self.images[index].as_raw() as _,
+                    skvk::Alloc::default(),
+                    skvk::ImageTiling::OPTIMAL,
+                    skvk::ImageLayout::UNDEFINED,
+                    skia_format(self.format.format),
+                    1,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+            };
+            let render_target =
+                gpu::BackendRenderTarget::new_vulkan((self.width as i32, self.height as i32), 1, &image_info);
+            self.surfaces[index] = Surface::from_backend_render_target(
+                &mut self.gr_context,
+                &render_target,
+                gpu::SurfaceOrigin::TopLeft,
+                color_type(self.format.format),
+                None,
+                None,
+            );
+        }
+        self.surfaces[index].as_mut()
+    }
+
+    fn acquire(&mut self) -> Option<u32> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        if self.stale && self.recreate_swapchain().is_err() {
+            return None;
+        }
+        if self.images.is_empty() {
+            return None;
+        }
+        let result = unsafe {
+            self.swapchain_loader
+                .acquire_next_image(self.swapchain, u64::MAX, vk::Semaphore::null(), self.acquire_fence)
+        };
+        match result {
+            Ok((index, suboptimal)) => {
+                // Skia submits synchronously, so waiting here is all the
+                // ordering the image needs.
+                unsafe {
+                    let _ = self.device.wait_for_fences(&[self.acquire_fence], true, u64::MAX);
+                    let _ = self.device.reset_fences(&[self.acquire_fence]);
+                }
+                self.stale |= suboptimal;
+                Some(index)
+            }
+            Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
+                self.stale = true;
+                None
+            }
+            Err(_) => None,
+        }
+    }
+}
+
+impl RenderBackend for VulkanRenderer {
+    fn name(&self) -> &'static str {
+        "vulkan"
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if width != self.width || height != self.height {
+            self.width = width;
+            self.height = height;
+            self.stale = true;
+        }
+    }
+
+    fn set_present_mode(&mut self, mode: PresentMode) {
+        if mode != self.present_mode {
+            self.present_mode = mode;
+            self.stale = true;
+        }
+    }
+
+    fn begin_frame(&mut self) {
+        self.current = self.acquire();
+        if let Some(index) = self.current {
+            if self.surface_for(index as usize).is_none() {
+                self.current = None;
+            }
+        }
+        self.canvas().clear(Color::WHITE);
+    }
+
+    fn canvas(&mut self) -> &mut Canvas {
+        match self.current {
+            Some(index) => self.surfaces[index as usize]
+                .as_mut()
+                .expect("acquired image has a surface")
+                .canvas(),
+            None => self.scratch.canvas(),
+        }
+    }
+
+    fn present(&mut self) {
+        let index = match self.current.take() {
+            Some(index) => index,
+            None => return,
+        };
+        if let Some(surface) = self.surfaces[index as usize].as_mut() {
+            // Leaves the image in the layout the presentation engine wants.
+            surface.flush_with_access_info(BackendSurfaceAccess::Present, &gpu::FlushInfo::default());
+        }
+        self.gr_context.submit(true);
+
+        let swapchains = [self.swapchain];
+        let indices = [index];
+        let info = vk::PresentInfoKHR::builder()
+            .swapchains(&swapchains)
+            .image_indices(&indices);
+        match unsafe { self.swapchain_loader.queue_present(self.queue, &info) } {
+            Ok(false) => {}
+            Ok(true) | Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => self.stale = true,
+            Err(_) => {}
+        }
+    }
+}
+
+impl Drop for VulkanRenderer {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.device.device_wait_idle();
+        }
+        self.surfaces.clear();
+        self.gr_context.abandon();
+        unsafe {
+            self.device.destroy_fence(self.acquire_fence, None);
+            self.swapchain_loader.destroy_swapchain(self.swapchain, None);
+            self.device.destroy_device(None);
+            self.surface_loader.destroy_surface(self.surface, None);
+            self.instance.destroy_instance(None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // These need a Vulkan driver with VK_EXT_headless_surface, e.g. Mesa's
+    // lavapipe, so they only run when asked for:
+    // `cargo test --features vulkan -- --ignored`. CI runs them on lavapipe.
+    fn headless(width: u32, height: u32) -> VulkanRenderer {
+        VulkanRenderer::headless(width, height).expect("no headless Vulkan device")
+    }
+
+    fn frame(renderer: &mut VulkanRenderer) -> bool {
+        renderer.begin_frame();
+        let acquired = renderer.current.is_some();
+        renderer.canvas().clear(Color::BLACK);
+        renderer.present();
+        acquired
+    }
+
+    #[test]
+    #[ignore = "needs a Vulkan driver"]
+    fn presents_through_a_minimise_and_restore() {
+        let mut renderer = headless(64, 48);
+        assert!(frame(&mut renderer));
+        assert_eq!(renderer.size(), (64, 48));
+
+        // Drawn into the scratch surface, with no swapchain rebuilds.
+        renderer.resize(0, 0);
+        for _ in 0..3 {
+            assert!(!frame(&mut renderer));
+        }
+        assert!(renderer.stale);
+
+        renderer.resize(64, 48);
+        assert!(frame(&mut renderer));
+        assert!(!renderer.stale);
+        assert_eq!(renderer.size(), (64, 48));
+        assert_eq!(renderer.surfaces.len(), renderer.images.len());
+    }
+
+    #[test]
+    #[ignore = "needs a Vulkan driver"]
+    fn rebuilds_the_swapchain_on_resize() {
+        let mut renderer = headless(64, 48);
+        assert!(frame(&mut renderer));
+        renderer.resize(100, 30);
+        assert!(frame(&mut renderer));
+        assert_eq!(renderer.size(), (100, 30));
+    }
+}
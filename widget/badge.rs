@@ -0,0 +1,251 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Color, Font, Paint, PaintStyle, Point, Rect};
+use std::time::Instant;
+
+const BADGE_HEIGHT: f32 = 18.0;
+const BADGE_PADDING: f32 = 6.0;
+const DOT_SIZE: f32 = 10.0;
+// Seconds per pulse of a busy status dot.
+const PULSE_PERIOD: f32 = 1.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Neutral,
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Status {
+    fn color(self) -> Color {
+        let theme = theme::current();
+        match self {
+            Status::Neutral => theme.border,
+            Status::Info => theme.accent,
+            Status::Success => theme.success,
+            Status::Warning => theme.warning,
+            Status::Error => theme.error,
+        }
+    }
+}
+
+// A small pill with a count or short text, e.g. unread messages on a tab.
+pub struct Badge {
+    text: String,
+    status: Status,
+    rect: Rect,
+}
+
+impl Badge {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            status: Status::Info,
+            rect: Rect::default(),
+        }
+    }
+
+    // Counts above 99 show as "99+".
+    pub fn count(count: u32) -> Self {
+        Self::new(&Self::count_text(count))
+    }
+
+    fn count_text(count: u32) -> String {
+        if count > 99 {
+            "99+".to_string()
+        } else {
+            count.to_string()
+        }
+    }
+
+    pub fn with_status(mut self, status: Status) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        context::request_redraw();
+    }
+
+    pub fn set_count(&mut self, count: u32) {
+        self.set_text(&Self::count_text(count));
+    }
+
+    pub fn set_status(&mut self, status: Status) {
+        self.status = status;
+        context::request_redraw();
+    }
+
+    fn font() -> Font {
+        let mut font = theme::current().font();
+        font.set_size(12.0);
+        font.set_embolden(true);
+        font
+    }
+}
+
+impl Widget for Badge {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        if self.text.is_empty() {
+            return;
+        }
+        let theme = theme::current();
+        let font = Self::font();
+        let (width, height) = self.preferred_size();
+        let pill = Rect::from_xywh(
+            self.rect.center_x() - width / 2.0,
+            self.rect.center_y() - height / 2.0,
+            width,
+            height,
+        );
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(self.status.color());
+        canvas.draw_round_rect(pill, height / 2.0, height / 2.0, &paint);
+
+        paint.set_color(theme.accent_foreground);
+        let text_width = font.measure_str(&self.text, None).0;
+        let baseline = pill.center_y() + font.size() / 3.0;
+        canvas.draw_str(&self.text, (pill.center_x() - text_width / 2.0, baseline), &font, &paint);
+    }
+
+    fn on_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("text", self.text.clone()),
+            ("status", format!("{:?}", self.status)),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let text_width = Self::font().measure_str(&self.text, None).0;
+        ((text_width + BADGE_PADDING * 2.0).max(BADGE_HEIGHT), BADGE_HEIGHT)
+    }
+}
+
+// A colored dot showing something's state, optionally with a label. A busy
+// dot pulses, asking for frames only while it is drawn.
+pub struct StatusDot {
+    status: Status,
+    label: String,
+    busy: bool,
+    start: Instant,
+    rect: Rect,
+}
+
+impl StatusDot {
+    pub fn new(status: Status) -> Self {
+        Self {
+            status,
+            label: String::new(),
+            busy: false,
+            start: Instant::now(),
+            rect: Rect::default(),
+        }
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn set_status(&mut self, status: Status) {
+        self.status = status;
+        context::request_redraw();
+    }
+
+    pub fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+        context::request_redraw();
+    }
+
+    pub fn set_busy(&mut self, busy: bool) {
+        self.busy = busy;
+        self.start = Instant::now();
+        context::request_redraw();
+    }
+}
+
+impl Widget for StatusDot {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let radius = DOT_SIZE / 2.0;
+        // Centred in a box twice its size, leaving room for the busy ring.
+        let center = Point::new(self.rect.left + DOT_SIZE, self.rect.center_y());
+        let color = self.status.color();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        if self.busy {
+            // A ring grows out of the dot and fades.
+            let t = (self.start.elapsed().as_secs_f32() % PULSE_PERIOD) / PULSE_PERIOD;
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_stroke_width(2.0);
+            paint.set_color(color.with_a(((1.0 - t) * 160.0) as u8));
+            canvas.draw_circle(center, radius + t * radius, &paint);
+            paint.set_style(PaintStyle::Fill);
+            context::request_animation_frame();
+        }
+        paint.set_color(color);
+        canvas.draw_circle(center, radius, &paint);
+
+        if !self.label.is_empty() {
+            let font = theme.font();
+            paint.set_color(theme.foreground);
+            let baseline = self.rect.center_y() + font.size() / 3.0;
+            canvas.draw_str(&self.label, (self.rect.left + DOT_SIZE * 2.0 + 4.0, baseline), &font, &paint);
+        }
+    }
+
+    fn on_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("label", self.label.clone()),
+            ("status", format!("{:?}", self.status)),
+            ("busy", self.busy.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let label = if self.label.is_empty() {
+            0.0
+        } else {
+            4.0 + theme::current().font().measure_str(&self.label, None).0
+        };
+        (DOT_SIZE * 2.0 + label, 20.0)
+    }
+}
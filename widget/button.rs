@@ -0,0 +1,266 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::icon::{draw_icon, icon_label_layout, icon_label_width};
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Point, Rect};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const PADDING: f32 = 16.0;
+const HEIGHT: f32 = 32.0;
+
+// A push button, drawn the way `Renderer::draw_button` draws one.
+pub struct Button {
+    label: String,
+    // A bundled icon name, drawn before the label.
+    icon: Option<String>,
+    enabled: bool,
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+    rect: Rect,
+    on_click: Option<Box<dyn FnMut()>>,
+}
+
+impl Button {
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            icon: None,
+            enabled: true,
+            hovered: false,
+            pressed: false,
+            focused: false,
+            rect: Rect::default(),
+            on_click: None,
+        }
+    }
+
+    pub fn with_icon(mut self, name: &str) -> Self {
+        self.icon = Some(name.to_string());
+        self
+    }
+
+    pub fn on_click<F: FnMut() + 'static>(mut self, f: F) -> Self {
+        self.on_click = Some(Box::new(f));
+        self
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+        context::request_redraw();
+    }
+
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    pub fn set_icon(&mut self, name: Option<&str>) {
+        self.icon = name.map(str::to_string);
+        context::request_redraw();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.hovered = false;
+            self.pressed = false;
+            self.focused = false;
+        }
+        context::request_redraw();
+    }
+
+    // Where the icon goes and where the label starts.
+    fn content_layout(&self) -> (Option<Rect>, f32) {
+        let font = theme::current().font();
+        let label_width = font.measure_str(&self.label, None).0;
+        icon_label_layout(self.rect, self.icon.is_some(), label_width)
+    }
+
+    fn click(&mut self) {
+        if let Some(on_click) = &mut self.on_click {
+            on_click();
+        }
+        context::request_redraw();
+    }
+}
+
+impl Widget for Button {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let radius = theme.corner_radius;
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(if self.pressed {
+            theme.surface_pressed
+        } else if self.hovered {
+            theme.surface_hover
+        } else {
+            theme.surface
+        });
+        canvas.draw_round_rect(self.rect, radius, radius, &paint);
+
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+        paint.set_color(theme.border);
+        canvas.draw_round_rect(self.rect, radius, radius, &paint);
+
+        let font = theme.font();
+        let mut text_paint = Paint::default();
+        text_paint.set_anti_alias(true);
+        let color = if self.enabled { theme.foreground } else { theme.disabled_foreground };
+        text_paint.set_color(color);
+        let (icon_rect, label_x) = self.content_layout();
+        if let (Some(name), Some(icon_rect)) = (&self.icon, icon_rect) {
+            draw_icon(canvas, name, icon_rect, color);
+        }
+        let baseline = self.rect.center_y() + font.size() / 3.0;
+        canvas.draw_str(&self.label, (label_x, baseline), &font, &text_paint);
+
+        if self.focused {
+            theme::draw_focus_ring(canvas, self.rect, radius);
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                let hovered = self.rect.contains(Point::new(*x, *y));
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    context::request_redraw();
+                }
+                false
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => {
+                self.focused = self.rect.contains(Point::new(*x, *y));
+                self.pressed = self.focused;
+                if self.pressed {
+                    context::request_redraw();
+                }
+                self.pressed
+            }
+            Event::MouseUp { x, y, button: MouseButton::Left, .. } if self.pressed => {
+                self.pressed = false;
+                if self.rect.contains(Point::new(*x, *y)) {
+                    self.click();
+                }
+                context::request_redraw();
+                true
+            }
+            Event::KeyDown { key: Some(VirtualKeyCode::Space | VirtualKeyCode::Return), .. } if self.focused => {
+                self.click();
+                true
+            }
+            Event::FocusLost => {
+                self.focused = false;
+                self.pressed = false;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        let mut properties = vec![
+            ("label", self.label.clone()),
+            ("enabled", self.enabled.to_string()),
+        ];
+        if let Some(icon) = &self.icon {
+            properties.push(("icon", icon.clone()));
+        }
+        properties
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let font = theme::current().font();
+        let label_width = font.measure_str(&self.label, None).0;
+        (icon_label_width(self.icon.is_some(), label_width) + PADDING * 2.0, HEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::icon::ICON_SIZE;
+    use crate::widget::testing::{press, render};
+
+    fn label_width(label: &str) -> f32 {
+        theme::current().font().measure_str(label, None).0
+    }
+
+    #[test]
+    fn button_darkens_while_pressed() {
+        let theme = theme::current();
+        let rect = Rect::from_xywh(10.0, 10.0, 100.0, 32.0);
+        let mut button = Button::new("OK");
+        let mut backend = render(&mut button, rect);
+        // Left of the centred label.
+        assert_eq!(backend.pixel(20, 26), Some(theme.surface));
+
+        assert!(button.on_event(&press(20.0, 26.0)));
+        let mut backend = render(&mut button, rect);
+        assert_eq!(backend.pixel(20, 26), Some(theme.surface_pressed));
+    }
+
+    #[test]
+    fn icon_sits_before_the_label_and_both_are_centred() {
+        let mut button = Button::new("Save").with_icon("check");
+        let rect = Rect::from_xywh(10.0, 10.0, 200.0, 32.0);
+        button.layout(rect);
+        let (icon, label_x) = button.content_layout();
+        let icon = icon.unwrap();
+
+        assert_eq!(icon.width(), ICON_SIZE);
+        assert_eq!(icon.center_y(), rect.center_y());
+        assert!(label_x > icon.right);
+        let right = label_x + label_width("Save");
+        assert!((icon.left - rect.left - (rect.right - right)).abs() < 0.01);
+    }
+
+    #[test]
+    fn icon_only_buttons_centre_the_icon() {
+        let mut button = Button::new("").with_icon("add");
+        button.layout(Rect::from_xywh(0.0, 0.0, 48.0, 32.0));
+        let (icon, _) = button.content_layout();
+        assert_eq!(icon, Some(Rect::from_xywh(16.0, 8.0, ICON_SIZE, ICON_SIZE)));
+        assert_eq!(button.preferred_size().0, ICON_SIZE + PADDING * 2.0);
+    }
+
+    #[test]
+    fn labels_stay_centred_without_an_icon() {
+        let mut button = Button::new("Open");
+        button.layout(Rect::from_xywh(0.0, 0.0, 100.0, 32.0));
+        let (icon, label_x) = button.content_layout();
+        assert_eq!(icon, None);
+        assert!((label_x - (50.0 - label_width("Open") / 2.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn the_icon_widens_the_preferred_size() {
+        let plain = Button::new("Save").preferred_size().0;
+        let with_icon = Button::new("Save").with_icon("check").preferred_size().0;
+        assert!(with_icon > plain + ICON_SIZE);
+    }
+}
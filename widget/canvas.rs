@@ -0,0 +1,364 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::image::{self, fitted_rect, ImageFit, ImageSource};
+use crate::widget::{theme, Widget};
+use skia_safe::{
+    BlurStyle, Canvas, Color, MaskFilter, Paint, PaintStyle, Path, Point, Rect, SamplingOptions, Shader, TileMode,
+};
+use std::time::Duration;
+
+// What shapes are filled with.
+#[derive(Debug, Clone)]
+pub enum Brush {
+    Solid(Color),
+    // Colors spread evenly from `start` to `end`.
+    Linear { start: Point, end: Point, colors: Vec<Color> },
+    Radial { center: Point, radius: f32, colors: Vec<Color> },
+}
+
+impl From<Color> for Brush {
+    fn from(color: Color) -> Self {
+        Brush::Solid(color)
+    }
+}
+
+impl Brush {
+    fn paint(&self) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        match self {
+            Brush::Solid(color) => {
+                paint.set_color(*color);
+            }
+            Brush::Linear { start, end, colors } => {
+                paint.set_shader(Shader::linear_gradient(
+                    (*start, *end),
+                    colors.as_slice(),
+                    None,
+                    TileMode::Clamp,
+                    None,
+                    None,
+                ));
+            }
+            Brush::Radial { center, radius, colors } => {
+                paint.set_shader(Shader::radial_gradient(
+                    *center,
+                    *radius,
+                    colors.as_slice(),
+                    None,
+                    TileMode::Clamp,
+                    None,
+                    None,
+                ));
+            }
+        }
+        paint
+    }
+}
+
+fn stroke_paint(color: Color, width: f32) -> Paint {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_stroke_width(width);
+    paint.set_color(color);
+    paint
+}
+
+// Handed to a `CanvasWidget`'s paint closure. Coordinates are local: (0, 0)
+// is the widget's top-left corner, and drawing is clipped to its bounds.
+// `canvas()` gives the raw Skia canvas for anything not covered here.
+pub struct DrawContext<'a> {
+    canvas: &'a mut Canvas,
+    width: f32,
+    height: f32,
+}
+
+impl<'a> DrawContext<'a> {
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::from_wh(self.width, self.height)
+    }
+
+    pub fn canvas(&mut self) -> &mut Canvas {
+        &mut *self.canvas
+    }
+
+    // Draws again on the next frame, for continuous animation.
+    pub fn animate(&self) {
+        context::request_animation_frame();
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        self.fill_rect(self.bounds(), color);
+    }
+
+    pub fn fill_rect(&mut self, rect: Rect, brush: impl Into<Brush>) {
+        self.canvas.draw_rect(rect, &brush.into().paint());
+    }
+
+    pub fn stroke_rect(&mut self, rect: Rect, color: Color, width: f32) {
+        self.canvas.draw_rect(rect, &stroke_paint(color, width));
+    }
+
+    pub fn fill_round_rect(&mut self, rect: Rect, radius: f32, brush: impl Into<Brush>) {
+        self.canvas.draw_round_rect(rect, radius, radius, &brush.into().paint());
+    }
+
+    pub fn stroke_round_rect(&mut self, rect: Rect, radius: f32, color: Color, width: f32) {
+        self.canvas.draw_round_rect(rect, radius, radius, &stroke_paint(color, width));
+    }
+
+    pub fn fill_circle(&mut self, center: impl Into<Point>, radius: f32, brush: impl Into<Brush>) {
+        self.canvas.draw_circle(center, radius, &brush.into().paint());
+    }
+
+    pub fn stroke_circle(&mut self, center: impl Into<Point>, radius: f32, color: Color, width: f32) {
+        self.canvas.draw_circle(center, radius, &stroke_paint(color, width));
+    }
+
+    pub fn line(&mut self, from: impl Into<Point>, to: impl Into<Point>, color: Color, width: f32) {
+        self.canvas.draw_line(from, to, &stroke_paint(color, width));
+    }
+
+    pub fn fill_path(&mut self, path: &Path, brush: impl Into<Brush>) {
+        self.canvas.draw_path(path, &brush.into().paint());
+    }
+
+    pub fn stroke_path(&mut self, path: &Path, color: Color, width: f32) {
+        self.canvas.draw_path(path, &stroke_paint(color, width));
+    }
+
+    // A blurred copy of `path`, offset by `offset`. Draw it before the shape
+    // that casts it.
+    pub fn shadow(&mut self, path: &Path, offset: (f32, f32), blur: f32, color: Color) {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(color);
+        if blur > 0.0 {
+            // Skia takes the blur as a sigma; half the radius looks right.
+            paint.set_mask_filter(MaskFilter::blur(BlurStyle::Normal, blur / 2.0, false));
+        }
+        self.canvas.save();
+        self.canvas.translate(offset);
+        self.canvas.draw_path(path, &paint);
+        self.canvas.restore();
+    }
+
+    // Draws `text` in the theme font at `size`, with its baseline at `y`.
+    pub fn text(&mut self, text: &str, x: f32, y: f32, size: f32, color: Color) {
+        let mut font = theme::current().font();
+        font.set_size(size);
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(color);
+        self.canvas.draw_str(text, (x, y), &font, &paint);
+    }
+
+    pub fn measure_text(&self, text: &str, size: f32) -> f32 {
+        let mut font = theme::current().font();
+        font.set_size(size);
+        font.measure_str(text, None).0
+    }
+
+    // Draws the first frame of an image, through the same cache as `Image`.
+    pub fn image(&mut self, source: &ImageSource, rect: Rect, fit: ImageFit) {
+        if let Some(decoded) = image::load(source) {
+            let dst = fitted_rect(decoded.size(), rect, fit);
+            self.canvas.save();
+            self.canvas.clip_rect(rect, None, Some(true));
+            self.canvas.draw_image_rect_with_sampling_options(
+                decoded.frame_at(Duration::ZERO),
+                None,
+                dst,
+                SamplingOptions::default(),
+                &Paint::default(),
+            );
+            self.canvas.restore();
+        }
+    }
+
+    pub fn save(&mut self) {
+        self.canvas.save();
+    }
+
+    pub fn restore(&mut self) {
+        self.canvas.restore();
+    }
+
+    pub fn translate(&mut self, dx: f32, dy: f32) {
+        self.canvas.translate((dx, dy));
+    }
+
+    pub fn scale(&mut self, sx: f32, sy: f32) {
+        self.canvas.scale((sx, sy));
+    }
+
+    // Degrees, clockwise.
+    pub fn rotate(&mut self, degrees: f32) {
+        self.canvas.rotate(degrees, None);
+    }
+}
+
+type PointerHandler = Box<dyn FnMut(&Event) -> bool>;
+
+// A widget that draws with a closure, for charts, diagrams, sketch pads and
+// anything else that doesn't need a full `Widget` impl. Pointer events are
+// handed to `on_pointer` in local coordinates; returning true from it marks
+// the event handled, which also redraws the window.
+pub struct CanvasWidget {
+    paint: Box<dyn FnMut(&mut DrawContext)>,
+    on_pointer: Option<PointerHandler>,
+    size: (f32, f32),
+    rect: Rect,
+    // Whether a press started inside; moves and the release are then
+    // forwarded even outside the bounds so drags can finish.
+    dragging: bool,
+}
+
+impl CanvasWidget {
+    pub fn new<F: FnMut(&mut DrawContext) + 'static>(paint: F) -> Self {
+        Self {
+            paint: Box::new(paint),
+            on_pointer: None,
+            size: (100.0, 100.0),
+            rect: Rect::default(),
+            dragging: false,
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    pub fn on_pointer<F: FnMut(&Event) -> bool + 'static>(mut self, f: F) -> Self {
+        self.on_pointer = Some(Box::new(f));
+        self
+    }
+
+    pub fn set_paint<F: FnMut(&mut DrawContext) + 'static>(&mut self, paint: F) {
+        self.paint = Box::new(paint);
+        context::request_redraw();
+    }
+
+    // Redraws after state the paint closure reads has changed.
+    pub fn invalidate(&self) {
+        context::request_redraw();
+    }
+
+    fn to_local(&self, event: &Event) -> Option<Event> {
+        let (dx, dy) = (self.rect.left, self.rect.top);
+        let inside = |x: f32, y: f32| self.rect.contains(Point::new(x, y));
+        match event.clone() {
+            Event::MouseDown { x, y, button, modifiers } if inside(x, y) => Some(Event::MouseDown {
+                x: x - dx,
+                y: y - dy,
+                button,
+                modifiers,
+            }),
+            Event::MouseUp { x, y, button, modifiers } if self.dragging || inside(x, y) => Some(Event::MouseUp {
+                x: x - dx,
+                y: y - dy,
+                button,
+                modifiers,
+            }),
+            Event::MouseMove { x, y, modifiers } if self.dragging || inside(x, y) => Some(Event::MouseMove {
+                x: x - dx,
+                y: y - dy,
+                modifiers,
+            }),
+            Event::Scroll { x, y, delta, phase, modifiers } if inside(x, y) => Some(Event::Scroll {
+                x: x - dx,
+                y: y - dy,
+                delta,
+                phase,
+                modifiers,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Widget for CanvasWidget {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        // Restoring to the count also drops any saves the painter left open.
+        let count = canvas.save();
+        canvas.clip_rect(self.rect, None, Some(true));
+        canvas.translate((self.rect.left, self.rect.top));
+        let mut ctx = DrawContext {
+            canvas: &mut *canvas,
+            width: self.rect.width(),
+            height: self.rect.height(),
+        };
+        (self.paint)(&mut ctx);
+        canvas.restore_to_count(count);
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        let local = match self.to_local(event) {
+            Some(local) => local,
+            None => return false,
+        };
+        match local {
+            Event::MouseDown { .. } => self.dragging = true,
+            Event::MouseUp { .. } => self.dragging = false,
+            _ => {}
+        }
+        match &mut self.on_pointer {
+            Some(on_pointer) => on_pointer(&local),
+            None => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{MemoryBackend, RenderBackend};
+
+    #[test]
+    fn undoes_saves_its_painter_leaves_open() {
+        let mut widget = CanvasWidget::new(|ctx| {
+            let canvas = ctx.canvas();
+            canvas.save();
+            canvas.translate((100.0, 0.0));
+            canvas.save();
+        });
+        let mut backend = MemoryBackend::new(240, 80);
+        backend.begin_frame();
+        widget.layout(Rect::from_xywh(10.0, 10.0, 50.0, 50.0));
+        let before = backend.canvas().save_count();
+        widget.draw(backend.canvas());
+        assert_eq!(backend.canvas().save_count(), before);
+
+        // Later drawing is neither clipped to the widget nor translated.
+        let mut paint = Paint::default();
+        paint.set_color(Color::BLACK);
+        backend.canvas().draw_rect(Rect::from_xywh(150.0, 0.0, 10.0, 10.0), &paint);
+        backend.present();
+        assert_eq!(backend.pixel(155, 5), Some(Color::BLACK));
+    }
+}
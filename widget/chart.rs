@@ -0,0 +1,929 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::scroll::scroll_amount;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Color, Font, Paint, PaintStyle, Path, Point, Rect};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+use winit::event::MouseButton;
+use winit::window::CursorIcon;
+
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+// Room around the plot for tick labels.
+const MARGIN_LEFT: f32 = 56.0;
+const MARGIN_BOTTOM: f32 = 24.0;
+const MARGIN_TOP: f32 = 8.0;
+const MARGIN_RIGHT: f32 = 12.0;
+const TICK_FONT_SIZE: f32 = 11.0;
+// How close the pointer must be to a point for its tooltip, in pixels.
+const HOVER_DISTANCE: f32 = 24.0;
+// Each wheel line zooms by this factor.
+const ZOOM_STEP: f64 = 0.85;
+
+// Series colors after the first, which uses the theme accent.
+const PALETTE: [Color; 5] = [
+    Color::from_rgb(255, 149, 0),
+    Color::from_rgb(52, 199, 89),
+    Color::from_rgb(175, 82, 222),
+    Color::from_rgb(255, 59, 48),
+    Color::from_rgb(90, 200, 250),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesKind {
+    Line,
+    // A line with the area down to zero (or the plot's bottom) filled.
+    Area,
+    Bar,
+    Scatter,
+}
+
+// One set of (x, y) points. Line, area and bar points must be sorted by x;
+// scatter points may be in any order.
+#[derive(Debug, Clone)]
+pub struct Series {
+    name: String,
+    kind: SeriesKind,
+    points: Vec<(f64, f64)>,
+    color: Option<Color>,
+    // Scatter point indices in x order, so only the columns in view are
+    // visited. Empty for the other kinds, whose points are already sorted.
+    by_x: Vec<usize>,
+}
+
+impl Series {
+    pub fn new(name: &str, kind: SeriesKind, points: Vec<(f64, f64)>) -> Self {
+        let mut series = Self {
+            name: name.to_string(),
+            kind,
+            points: Vec::new(),
+            color: None,
+            by_x: Vec::new(),
+        };
+        series.set_points(points);
+        series
+    }
+
+    pub fn line(name: &str, points: Vec<(f64, f64)>) -> Self {
+        Self::new(name, SeriesKind::Line, points)
+    }
+
+    pub fn area(name: &str, points: Vec<(f64, f64)>) -> Self {
+        Self::new(name, SeriesKind::Area, points)
+    }
+
+    pub fn bar(name: &str, points: Vec<(f64, f64)>) -> Self {
+        Self::new(name, SeriesKind::Bar, points)
+    }
+
+    pub fn scatter(name: &str, points: Vec<(f64, f64)>) -> Self {
+        Self::new(name, SeriesKind::Scatter, points)
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> SeriesKind {
+        self.kind
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    fn is_sorted(&self) -> bool {
+        self.kind != SeriesKind::Scatter
+    }
+
+    fn set_points(&mut self, points: Vec<(f64, f64)>) {
+        self.points = points;
+        self.by_x.clear();
+        if !self.is_sorted() {
+            self.by_x = (0..self.points.len()).collect();
+            let points = &self.points;
+            self.by_x.sort_by(|a, b| points[*a].0.total_cmp(&points[*b].0));
+        }
+    }
+
+    // The point at `position` in x order.
+    fn nth_by_x(&self, position: usize) -> usize {
+        if self.is_sorted() {
+            position
+        } else {
+            self.by_x[position]
+        }
+    }
+
+    // Positions in x order (see `nth_by_x`) of the points within `x0..=x1`,
+    // plus one either side so lines run off the edge of the plot instead of
+    // stopping short. For sorted series these are the point indices.
+    fn visible(&self, x0: f64, x1: f64) -> Range<usize> {
+        let (start, end) = if self.is_sorted() {
+            (
+                self.points.partition_point(|p| p.0 < x0),
+                self.points.partition_point(|p| p.0 <= x1),
+            )
+        } else {
+            (
+                self.by_x.partition_point(|i| self.points[*i].0 < x0),
+                self.by_x.partition_point(|i| self.points[*i].0 <= x1),
+            )
+        };
+        let start = start.saturating_sub(1);
+        let end = (end + 1).min(self.points.len());
+        start..end.max(start)
+    }
+
+    fn visible_points(&self, x0: f64, x1: f64) -> impl Iterator<Item = (usize, (f64, f64))> + '_ {
+        self.visible(x0, x1).map(|position| {
+            let index = self.nth_by_x(position);
+            (index, self.points[index])
+        })
+    }
+}
+
+// "Nice" tick values (steps of 1, 2 or 5 times a power of ten) covering
+// `min..=max` with about `target` ticks, and the step between them.
+fn nice_ticks(min: f64, max: f64, target: usize) -> (Vec<f64>, f64) {
+    let span = max - min;
+    if span <= 0.0 || !span.is_finite() {
+        return (vec![min], 1.0);
+    }
+    let raw = span / target.max(1) as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = magnitude
+        * match raw / magnitude {
+            n if n < 1.5 => 1.0,
+            n if n < 3.0 => 2.0,
+            n if n < 7.0 => 5.0,
+            _ => 10.0,
+        };
+    let first = (min / step).ceil();
+    let ticks = (0..)
+        .map(|i| (first + i as f64) * step)
+        .take_while(|t| *t <= max + step * 1e-9)
+        .collect();
+    (ticks, step)
+}
+
+// Enough decimals to tell ticks `step` apart.
+fn format_tick(value: f64, step: f64) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    format!("{:.*}", decimals, value)
+}
+
+// Maps data coordinates to window pixels for one frame.
+#[derive(Clone, Copy)]
+struct Mapping {
+    x: (f64, f64),
+    y: (f64, f64),
+    plot: Rect,
+}
+
+impl Mapping {
+    fn px(&self, (x, y): (f64, f64)) -> Point {
+        let fx = (x - self.x.0) / (self.x.1 - self.x.0);
+        let fy = (y - self.y.0) / (self.y.1 - self.y.0);
+        Point::new(
+            self.plot.left + fx as f32 * self.plot.width(),
+            self.plot.bottom - fy as f32 * self.plot.height(),
+        )
+    }
+
+    fn x_at(&self, px: f32) -> f64 {
+        self.x.0 + ((px - self.plot.left) / self.plot.width()) as f64 * (self.x.1 - self.x.0)
+    }
+
+    fn x_per_pixel(&self) -> f64 {
+        (self.x.1 - self.x.0) / self.plot.width().max(1.0) as f64
+    }
+}
+
+// Reduces a sorted run of points to at most four per pixel column (first,
+// min, max and last), which draws the same line as the full data. This is
+// what keeps 100k-point series cheap.
+fn decimate(points: &[(f64, f64)], map: &Mapping) -> Vec<Point> {
+    if points.len() as f32 <= map.plot.width() * 4.0 {
+        return points.iter().map(|p| map.px(*p)).collect();
+    }
+    let mut out = Vec::with_capacity(map.plot.width() as usize * 4);
+    let mut column = i64::MIN;
+    let mut bucket: Vec<usize> = Vec::new();
+    let flush = |bucket: &mut Vec<usize>, out: &mut Vec<Point>| {
+        if let (Some(&first), Some(&last)) = (bucket.first(), bucket.last()) {
+            let by_y = |a: &&usize, b: &&usize| points[**a].1.total_cmp(&points[**b].1);
+            let min = *bucket.iter().min_by(by_y).unwrap_or(&first);
+            let max = *bucket.iter().max_by(by_y).unwrap_or(&first);
+            let mut keep = [first, min, max, last];
+            keep.sort_unstable();
+            let mut previous = None;
+            for index in keep {
+                if previous != Some(index) {
+                    out.push(map.px(points[index]));
+                    previous = Some(index);
+                }
+            }
+        }
+        bucket.clear();
+    };
+    for (index, point) in points.iter().enumerate() {
+        let c = map.px(*point).x.floor() as i64;
+        if c != column {
+            flush(&mut bucket, &mut out);
+            column = c;
+        }
+        bucket.push(index);
+    }
+    flush(&mut bucket, &mut out);
+    out
+}
+
+// A plot of one or more series with axes, a legend and a tooltip for the
+// point under the pointer. The wheel zooms the x axis around the pointer,
+// dragging pans it and a double click resets the view. Unless a fixed y
+// range is set, the y axis fits whatever is visible.
+pub struct Chart {
+    series: Vec<Series>,
+    // Smallest and largest (x, y) over every series, refreshed whenever the
+    // series change so the pointer doesn't rescan all the data.
+    bounds: Option<((f64, f64), (f64, f64))>,
+    y_range: Option<(f64, f64)>,
+    // The x range shown after zooming or panning; `None` shows all data.
+    view: Option<(f64, f64)>,
+    show_legend: bool,
+    x_format: Option<Box<dyn Fn(f64) -> String>>,
+    y_format: Option<Box<dyn Fn(f64) -> String>>,
+    rect: Rect,
+    // (series, point) under the pointer.
+    hover: Option<(usize, usize)>,
+    // Pointer x and view when a pan began.
+    drag: Option<(f32, (f64, f64))>,
+    last_click: Option<Instant>,
+}
+
+impl Default for Chart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Chart {
+    pub fn new() -> Self {
+        Self {
+            series: Vec::new(),
+            bounds: None,
+            y_range: None,
+            view: None,
+            show_legend: true,
+            x_format: None,
+            y_format: None,
+            rect: Rect::default(),
+            hover: None,
+            drag: None,
+            last_click: None,
+        }
+    }
+
+    pub fn with_series(mut self, series: Series) -> Self {
+        self.series.push(series);
+        self.update_bounds();
+        self
+    }
+
+    pub fn with_y_range(mut self, min: f64, max: f64) -> Self {
+        self.y_range = Some((min, max));
+        self
+    }
+
+    pub fn with_legend(mut self, show: bool) -> Self {
+        self.show_legend = show;
+        self
+    }
+
+    // Formats x tick labels and tooltips, e.g. to show timestamps.
+    pub fn with_x_format<F: Fn(f64) -> String + 'static>(mut self, f: F) -> Self {
+        self.x_format = Some(Box::new(f));
+        self
+    }
+
+    pub fn with_y_format<F: Fn(f64) -> String + 'static>(mut self, f: F) -> Self {
+        self.y_format = Some(Box::new(f));
+        self
+    }
+
+    pub fn series(&self) -> &[Series] {
+        &self.series
+    }
+
+    pub fn add_series(&mut self, series: Series) {
+        self.series.push(series);
+        self.update_bounds();
+        context::request_redraw();
+    }
+
+    pub fn clear(&mut self) {
+        self.series.clear();
+        self.update_bounds();
+        self.hover = None;
+        context::request_redraw();
+    }
+
+    // Replaces a series' points, e.g. when new samples arrive.
+    pub fn set_points(&mut self, series: usize, points: Vec<(f64, f64)>) {
+        if let Some(s) = self.series.get_mut(series) {
+            s.set_points(points);
+            self.update_bounds();
+            self.hover = None;
+            context::request_redraw();
+        }
+    }
+
+    fn update_bounds(&mut self) {
+        let mut x = (f64::INFINITY, f64::NEG_INFINITY);
+        let mut y = (f64::INFINITY, f64::NEG_INFINITY);
+        for series in &self.series {
+            for &(px, py) in &series.points {
+                x = (x.0.min(px), x.1.max(px));
+                y = (y.0.min(py), y.1.max(py));
+            }
+        }
+        self.bounds = (x.0.is_finite() && y.0.is_finite()).then_some((x, y));
+    }
+
+    pub fn set_y_range(&mut self, range: Option<(f64, f64)>) {
+        self.y_range = range;
+        context::request_redraw();
+    }
+
+    pub fn x_view(&self) -> Option<(f64, f64)> {
+        self.view
+    }
+
+    pub fn set_x_view(&mut self, view: Option<(f64, f64)>) {
+        self.view = view.filter(|(min, max)| max > min);
+        context::request_redraw();
+    }
+
+    pub fn reset_view(&mut self) {
+        self.set_x_view(None);
+    }
+
+    fn color(&self, index: usize) -> Color {
+        match (self.series[index].color, index) {
+            (Some(color), _) => color,
+            (None, 0) => theme::current().accent,
+            (None, i) => PALETTE[(i - 1) % PALETTE.len()],
+        }
+    }
+
+    fn plot_rect(&self) -> Rect {
+        Rect::from_ltrb(
+            self.rect.left + MARGIN_LEFT,
+            self.rect.top + MARGIN_TOP,
+            self.rect.right - MARGIN_RIGHT,
+            self.rect.bottom - MARGIN_BOTTOM,
+        )
+    }
+
+    fn has_bars(&self) -> bool {
+        self.series.iter().any(|s| s.kind == SeriesKind::Bar)
+    }
+
+    fn data_x_range(&self) -> (f64, f64) {
+        let range = match self.bounds {
+            Some((x, _)) => x,
+            None => return (0.0, 1.0),
+        };
+        if range.0 == range.1 {
+            return (range.0 - 0.5, range.1 + 0.5);
+        }
+        if self.has_bars() {
+            // Some room either side so the outer bars aren't cut in half.
+            let pad = (range.1 - range.0) * 0.02;
+            return (range.0 - pad, range.1 + pad);
+        }
+        range
+    }
+
+    fn y_range_for(&self, x: (f64, f64)) -> (f64, f64) {
+        if let Some(range) = self.y_range {
+            return range;
+        }
+        let mut range = (f64::INFINITY, f64::NEG_INFINITY);
+        for series in &self.series {
+            for (_, (px, py)) in series.visible_points(x.0, x.1) {
+                if px >= x.0 && px <= x.1 {
+                    range = (range.0.min(py), range.1.max(py));
+                }
+            }
+        }
+        self.fit_y(range.0.is_finite().then_some(range))
+    }
+
+    // Pads the y values in view, or uses the fixed range if there is one.
+    fn fit_y(&self, range: Option<(f64, f64)>) -> (f64, f64) {
+        if let Some(range) = self.y_range {
+            return range;
+        }
+        let mut range = match range {
+            Some(range) => range,
+            None => return (0.0, 1.0),
+        };
+        if self.has_bars() || self.series.iter().any(|s| s.kind == SeriesKind::Area) {
+            range = (range.0.min(0.0), range.1.max(0.0));
+        }
+        if range.0 == range.1 {
+            return (range.0 - 1.0, range.1 + 1.0);
+        }
+        let pad = (range.1 - range.0) * 0.05;
+        (range.0 - pad, range.1 + pad)
+    }
+
+    fn mapping(&self) -> Mapping {
+        let (x, y) = match self.view {
+            Some(view) => (view, self.y_range_for(view)),
+            // Everything is in view, so the cached bounds are what's visible.
+            None => (self.data_x_range(), self.fit_y(self.bounds.map(|(_, y)| y))),
+        };
+        Mapping {
+            x,
+            y,
+            plot: self.plot_rect(),
+        }
+    }
+
+    fn format_x(&self, value: f64, step: f64) -> String {
+        match &self.x_format {
+            Some(format) => format(value),
+            None => format_tick(value, step),
+        }
+    }
+
+    fn format_y(&self, value: f64, step: f64) -> String {
+        match &self.y_format {
+            Some(format) => format(value),
+            None => format_tick(value, step),
+        }
+    }
+
+    fn tick_font() -> Font {
+        let mut font = theme::current().font();
+        font.set_size(TICK_FONT_SIZE);
+        font
+    }
+
+    fn draw_axes(&self, canvas: &mut Canvas, map: &Mapping) {
+        let theme = theme::current();
+        let font = Self::tick_font();
+        let plot = map.plot;
+
+        let mut grid = Paint::default();
+        grid.set_color(theme.border.with_a(60));
+        let mut text = Paint::default();
+        text.set_anti_alias(true);
+        text.set_color(theme.foreground);
+
+        let (x_ticks, x_step) = nice_ticks(map.x.0, map.x.1, (plot.width() / 90.0).max(2.0) as usize);
+        for x in x_ticks {
+            let px = map.px((x, map.y.0)).x;
+            canvas.draw_line((px, plot.top), (px, plot.bottom), &grid);
+            let label = self.format_x(x, x_step);
+            let width = font.measure_str(&label, None).0;
+            canvas.draw_str(&label, (px - width / 2.0, plot.bottom + 16.0), &font, &text);
+        }
+        let (y_ticks, y_step) = nice_ticks(map.y.0, map.y.1, (plot.height() / 40.0).max(2.0) as usize);
+        for y in y_ticks {
+            let py = map.px((map.x.0, y)).y;
+            canvas.draw_line((plot.left, py), (plot.right, py), &grid);
+            let label = self.format_y(y, y_step);
+            let width = font.measure_str(&label, None).0;
+            canvas.draw_str(&label, (plot.left - width - 6.0, py + font.size() / 3.0), &font, &text);
+        }
+
+        let mut axis = Paint::default();
+        axis.set_color(theme.border);
+        canvas.draw_line((plot.left, plot.top), (plot.left, plot.bottom), &axis);
+        canvas.draw_line((plot.left, plot.bottom), (plot.right, plot.bottom), &axis);
+    }
+
+    fn draw_series(&self, canvas: &mut Canvas, map: &Mapping, index: usize, bar_slot: (usize, usize)) {
+        let series = &self.series[index];
+        let visible = series.visible(map.x.0, map.x.1);
+        if visible.is_empty() {
+            return;
+        }
+        // Only meaningful for sorted series; scatter goes through `by_x`.
+        let points = &series.points[visible.clone()];
+        let color = self.color(index);
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(color);
+
+        match series.kind {
+            SeriesKind::Line | SeriesKind::Area => {
+                let pixels = decimate(points, map);
+                let mut path = Path::new();
+                path.add_poly(&pixels, false);
+                if series.kind == SeriesKind::Area {
+                    let base = map.px((map.x.0, 0.0)).y.clamp(map.plot.top, map.plot.bottom);
+                    let mut area = path.clone();
+                    area.line_to((pixels[pixels.len() - 1].x, base));
+                    area.line_to((pixels[0].x, base));
+                    area.close();
+                    paint.set_color(color.with_a(60));
+                    canvas.draw_path(&area, &paint);
+                    paint.set_color(color);
+                }
+                paint.set_style(PaintStyle::Stroke);
+                paint.set_stroke_width(1.5);
+                canvas.draw_path(&path, &paint);
+            }
+            SeriesKind::Scatter => {
+                // Points come in x order, so a pixel column is finished once
+                // the next one starts. `drawn[row]` is the last column that
+                // drew a point on that row, and each pixel is drawn once.
+                let mut drawn = vec![i32::MIN; map.plot.height().ceil() as usize + 1];
+                for (_, point) in series.visible_points(map.x.0, map.x.1) {
+                    let p = map.px(point);
+                    if !map.plot.contains(p) {
+                        continue;
+                    }
+                    let (column, row) = (p.x as i32, (p.y - map.plot.top) as usize);
+                    if drawn[row] != column {
+                        drawn[row] = column;
+                        canvas.draw_circle(p, 2.5, &paint);
+                    }
+                }
+            }
+            SeriesKind::Bar => {
+                let (slot, slots) = bar_slot;
+                let spacing = points
+                    .windows(2)
+                    .map(|w| w[1].0 - w[0].0)
+                    .filter(|d| *d > 0.0)
+                    .fold(f64::INFINITY, f64::min);
+                let spacing = if spacing.is_finite() { spacing } else { (map.x.1 - map.x.0) / 10.0 };
+                let group = (spacing / map.x_per_pixel()) as f32 * 0.8;
+                let width = (group / slots as f32).max(1.0);
+                let base = map.px((map.x.0, 0.0)).y.clamp(map.plot.top, map.plot.bottom);
+                // Narrower than a pixel: keep only the tallest bar per column,
+                // i.e. the one furthest from the base.
+                let mut tallest: Option<(i32, Rect)> = None;
+                for point in points {
+                    let p = map.px(*point);
+                    let left = p.x - group / 2.0 + width * slot as f32;
+                    let bar = Rect::from_ltrb(left, p.y.min(base), left + width, p.y.max(base));
+                    if width > 1.0 {
+                        canvas.draw_rect(bar, &paint);
+                        continue;
+                    }
+                    let column = left.floor() as i32;
+                    match tallest {
+                        Some((c, kept)) if c == column => {
+                            if bar.height() > kept.height() {
+                                tallest = Some((column, bar));
+                            }
+                        }
+                        _ => {
+                            if let Some((_, kept)) = tallest.replace((column, bar)) {
+                                canvas.draw_rect(kept, &paint);
+                            }
+                        }
+                    }
+                }
+                if let Some((_, kept)) = tallest {
+                    canvas.draw_rect(kept, &paint);
+                }
+            }
+        }
+    }
+
+    fn draw_legend(&self, canvas: &mut Canvas, plot: Rect) {
+        let theme = theme::current();
+        let font = Self::tick_font();
+        let row = 16.0;
+        let width = self
+            .series
+            .iter()
+            .map(|s| font.measure_str(&s.name, None).0)
+            .fold(0.0, f32::max)
+            + 26.0;
+        let legend = Rect::from_xywh(plot.right - width - 8.0, plot.top + 8.0, width, row * self.series.len() as f32 + 6.0);
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(theme.background.with_a(220));
+        canvas.draw_round_rect(legend, 4.0, 4.0, &paint);
+        for (index, series) in self.series.iter().enumerate() {
+            let top = legend.top + 3.0 + row * index as f32;
+            paint.set_color(self.color(index));
+            canvas.draw_rect(Rect::from_xywh(legend.left + 6.0, top + 4.0, 10.0, 8.0), &paint);
+            paint.set_color(theme.foreground);
+            canvas.draw_str(&series.name, (legend.left + 22.0, top + row / 2.0 + font.size() / 3.0), &font, &paint);
+        }
+    }
+
+    fn draw_tooltip(&self, canvas: &mut Canvas, map: &Mapping, (series, point): (usize, usize)) {
+        let theme = theme::current();
+        let font = Self::tick_font();
+        let data = self.series[series].points[point];
+        let p = map.px(data);
+        if !map.plot.contains(p) {
+            return;
+        }
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(self.color(series));
+        canvas.draw_circle(p, 4.0, &paint);
+
+        let x_step = nice_ticks(map.x.0, map.x.1, 10).1 / 10.0;
+        let y_step = nice_ticks(map.y.0, map.y.1, 10).1 / 10.0;
+        let label = format!(
+            "{}: {}, {}",
+            self.series[series].name,
+            self.format_x(data.0, x_step),
+            self.format_y(data.1, y_step)
+        );
+        let width = font.measure_str(&label, None).0 + 12.0;
+        let height = 20.0;
+        // Above and right of the point, flipped to stay inside the plot.
+        let left = if p.x + 8.0 + width > map.plot.right { p.x - 8.0 - width } else { p.x + 8.0 };
+        let top = if p.y - 8.0 - height < map.plot.top { p.y + 8.0 } else { p.y - 8.0 - height };
+        let tip = Rect::from_xywh(left, top, width, height);
+
+        paint.set_color(theme.surface);
+        canvas.draw_round_rect(tip, 4.0, 4.0, &paint);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_color(theme.border);
+        canvas.draw_round_rect(tip, 4.0, 4.0, &paint);
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(theme.foreground);
+        canvas.draw_str(&label, (tip.left + 6.0, tip.center_y() + font.size() / 3.0), &font, &paint);
+    }
+
+    // The point nearest the pointer within `HOVER_DISTANCE`.
+    fn point_at(&self, x: f32, y: f32) -> Option<(usize, usize)> {
+        let map = self.mapping();
+        let cursor = Point::new(x, y);
+        let data_x = map.x_at(x);
+        let reach = map.x_per_pixel() * HOVER_DISTANCE as f64;
+        let mut best: Option<((usize, usize), f32)> = None;
+        for (s, series) in self.series.iter().enumerate() {
+            for (i, point) in series.visible_points(data_x - reach, data_x + reach) {
+                let distance = (map.px(point) - cursor).length();
+                if distance <= HOVER_DISTANCE && best.is_none_or(|(_, d)| distance < d) {
+                    best = Some(((s, i), distance));
+                }
+            }
+        }
+        best.map(|(hit, _)| hit)
+    }
+}
+
+impl Widget for Chart {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let map = self.mapping();
+        if map.plot.width() <= 0.0 || map.plot.height() <= 0.0 {
+            return;
+        }
+        self.draw_axes(canvas, &map);
+
+        canvas.save();
+        canvas.clip_rect(map.plot, None, Some(true));
+        let bar_count = self.series.iter().filter(|s| s.kind == SeriesKind::Bar).count();
+        let mut bar_slot = 0;
+        for index in 0..self.series.len() {
+            let slot = (bar_slot, bar_count.max(1));
+            if self.series[index].kind == SeriesKind::Bar {
+                bar_slot += 1;
+            }
+            self.draw_series(canvas, &map, index, slot);
+        }
+        canvas.restore();
+
+        if self.show_legend && !self.series.is_empty() {
+            self.draw_legend(canvas, map.plot);
+        }
+        if let Some(hover) = self.hover {
+            self.draw_tooltip(canvas, &map, hover);
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                if let Some((start, view)) = self.drag {
+                    let shift = (*x - start) as f64 * self.mapping().x_per_pixel();
+                    self.view = Some((view.0 - shift, view.1 - shift));
+                    context::set_cursor(CursorIcon::Grabbing);
+                    context::request_redraw();
+                    return true;
+                }
+                let hover = if self.plot_rect().contains(Point::new(*x, *y)) {
+                    self.point_at(*x, *y)
+                } else {
+                    None
+                };
+                if hover != self.hover {
+                    self.hover = hover;
+                    context::request_redraw();
+                }
+                false
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. }
+                if self.plot_rect().contains(Point::new(*x, *y)) =>
+            {
+                let now = Instant::now();
+                if self.last_click.is_some_and(|at| now - at < DOUBLE_CLICK) {
+                    self.last_click = None;
+                    self.reset_view();
+                    return true;
+                }
+                self.last_click = Some(now);
+                self.drag = Some((*x, self.mapping().x));
+                self.hover = None;
+                true
+            }
+            Event::MouseUp { button: MouseButton::Left, .. } if self.drag.is_some() => {
+                self.drag = None;
+                true
+            }
+            Event::Scroll { x, y, delta, .. } if self.plot_rect().contains(Point::new(*x, *y)) => {
+                let (_, amount) = scroll_amount(*delta);
+                let map = self.mapping();
+                // Wheel up zooms in, keeping the value under the pointer fixed.
+                let factor = ZOOM_STEP.powf(-amount as f64 / 40.0);
+                let anchor = map.x_at(*x);
+                let view = (anchor - (anchor - map.x.0) * factor, anchor + (map.x.1 - anchor) * factor);
+                if view.1 - view.0 > f64::EPSILON * anchor.abs().max(1.0) * 16.0 {
+                    self.view = Some(view);
+                    self.hover = None;
+                    context::request_redraw();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("series", self.series.len().to_string()),
+            ("view", format!("{:?}", self.view)),
+        ]
+    }
+
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        let colors: Vec<String> = (0..self.series.len()).map(|i| theme::color_hex(self.color(i))).collect();
+        vec![
+            ("legend", self.show_legend.to_string()),
+            ("series_colors", colors.join(" ")),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (400.0, 240.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{MemoryBackend, RenderBackend};
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9)
+    }
+
+    #[test]
+    fn nice_ticks_use_round_steps() {
+        let (ticks, step) = nice_ticks(0.0, 10.0, 5);
+        assert_eq!(step, 2.0);
+        assert!(close(&ticks, &[0.0, 2.0, 4.0, 6.0, 8.0, 10.0]));
+
+        let (ticks, step) = nice_ticks(0.13, 0.91, 4);
+        assert!((step - 0.2).abs() < 1e-12);
+        assert!(close(&ticks, &[0.2, 0.4, 0.6, 0.8]));
+
+        let (ticks, step) = nice_ticks(-340.0, 1200.0, 3);
+        assert_eq!(step, 500.0);
+        assert!(close(&ticks, &[0.0, 500.0, 1000.0]));
+    }
+
+    #[test]
+    fn nice_ticks_survive_empty_and_broken_ranges() {
+        assert_eq!(nice_ticks(5.0, 5.0, 4), (vec![5.0], 1.0));
+        assert_eq!(nice_ticks(5.0, 1.0, 4), (vec![5.0], 1.0));
+        assert_eq!(nice_ticks(0.0, f64::INFINITY, 4).0, vec![0.0]);
+        assert_eq!(nice_ticks(0.0, 1.0, 0).1, 1.0);
+    }
+
+    #[test]
+    fn format_tick_shows_enough_decimals() {
+        assert_eq!(format_tick(2.0, 1.0), "2");
+        assert_eq!(format_tick(0.25, 0.05), "0.25");
+        assert_eq!(format_tick(1500.0, 500.0), "1500");
+    }
+
+    fn map(width: f32) -> Mapping {
+        Mapping {
+            x: (0.0, 1000.0),
+            y: (-1.0, 1.0),
+            plot: Rect::from_xywh(0.0, 0.0, width, 100.0),
+        }
+    }
+
+    #[test]
+    fn decimate_keeps_short_runs_as_they_are() {
+        let points: Vec<(f64, f64)> = (0..50).map(|i| (i as f64 * 20.0, 0.0)).collect();
+        assert_eq!(decimate(&points, &map(100.0)).len(), 50);
+    }
+
+    #[test]
+    fn decimate_keeps_each_columns_extremes_and_ends() {
+        let points: Vec<(f64, f64)> = (0..10_000).map(|i| (i as f64 / 10.0, (i as f64 * 0.37).sin())).collect();
+        let map = map(100.0);
+        let out = decimate(&points, &map);
+        assert!(out.len() <= 100 * 4 + 4);
+        assert_eq!(out.first(), Some(&map.px(points[0])));
+        assert_eq!(out.last(), Some(&map.px(points[points.len() - 1])));
+
+        // Each column's highest and lowest pixels survive.
+        for column in 0..100 {
+            let in_column = |p: &Point| p.x.floor() as i32 == column;
+            let all: Vec<Point> = points.iter().map(|p| map.px(*p)).filter(in_column).collect();
+            let kept: Vec<Point> = out.iter().copied().filter(in_column).collect();
+            let top = |ps: &[Point]| ps.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+            let bottom = |ps: &[Point]| ps.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+            assert_eq!(top(&all), top(&kept));
+            assert_eq!(bottom(&all), bottom(&kept));
+        }
+    }
+
+    #[test]
+    fn thin_bars_keep_the_tallest_in_each_column() {
+        // 4000 bars across a 200px plot: one in twenty is tall, and it is
+        // never the first in its column.
+        let points = (0..4000).map(|i| (i as f64, if i % 20 == 7 { 100.0 } else { 1.0 })).collect();
+        let mut chart = Chart::new()
+            .with_series(Series::bar("load", points))
+            .with_y_range(0.0, 100.0)
+            .with_legend(false);
+        let mut backend = MemoryBackend::new(268, 112);
+        backend.begin_frame();
+        chart.layout(Rect::from_wh(268.0, 112.0));
+        chart.draw(backend.canvas());
+        backend.present();
+
+        let accent = theme::current().accent;
+        let tall = (60..250).filter(|x| backend.pixel(*x, 20) == Some(accent)).count();
+        assert!(tall > 150, "only {} columns show a tall bar", tall);
+    }
+
+    #[test]
+    fn scatter_hit_testing_finds_unsorted_points_in_a_zoomed_view() {
+        let points = vec![(90.0, 5.0), (10.0, 5.0), (50.0, 5.0), (30.0, 5.0)];
+        let mut chart = Chart::new().with_series(Series::scatter("dots", points)).with_y_range(0.0, 10.0);
+        chart.layout(Rect::from_wh(268.0, 112.0));
+        chart.set_x_view(Some((20.0, 60.0)));
+
+        let map = chart.mapping();
+        let target = map.px((50.0, 5.0));
+        assert_eq!(chart.point_at(target.x + 2.0, target.y), Some((0, 2)));
+        let other = map.px((30.0, 5.0));
+        assert_eq!(chart.point_at(other.x, other.y - 3.0), Some((0, 3)));
+    }
+
+    #[test]
+    fn cached_bounds_follow_new_points() {
+        let mut chart = Chart::new().with_series(Series::line("a", vec![(0.0, 1.0), (10.0, 3.0)]));
+        chart.layout(Rect::from_wh(268.0, 112.0));
+        assert_eq!(chart.mapping().x, (0.0, 10.0));
+
+        chart.set_points(0, vec![(-5.0, 0.0), (20.0, 40.0)]);
+        let map = chart.mapping();
+        assert_eq!(map.x, (-5.0, 20.0));
+        assert!(map.y.0 < 0.0 && map.y.1 > 40.0);
+
+        chart.clear();
+        assert_eq!(chart.mapping().x, (0.0, 1.0));
+    }
+}
@@ -0,0 +1,242 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Path, Point, Rect};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const BOX_SIZE: f32 = 16.0;
+const LABEL_GAP: f32 = 8.0;
+const HEIGHT: f32 = 24.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    // "Some of these": drawn as a dash. Only reachable by clicking when the
+    // checkbox is tri-state.
+    Indeterminate,
+}
+
+// Draws the box itself. Also used by the table's checkbox columns.
+pub(crate) fn draw_box(canvas: &mut Canvas, bx: Rect, state: CheckState, hovered: bool, pressed: bool, enabled: bool) {
+    let theme = theme::current();
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_color(if !enabled {
+        theme.surface
+    } else if pressed {
+        theme.surface_pressed
+    } else if state != CheckState::Unchecked {
+        theme.accent
+    } else if hovered {
+        theme.surface_hover
+    } else {
+        theme.background
+    });
+    canvas.draw_round_rect(bx, 3.0, 3.0, &paint);
+
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_stroke_width(1.0);
+    paint.set_color(theme.border);
+    canvas.draw_round_rect(bx, 3.0, 3.0, &paint);
+
+    let mut path = Path::new();
+    match state {
+        CheckState::Unchecked => return,
+        CheckState::Checked => {
+            path.move_to((bx.left + bx.width() * 0.22, bx.center_y()));
+            path.line_to((bx.left + bx.width() * 0.42, bx.bottom - bx.height() * 0.25));
+            path.line_to((bx.right - bx.width() * 0.22, bx.top + bx.height() * 0.25));
+        }
+        CheckState::Indeterminate => {
+            path.move_to((bx.left + bx.width() * 0.25, bx.center_y()));
+            path.line_to((bx.right - bx.width() * 0.25, bx.center_y()));
+        }
+    }
+    paint.set_stroke_width(2.0);
+    paint.set_color(if enabled { theme.accent_foreground } else { theme.disabled_foreground });
+    canvas.draw_path(&path, &paint);
+}
+
+pub struct Checkbox {
+    label: String,
+    state: CheckState,
+    tristate: bool,
+    enabled: bool,
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+    rect: Rect,
+    on_changed: Option<Box<dyn FnMut(CheckState)>>,
+}
+
+impl Checkbox {
+    pub fn new(label: &str, checked: bool) -> Self {
+        Self {
+            label: label.to_string(),
+            state: if checked { CheckState::Checked } else { CheckState::Unchecked },
+            tristate: false,
+            enabled: true,
+            hovered: false,
+            pressed: false,
+            focused: false,
+            rect: Rect::default(),
+            on_changed: None,
+        }
+    }
+
+    pub fn tristate(mut self, tristate: bool) -> Self {
+        self.tristate = tristate;
+        self
+    }
+
+    pub fn on_changed<F: FnMut(CheckState) + 'static>(mut self, f: F) -> Self {
+        self.on_changed = Some(Box::new(f));
+        self
+    }
+
+    pub fn state(&self) -> CheckState {
+        self.state
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.state == CheckState::Checked
+    }
+
+    // Doesn't fire the callback; that is only for changes made by the user.
+    pub fn set_state(&mut self, state: CheckState) {
+        self.state = state;
+        context::request_redraw();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.hovered = false;
+            self.pressed = false;
+            self.focused = false;
+        }
+        context::request_redraw();
+    }
+
+    fn toggle(&mut self) {
+        self.state = match (self.state, self.tristate) {
+            (CheckState::Unchecked, _) => CheckState::Checked,
+            (CheckState::Checked, true) => CheckState::Indeterminate,
+            (CheckState::Checked, false) => CheckState::Unchecked,
+            (CheckState::Indeterminate, true) => CheckState::Unchecked,
+            (CheckState::Indeterminate, false) => CheckState::Checked,
+        };
+        if let Some(on_changed) = &mut self.on_changed {
+            on_changed(self.state);
+        }
+        context::request_redraw();
+    }
+
+    fn box_rect(&self) -> Rect {
+        Rect::from_xywh(self.rect.left, self.rect.center_y() - BOX_SIZE / 2.0, BOX_SIZE, BOX_SIZE)
+    }
+}
+
+impl Widget for Checkbox {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let bx = self.box_rect();
+        draw_box(canvas, bx, self.state, self.hovered, self.pressed, self.enabled);
+        if self.focused {
+            theme::draw_focus_ring(canvas, bx, 3.0);
+        }
+
+        let font = theme.font();
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(if self.enabled { theme.foreground } else { theme.disabled_foreground });
+        let baseline = self.rect.center_y() + font.size() / 3.0;
+        canvas.draw_str(&self.label, (bx.right + LABEL_GAP, baseline), &font, &paint);
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                let hovered = self.rect.contains(Point::new(*x, *y));
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    context::request_redraw();
+                }
+                false
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => {
+                self.focused = self.rect.contains(Point::new(*x, *y));
+                self.pressed = self.focused;
+                self.pressed
+            }
+            Event::MouseUp { x, y, button: MouseButton::Left, .. } if self.pressed => {
+                self.pressed = false;
+                if self.rect.contains(Point::new(*x, *y)) {
+                    self.toggle();
+                }
+                true
+            }
+            Event::KeyDown { key: Some(VirtualKeyCode::Space), .. } if self.focused => {
+                self.toggle();
+                true
+            }
+            Event::FocusLost => {
+                self.focused = false;
+                self.pressed = false;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("label", self.label.clone()),
+            ("state", format!("{:?}", self.state)),
+            ("enabled", self.enabled.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let font = theme::current().font();
+        (BOX_SIZE + LABEL_GAP + font.measure_str(&self.label, None).0, HEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::testing::render;
+
+    #[test]
+    fn checkbox_fills_the_box_when_checked() {
+        let theme = theme::current();
+        let mut checked = Checkbox::new("Wrap", true);
+        let mut backend = render(&mut checked, Rect::from_xywh(10.0, 10.0, 120.0, 24.0));
+        // Top left of the 16px box, clear of the border and the tick.
+        assert_eq!(backend.pixel(14, 18), Some(theme.accent));
+
+        let mut unchecked = Checkbox::new("Wrap", false);
+        let mut backend = render(&mut unchecked, Rect::from_xywh(10.0, 10.0, 120.0, 24.0));
+        assert_eq!(backend.pixel(14, 18), Some(theme.background));
+    }
+}
@@ -0,0 +1,638 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::overlay::{Placement, Popup, PopupId};
+use crate::widget::scroll::scroll_amount;
+use crate::widget::textedit::TextBuffer;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Path, Point, Rect};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const HEIGHT: f32 = 28.0;
+const ROW_HEIGHT: f32 = 24.0;
+const MAX_ROWS: usize = 10;
+const PADDING: f32 = 8.0;
+const ARROW_WIDTH: f32 = 24.0;
+// Type-ahead starts over after this long without a keystroke.
+const TYPE_AHEAD_RESET: Duration = Duration::from_millis(1000);
+
+type Changed<T> = Box<dyn FnMut(usize, &T)>;
+
+// Accumulates typed characters into a prefix for jumping to an item.
+#[derive(Default)]
+struct TypeAhead {
+    prefix: String,
+    last: Option<Instant>,
+}
+
+impl TypeAhead {
+    fn push(&mut self, c: char) -> &str {
+        let now = Instant::now();
+        if self.last.is_none_or(|last| now - last > TYPE_AHEAD_RESET) {
+            self.prefix.clear();
+        }
+        self.last = Some(now);
+        self.prefix.extend(c.to_lowercase());
+        &self.prefix
+    }
+}
+
+// Index of the first label starting with `prefix`, ignoring case.
+fn find_prefix<'a>(labels: impl Iterator<Item = &'a String>, prefix: &str) -> Option<usize> {
+    labels.enumerate().find(|(_, label)| label.to_lowercase().starts_with(prefix)).map(|(i, _)| i)
+}
+
+// State shared between the box and its popup list. The popup lives in the
+// overlay, so it reports a choice here and the box picks it up on its next
+// event or draw.
+struct ComboState {
+    labels: Vec<String>,
+    editable: bool,
+    text: TextBuffer,
+    // Item indices that pass the filter, in display order.
+    visible: Vec<usize>,
+    // Position in `visible`.
+    highlighted: Option<usize>,
+    offset: f32,
+    chosen: Option<usize>,
+    open: Option<PopupId>,
+    type_ahead: TypeAhead,
+}
+
+impl ComboState {
+    // Shows the items containing `filter`, ignoring case; all of them when
+    // it is empty.
+    fn refilter(&mut self, filter: &str) {
+        let filter = filter.to_lowercase();
+        self.visible = (0..self.labels.len())
+            .filter(|i| filter.is_empty() || self.labels[*i].to_lowercase().contains(&filter))
+            .collect();
+        self.highlighted = if self.visible.is_empty() { None } else { Some(0) };
+        self.offset = 0.0;
+    }
+
+    fn highlight(&mut self, position: usize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let position = position.min(self.visible.len() - 1);
+        self.highlighted = Some(position);
+        let top = position as f32 * ROW_HEIGHT;
+        let page = MAX_ROWS as f32 * ROW_HEIGHT;
+        if top < self.offset {
+            self.offset = top;
+        } else if top + ROW_HEIGHT > self.offset + page {
+            self.offset = top + ROW_HEIGHT - page;
+        }
+        context::request_redraw();
+    }
+
+    fn choose(&mut self, position: usize) {
+        let index = self.visible[position];
+        self.chosen = Some(index);
+        if self.editable {
+            let label = self.labels[index].clone();
+            self.text.set_text(&label);
+        }
+        self.close();
+    }
+
+    fn close(&mut self) {
+        if let Some(id) = self.open.take() {
+            context::dismiss_popup(id);
+        }
+        context::request_redraw();
+    }
+}
+
+// A button showing the current choice that opens a list of options. The
+// editable variant is a text field whose typing filters the list.
+pub struct ComboBox<T> {
+    items: Vec<T>,
+    format: Box<dyn Fn(&T) -> String>,
+    state: Rc<RefCell<ComboState>>,
+    selected: Option<usize>,
+    enabled: bool,
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+    rect: Rect,
+    on_changed: Option<Changed<T>>,
+}
+
+impl<T: ToString + 'static> ComboBox<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        Self::with_formatter(items, |item| item.to_string())
+    }
+}
+
+impl<T: 'static> ComboBox<T> {
+    pub fn with_formatter<F: Fn(&T) -> String + 'static>(items: Vec<T>, format: F) -> Self {
+        let labels = items.iter().map(&format).collect();
+        let state = ComboState {
+            labels,
+            editable: false,
+            text: TextBuffer::default(),
+            visible: Vec::new(),
+            highlighted: None,
+            offset: 0.0,
+            chosen: None,
+            open: None,
+            type_ahead: TypeAhead::default(),
+        };
+        Self {
+            items,
+            format: Box::new(format),
+            state: Rc::new(RefCell::new(state)),
+            selected: None,
+            enabled: true,
+            hovered: false,
+            pressed: false,
+            focused: false,
+            rect: Rect::default(),
+            on_changed: None,
+        }
+    }
+
+    pub fn editable(mut self) -> Self {
+        self.state.borrow_mut().editable = true;
+        self.set_selected(self.selected);
+        self
+    }
+
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.set_selected(Some(index));
+        self
+    }
+
+    pub fn on_changed<F: FnMut(usize, &T) + 'static>(mut self, f: F) -> Self {
+        self.on_changed = Some(Box::new(f));
+        self
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.state.borrow_mut().labels = items.iter().map(&self.format).collect();
+        self.items = items;
+        self.selected = self.selected.filter(|i| *i < self.items.len());
+        self.state.borrow_mut().close();
+        context::request_redraw();
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn selected_item(&self) -> Option<&T> {
+        self.selected.map(|i| &self.items[i])
+    }
+
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|i| *i < self.items.len());
+        let mut state = self.state.borrow_mut();
+        if state.editable {
+            let text = self.selected.map(|i| state.labels[i].clone()).unwrap_or_default();
+            state.text.set_text(&text);
+        }
+        context::request_redraw();
+    }
+
+    // What the editable variant's field holds, which may not be one of the
+    // items.
+    pub fn text(&self) -> String {
+        let state = self.state.borrow();
+        if state.editable {
+            state.text.text().to_string()
+        } else {
+            self.selected.map(|i| state.labels[i].clone()).unwrap_or_default()
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state.borrow().open.is_some()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.hovered = false;
+            self.pressed = false;
+            self.focused = false;
+            self.state.borrow_mut().close();
+        }
+        context::request_redraw();
+    }
+
+    fn change(&mut self, index: usize) {
+        if self.selected == Some(index) {
+            return;
+        }
+        self.selected = Some(index);
+        if let Some(on_changed) = &mut self.on_changed {
+            on_changed(index, &self.items[index]);
+        }
+        context::request_redraw();
+    }
+
+    // Picks up a choice made in the popup.
+    fn sync(&mut self) {
+        let chosen = self.state.borrow_mut().chosen.take();
+        if let Some(index) = chosen {
+            self.change(index);
+        }
+    }
+
+    fn open(&mut self) {
+        let mut state = self.state.borrow_mut();
+        if state.open.is_some() || state.labels.is_empty() {
+            return;
+        }
+        // The editable list is filtered by the field, unless the field just
+        // shows the current selection.
+        let shows_selection = self.selected.is_some_and(|i| state.labels[i] == state.text.text());
+        let filter = if state.editable && !shows_selection { state.text.text().to_string() } else { String::new() };
+        state.refilter(&filter);
+        if let Some(position) = self.selected.and_then(|i| state.visible.iter().position(|v| *v == i)) {
+            state.highlight(position);
+        }
+
+        let id = PopupId::next();
+        state.open = Some(id);
+        let rows = state.labels.len().clamp(1, MAX_ROWS);
+        let size = (self.rect.width(), rows as f32 * ROW_HEIGHT + 2.0);
+        let list = ComboList {
+            state: self.state.clone(),
+            rect: Rect::default(),
+        };
+        let dismissed = Rc::downgrade(&self.state);
+        let popup = Popup::new(id, Box::new(list), self.rect, size)
+            .with_placement(Placement::Below)
+            .on_dismiss(move || {
+                if let Some(state) = dismissed.upgrade() {
+                    let mut state = state.borrow_mut();
+                    if state.open == Some(id) {
+                        state.open = None;
+                    }
+                }
+            });
+        context::show_popup(popup);
+        context::request_redraw();
+    }
+
+    fn on_key(&mut self, key: VirtualKeyCode, alt: bool) -> bool {
+        let editable = self.state.borrow().editable;
+        let last = self.items.len().checked_sub(1);
+        match key {
+            VirtualKeyCode::Down if alt => self.open(),
+            VirtualKeyCode::F4 => self.open(),
+            VirtualKeyCode::Space | VirtualKeyCode::Return if !editable => self.open(),
+            VirtualKeyCode::Up | VirtualKeyCode::Down if !editable => {
+                if let Some(last) = last {
+                    let target = match (key, self.selected) {
+                        (VirtualKeyCode::Up, Some(i)) => i.saturating_sub(1),
+                        (VirtualKeyCode::Down, Some(i)) => (i + 1).min(last),
+                        _ => 0,
+                    };
+                    self.change(target);
+                }
+            }
+            _ if editable => {
+                let event = Event::KeyDown { key: Some(key), modifiers: Default::default() };
+                if !self.state.borrow_mut().text.handle_event(&event) {
+                    return false;
+                }
+                context::request_redraw();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn on_char(&mut self, c: char) -> bool {
+        if c.is_control() {
+            return false;
+        }
+        let editable = self.state.borrow().editable;
+        if editable {
+            // Typing into the field opens the list filtered by the new text.
+            self.state.borrow_mut().text.insert(c);
+            self.open();
+        } else {
+            let found = {
+                let mut state = self.state.borrow_mut();
+                let state = &mut *state;
+                let prefix = state.type_ahead.push(c);
+                find_prefix(state.labels.iter(), prefix)
+            };
+            if let Some(index) = found {
+                self.change(index);
+            }
+        }
+        true
+    }
+}
+
+impl<T: 'static> Widget for ComboBox<T> {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        self.sync();
+        let theme = theme::current();
+        let state = self.state.borrow();
+        let open = state.open.is_some();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(if !self.enabled {
+            theme.surface
+        } else if state.editable {
+            theme.background
+        } else if self.pressed || open {
+            theme.surface_pressed
+        } else if self.hovered {
+            theme.surface_hover
+        } else {
+            theme.surface
+        });
+        canvas.draw_round_rect(self.rect, theme.corner_radius, theme.corner_radius, &paint);
+
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+        paint.set_color(theme.border);
+        canvas.draw_round_rect(self.rect, theme.corner_radius, theme.corner_radius, &paint);
+        if self.focused && !open {
+            theme::draw_focus_ring(canvas, self.rect, theme.corner_radius);
+        }
+
+        let font = theme.font();
+        let color = if self.enabled { theme.foreground } else { theme.disabled_foreground };
+        let text_rect = Rect::from_ltrb(
+            self.rect.left + PADDING,
+            self.rect.top,
+            self.rect.right - ARROW_WIDTH,
+            self.rect.bottom,
+        );
+        if state.editable {
+            state.text.draw(canvas, text_rect, &font, color, self.focused);
+        } else if let Some(index) = self.selected {
+            let mut text_paint = Paint::default();
+            text_paint.set_anti_alias(true);
+            text_paint.set_color(color);
+            let baseline = text_rect.center_y() + font.size() / 3.0;
+            canvas.save();
+            canvas.clip_rect(text_rect, None, Some(true));
+            canvas.draw_str(&state.labels[index], (text_rect.left, baseline), &font, &text_paint);
+            canvas.restore();
+        }
+
+        // Down chevron.
+        let (cx, cy) = (self.rect.right - ARROW_WIDTH / 2.0, self.rect.center_y());
+        let mut path = Path::new();
+        path.move_to((cx - 4.0, cy - 2.0));
+        path.line_to((cx, cy + 2.0));
+        path.line_to((cx + 4.0, cy - 2.0));
+        paint.set_color(color);
+        paint.set_stroke_width(1.5);
+        canvas.draw_path(&path, &paint);
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        self.sync();
+        if !self.enabled {
+            return false;
+        }
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                let hovered = self.rect.contains(Point::new(*x, *y));
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    context::request_redraw();
+                }
+                false
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => {
+                self.focused = self.rect.contains(Point::new(*x, *y));
+                self.pressed = self.focused;
+                self.pressed
+            }
+            Event::MouseUp { x, y, button: MouseButton::Left, .. } if self.pressed => {
+                self.pressed = false;
+                let editable = self.state.borrow().editable;
+                // The editable field only opens from its arrow.
+                let arrow = Rect::from_ltrb(self.rect.right - ARROW_WIDTH, self.rect.top, self.rect.right, self.rect.bottom);
+                let target = if editable { arrow } else { self.rect };
+                if target.contains(Point::new(*x, *y)) {
+                    self.open();
+                }
+                true
+            }
+            Event::KeyDown { key: Some(key), modifiers } if self.focused => self.on_key(*key, modifiers.alt()),
+            Event::CharInput(c) if self.focused => self.on_char(*c),
+            Event::FocusLost => {
+                self.focused = false;
+                self.pressed = false;
+                self.state.borrow_mut().close();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("items", self.items.len().to_string()),
+            ("selected", format!("{:?}", self.selected)),
+            ("enabled", self.enabled.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let font = theme::current().font();
+        let widest = self
+            .state
+            .borrow()
+            .labels
+            .iter()
+            .map(|label| font.measure_str(label, None).0)
+            .fold(0.0, f32::max);
+        (widest + PADDING + ARROW_WIDTH, HEIGHT)
+    }
+}
+
+// The popup half of a combo box. While it is open the overlay sends it all
+// keyboard input, so it also handles typing for the editable variant.
+struct ComboList {
+    state: Rc<RefCell<ComboState>>,
+    rect: Rect,
+}
+
+impl ComboList {
+    fn position_at(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.rect.contains(Point::new(x, y)) {
+            return None;
+        }
+        let state = self.state.borrow();
+        let position = ((y - self.rect.top - 1.0 + state.offset) / ROW_HEIGHT) as usize;
+        (position < state.visible.len()).then_some(position)
+    }
+
+    fn on_key(&mut self, key: VirtualKeyCode, alt: bool) -> bool {
+        let mut state = self.state.borrow_mut();
+        let current = state.highlighted;
+        let last = state.visible.len().saturating_sub(1);
+        match key {
+            VirtualKeyCode::Up if alt => state.close(),
+            VirtualKeyCode::Up => state.highlight(current.map_or(0, |p| p.saturating_sub(1))),
+            VirtualKeyCode::Down => state.highlight(current.map_or(0, |p| (p + 1).min(last))),
+            VirtualKeyCode::PageUp => state.highlight(current.unwrap_or(0).saturating_sub(MAX_ROWS)),
+            VirtualKeyCode::PageDown => state.highlight(current.unwrap_or(0) + MAX_ROWS),
+            VirtualKeyCode::Home if !state.editable => state.highlight(0),
+            VirtualKeyCode::End if !state.editable => state.highlight(last),
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Tab => match current {
+                Some(position) => state.choose(position),
+                None => state.close(),
+            },
+            VirtualKeyCode::Back | VirtualKeyCode::Delete if state.editable => {
+                let event = Event::KeyDown { key: Some(key), modifiers: Default::default() };
+                state.text.handle_event(&event);
+                let filter = state.text.text().to_string();
+                state.refilter(&filter);
+                context::request_redraw();
+            }
+            VirtualKeyCode::Left | VirtualKeyCode::Right | VirtualKeyCode::Home | VirtualKeyCode::End
+                if state.editable =>
+            {
+                let event = Event::KeyDown { key: Some(key), modifiers: Default::default() };
+                state.text.handle_event(&event);
+                context::request_redraw();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn on_char(&mut self, c: char) -> bool {
+        if c.is_control() {
+            return false;
+        }
+        let mut state = self.state.borrow_mut();
+        if state.editable {
+            state.text.insert(c);
+            let filter = state.text.text().to_string();
+            state.refilter(&filter);
+            context::request_redraw();
+        } else {
+            let state = &mut *state;
+            let prefix = state.type_ahead.push(c);
+            let labels = state.visible.iter().map(|i| &state.labels[*i]);
+            if let Some(position) = find_prefix(labels, prefix) {
+                state.highlight(position);
+            }
+        }
+        true
+    }
+}
+
+impl Widget for ComboList {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let state = self.state.borrow();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(theme.background);
+        canvas.draw_round_rect(self.rect, 4.0, 4.0, &paint);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_color(theme.border);
+        canvas.draw_round_rect(self.rect, 4.0, 4.0, &paint);
+        paint.set_style(PaintStyle::Fill);
+
+        let font = theme.font();
+        let inner = self.rect.with_inset((1.0, 1.0));
+        canvas.save();
+        canvas.clip_rect(inner, None, Some(true));
+
+        if state.visible.is_empty() {
+            paint.set_color(theme.disabled_foreground);
+            let baseline = inner.top + ROW_HEIGHT / 2.0 + font.size() / 3.0;
+            canvas.draw_str("No matches", (inner.left + PADDING, baseline), &font, &paint);
+        }
+
+        let first = (state.offset / ROW_HEIGHT) as usize;
+        for (position, index) in state.visible.iter().enumerate().skip(first).take(MAX_ROWS + 1) {
+            let row = Rect::from_xywh(
+                inner.left,
+                inner.top + position as f32 * ROW_HEIGHT - state.offset,
+                inner.width(),
+                ROW_HEIGHT,
+            );
+            let highlighted = state.highlighted == Some(position);
+            if highlighted {
+                paint.set_color(theme.accent);
+                canvas.draw_round_rect(row.with_inset((4.0, 1.0)), 4.0, 4.0, &paint);
+            }
+            paint.set_color(if highlighted { theme.accent_foreground } else { theme.foreground });
+            let baseline = row.center_y() + font.size() / 3.0;
+            canvas.draw_str(&state.labels[*index], (row.left + PADDING, baseline), &font, &paint);
+        }
+        canvas.restore();
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                if let Some(position) = self.position_at(*x, *y) {
+                    let mut state = self.state.borrow_mut();
+                    if state.highlighted != Some(position) {
+                        state.highlighted = Some(position);
+                        context::request_redraw();
+                    }
+                }
+                true
+            }
+            Event::MouseUp { x, y, button: MouseButton::Left, .. } => {
+                if let Some(position) = self.position_at(*x, *y) {
+                    self.state.borrow_mut().choose(position);
+                }
+                true
+            }
+            Event::Scroll { delta, .. } => {
+                let mut state = self.state.borrow_mut();
+                let (_, dy) = scroll_amount(*delta);
+                let max = (state.visible.len() as f32 * ROW_HEIGHT - (self.rect.height() - 2.0)).max(0.0);
+                state.offset = (state.offset + dy).clamp(0.0, max);
+                context::request_redraw();
+                true
+            }
+            Event::KeyDown { key: Some(key), modifiers } => self.on_key(*key, modifiers.alt()),
+            Event::CharInput(c) => self.on_char(*c),
+            _ => true,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+}
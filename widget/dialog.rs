@@ -0,0 +1,332 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::overlay::PopupId;
+use crate::widget::textedit::TextBuffer;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Rect};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const DIALOG_WIDTH: f32 = 360.0;
+const PADDING: f32 = 16.0;
+const LINE_HEIGHT: f32 = 22.0;
+const FIELD_HEIGHT: f32 = 28.0;
+const BUTTON_WIDTH: f32 = 88.0;
+const BUTTON_HEIGHT: f32 = 28.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogResult {
+    Ok,
+    Cancel,
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DialogKind {
+    Message,
+    Confirm,
+    Prompt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DialogButton {
+    Ok,
+    Cancel,
+}
+
+// A modal dialog shown in its window's overlay. While one is open it gets
+// every event and nothing reaches the widgets underneath.
+pub struct Dialog {
+    id: PopupId,
+    kind: DialogKind,
+    title: String,
+    message: String,
+    ok_label: String,
+    cancel_label: String,
+    input: TextBuffer,
+    on_result: Option<Box<dyn FnOnce(DialogResult)>>,
+    bounds: Rect,
+    panel: Rect,
+    hovered: Option<DialogButton>,
+    pressed: Option<DialogButton>,
+}
+
+impl Dialog {
+    fn new(kind: DialogKind, title: &str, message: &str) -> Self {
+        Self {
+            id: PopupId::next(),
+            kind,
+            title: title.to_string(),
+            message: message.to_string(),
+            ok_label: "OK".to_string(),
+            cancel_label: "Cancel".to_string(),
+            input: TextBuffer::default(),
+            on_result: None,
+            bounds: Rect::default(),
+            panel: Rect::default(),
+            hovered: None,
+            pressed: None,
+        }
+    }
+
+    pub fn message(title: &str, message: &str) -> Self {
+        Self::new(DialogKind::Message, title, message)
+    }
+
+    pub fn confirm(title: &str, message: &str) -> Self {
+        Self::new(DialogKind::Confirm, title, message)
+    }
+
+    pub fn prompt(title: &str, message: &str, initial: &str) -> Self {
+        let mut dialog = Self::new(DialogKind::Prompt, title, message);
+        dialog.input.set_text(initial);
+        dialog
+    }
+
+    pub fn with_labels(mut self, ok: &str, cancel: &str) -> Self {
+        self.ok_label = ok.to_string();
+        self.cancel_label = cancel.to_string();
+        self
+    }
+
+    pub fn on_result<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(DialogResult) + 'static,
+    {
+        self.on_result = Some(Box::new(f));
+        self
+    }
+
+    pub fn id(&self) -> PopupId {
+        self.id
+    }
+
+    pub fn accept(&mut self) {
+        let result = match self.kind {
+            DialogKind::Prompt => DialogResult::Text(self.input.text().to_string()),
+            _ => DialogResult::Ok,
+        };
+        self.finish(result);
+    }
+
+    pub fn cancel(&mut self) {
+        // A plain message box only has one answer.
+        let result = match self.kind {
+            DialogKind::Message => DialogResult::Ok,
+            _ => DialogResult::Cancel,
+        };
+        self.finish(result);
+    }
+
+    fn finish(&mut self, result: DialogResult) {
+        if let Some(on_result) = self.on_result.take() {
+            on_result(result);
+        }
+        context::dismiss_popup(self.id);
+    }
+
+    fn has_cancel(&self) -> bool {
+        self.kind != DialogKind::Message
+    }
+
+    fn field_rect(&self) -> Rect {
+        let top = self.panel.top + PADDING + LINE_HEIGHT * (1 + self.message.lines().count()) as f32 + 8.0;
+        Rect::from_xywh(
+            self.panel.left + PADDING,
+            top,
+            self.panel.width() - PADDING * 2.0,
+            FIELD_HEIGHT,
+        )
+    }
+
+    fn button_rect(&self, button: DialogButton) -> Rect {
+        let top = self.panel.bottom - PADDING - BUTTON_HEIGHT;
+        let right = self.panel.right - PADDING;
+        // OK sits on the right, Cancel to its left.
+        let slot = match button {
+            DialogButton::Ok => 0.0,
+            DialogButton::Cancel => 1.0,
+        };
+        Rect::from_xywh(
+            right - BUTTON_WIDTH - slot * (BUTTON_WIDTH + 8.0),
+            top,
+            BUTTON_WIDTH,
+            BUTTON_HEIGHT,
+        )
+    }
+
+    fn button_at(&self, x: f32, y: f32) -> Option<DialogButton> {
+        let mut buttons = vec![DialogButton::Ok];
+        if self.has_cancel() {
+            buttons.push(DialogButton::Cancel);
+        }
+        buttons
+            .into_iter()
+            .find(|b| self.button_rect(*b).contains(skia_safe::Point::new(x, y)))
+    }
+
+    fn draw_button(&self, canvas: &mut Canvas, button: DialogButton) {
+        let theme = theme::current();
+        let rect = self.button_rect(button);
+        let is_default = button == DialogButton::Ok;
+
+        let mut bg_paint = Paint::default();
+        bg_paint.set_anti_alias(true);
+        bg_paint.set_style(PaintStyle::Fill);
+        bg_paint.set_color(if self.pressed == Some(button) {
+            theme.surface_pressed
+        } else if is_default {
+            theme.accent
+        } else if self.hovered == Some(button) {
+            theme.surface_hover
+        } else {
+            theme.surface
+        });
+        canvas.draw_round_rect(rect, theme.corner_radius, theme.corner_radius, &bg_paint);
+
+        let mut border_paint = Paint::default();
+        border_paint.set_anti_alias(true);
+        border_paint.set_color(theme.border);
+        border_paint.set_style(PaintStyle::Stroke);
+        border_paint.set_stroke_width(1.0);
+        canvas.draw_round_rect(rect, theme.corner_radius, theme.corner_radius, &border_paint);
+
+        let label = match button {
+            DialogButton::Ok => &self.ok_label,
+            DialogButton::Cancel => &self.cancel_label,
+        };
+        let font = theme.font();
+        let mut text_paint = Paint::default();
+        text_paint.set_anti_alias(true);
+        text_paint.set_color(if is_default && self.pressed != Some(button) {
+            theme.accent_foreground
+        } else {
+            theme.foreground
+        });
+        let text_x = rect.left + (rect.width() - font.measure_str(label, Some(&text_paint)).0) / 2.0;
+        let text_y = rect.top + rect.height() / 2.0 + 5.0;
+        canvas.draw_str(label, (text_x, text_y), &font, &text_paint);
+    }
+}
+
+impl Widget for Dialog {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+
+        let mut backdrop = Paint::default();
+        backdrop.set_color(theme.backdrop);
+        canvas.draw_rect(self.bounds, &backdrop);
+
+        let mut panel_paint = Paint::default();
+        panel_paint.set_anti_alias(true);
+        panel_paint.set_color(theme.background);
+        canvas.draw_round_rect(self.panel, theme.corner_radius, theme.corner_radius, &panel_paint);
+
+        panel_paint.set_style(PaintStyle::Stroke);
+        panel_paint.set_color(theme.border);
+        canvas.draw_round_rect(self.panel, theme.corner_radius, theme.corner_radius, &panel_paint);
+
+        let mut text_paint = Paint::default();
+        text_paint.set_anti_alias(true);
+        text_paint.set_color(theme.foreground);
+
+        let mut title_font = theme.font();
+        title_font.set_embolden(true);
+        let mut y = self.panel.top + PADDING + LINE_HEIGHT - 6.0;
+        canvas.draw_str(&self.title, (self.panel.left + PADDING, y), &title_font, &text_paint);
+
+        let font = theme.font();
+        for line in self.message.lines() {
+            y += LINE_HEIGHT;
+            canvas.draw_str(line, (self.panel.left + PADDING, y), &font, &text_paint);
+        }
+
+        if self.kind == DialogKind::Prompt {
+            let field = self.field_rect();
+            let mut field_paint = Paint::default();
+            field_paint.set_anti_alias(true);
+            field_paint.set_style(PaintStyle::Stroke);
+            field_paint.set_color(theme.accent);
+            canvas.draw_round_rect(field, 4.0, 4.0, &field_paint);
+            self.input.draw(
+                canvas,
+                field.with_inset((6.0, 0.0)),
+                &font,
+                theme.foreground,
+                true,
+            );
+        }
+
+        self.draw_button(canvas, DialogButton::Ok);
+        if self.has_cancel() {
+            self.draw_button(canvas, DialogButton::Cancel);
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::KeyDown { key: Some(VirtualKeyCode::Return), .. }
+            | Event::KeyDown { key: Some(VirtualKeyCode::NumpadEnter), .. } => {
+                self.accept();
+            }
+            Event::KeyDown { key: Some(VirtualKeyCode::Escape), .. } => {
+                self.cancel();
+            }
+            Event::MouseMove { x, y, .. } => {
+                self.hovered = self.button_at(*x, *y);
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => {
+                self.pressed = self.button_at(*x, *y);
+            }
+            Event::MouseUp { x, y, button: MouseButton::Left, .. } => {
+                let released = self.button_at(*x, *y);
+                if released.is_some() && released == self.pressed.take() {
+                    match released {
+                        Some(DialogButton::Ok) => self.accept(),
+                        Some(DialogButton::Cancel) => self.cancel(),
+                        None => {}
+                    }
+                }
+                self.pressed = None;
+            }
+            _ => {
+                if self.kind == DialogKind::Prompt {
+                    self.input.handle_event(event);
+                }
+            }
+        }
+        // Modal: nothing gets past a dialog.
+        true
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.bounds = rect;
+
+        let mut height = PADDING * 2.0 + LINE_HEIGHT * (1 + self.message.lines().count()) as f32;
+        if self.kind == DialogKind::Prompt {
+            height += FIELD_HEIGHT + 16.0;
+        }
+        height += BUTTON_HEIGHT + 16.0;
+
+        let width = DIALOG_WIDTH.min(rect.width());
+        self.panel = Rect::from_xywh(
+            rect.left + (rect.width() - width) / 2.0,
+            rect.top + (rect.height() - height) / 2.0,
+            width,
+            height,
+        );
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("kind", format!("{:?}", self.kind)),
+            ("title", self.title.clone()),
+        ]
+    }
+}
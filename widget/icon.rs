@@ -0,0 +1,320 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::{theme, Widget};
+use skia_safe::canvas::SaveLayerRec;
+use skia_safe::svg::Dom;
+use skia_safe::{color_filters, BlendMode, Canvas, Color, Paint, Rect, Size};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+pub const ICON_SIZE: f32 = 16.0;
+// Space between an icon and the label beside it.
+const ICON_GAP: f32 = 6.0;
+
+// The bundled icons, drawn on a 24x24 grid in black so they can be tinted.
+const ICONS: &[(&str, &str)] = &[
+    ("add", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round"><path d="M12 5v14M5 12h14"/></svg>"#),
+    ("remove", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round"><path d="M5 12h14"/></svg>"#),
+    ("close", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round"><path d="M6 6l12 12M18 6L6 18"/></svg>"#),
+    ("check", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M5 12l5 5L20 7"/></svg>"#),
+    ("chevron-down", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M6 9l6 6 6-6"/></svg>"#),
+    ("chevron-up", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M6 15l6-6 6 6"/></svg>"#),
+    ("chevron-left", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M15 6l-6 6 6 6"/></svg>"#),
+    ("chevron-right", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M9 6l6 6-6 6"/></svg>"#),
+    ("search", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round"><circle cx="11" cy="11" r="7"/><path d="M16 16l5 5"/></svg>"#),
+    ("menu", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round"><path d="M4 6h16M4 12h16M4 18h16"/></svg>"#),
+    ("refresh", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M20 12a8 8 0 1 1-2.3-5.7M20 4v5h-5"/></svg>"#),
+    ("settings", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round"><circle cx="12" cy="12" r="3"/><path d="M12 2v3M12 19v3M2 12h3M19 12h3M4.9 4.9l2.1 2.1M17 17l2.1 2.1M4.9 19.1L7 17M17 7l2.1-2.1"/></svg>"#),
+    ("info", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round"><circle cx="12" cy="12" r="9"/><path d="M12 11v6M12 7.5v.5"/></svg>"#),
+    ("warning", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M12 3L2 20h20z"/><path d="M12 10v4M12 17v.5"/></svg>"#),
+    ("folder", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linejoin="round"><path d="M3 6h6l2 2h10v11H3z"/></svg>"#),
+    ("file", r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linejoin="round"><path d="M6 3h8l5 5v13H6z"/><path d="M14 3v5h5"/></svg>"#),
+];
+
+// Names of the bundled icons, for `Icon::named`.
+pub fn names() -> impl Iterator<Item = &'static str> {
+    ICONS.iter().map(|(name, _)| *name)
+}
+
+#[derive(Debug, Clone)]
+pub enum SvgSource {
+    // One of the bundled icons.
+    Named(String),
+    File(PathBuf),
+    // SVG markup, cached under `key`.
+    Markup { key: String, svg: Rc<str> },
+}
+
+impl SvgSource {
+    fn key(&self) -> String {
+        match self {
+            SvgSource::Named(name) => format!("icon:{}", name),
+            SvgSource::File(path) => format!("file:{}", path.display()),
+            SvgSource::Markup { key, .. } => format!("markup:{}", key),
+        }
+    }
+
+    fn read(&self) -> Option<Vec<u8>> {
+        match self {
+            SvgSource::Named(name) => ICONS
+                .iter()
+                .find(|(icon, _)| icon == name)
+                .map(|(_, svg)| svg.as_bytes().to_vec()),
+            SvgSource::File(path) => std::fs::read(path).ok(),
+            SvgSource::Markup { svg, .. } => Some(svg.as_bytes().to_vec()),
+        }
+    }
+}
+
+thread_local! {
+    // Parsed documents keyed by source, like the image cache. Failures are
+    // kept so a missing icon isn't re-read every frame.
+    static CACHE: RefCell<HashMap<String, Option<Rc<RefCell<Dom>>>>> = RefCell::new(HashMap::new());
+}
+
+fn load(source: &SvgSource) -> Option<Rc<RefCell<Dom>>> {
+    let key = source.key();
+    if let Some(cached) = CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        return cached;
+    }
+    let dom = source
+        .read()
+        .and_then(|bytes| Dom::from_bytes(&bytes).ok())
+        .map(|dom| Rc::new(RefCell::new(dom)));
+    CACHE.with(|cache| cache.borrow_mut().insert(key, dom.clone()));
+    dom
+}
+
+// Renders an SVG into `rect`. The document is laid out at the rect's size
+// rather than scaled as a bitmap, so it stays sharp at any size and scale
+// factor. With a tint, every painted pixel takes that color and keeps its
+// coverage.
+fn render(canvas: &mut Canvas, dom: &RefCell<Dom>, rect: Rect, tint: Option<Color>) {
+    let mut dom = dom.borrow_mut();
+    dom.set_container_size(Size::new(rect.width(), rect.height()));
+    canvas.save();
+    canvas.clip_rect(rect, None, Some(true));
+    if let Some(color) = tint {
+        let mut paint = Paint::default();
+        paint.set_color_filter(color_filters::blend(color, BlendMode::SrcIn));
+        canvas.save_layer(&SaveLayerRec::default().bounds(&rect).paint(&paint));
+    }
+    canvas.translate((rect.left, rect.top));
+    dom.render(canvas);
+    if tint.is_some() {
+        canvas.restore();
+    }
+    canvas.restore();
+}
+
+// Draws a bundled icon centred in `rect`, for widgets that put an icon next
+// to their own text. Unknown names draw nothing.
+pub fn draw_icon(canvas: &mut Canvas, name: &str, rect: Rect, color: Color) {
+    if let Some(dom) = load(&SvgSource::Named(name.to_string())) {
+        let size = rect.width().min(rect.height());
+        let square = Rect::from_xywh(rect.center_x() - size / 2.0, rect.center_y() - size / 2.0, size, size);
+        render(canvas, &dom, square, Some(color));
+    }
+}
+
+// Width of an optional icon and a label of `label_width` side by side.
+pub fn icon_label_width(has_icon: bool, label_width: f32) -> f32 {
+    match (has_icon, label_width > 0.0) {
+        (false, _) => label_width,
+        (true, false) => ICON_SIZE,
+        (true, true) => ICON_SIZE + ICON_GAP + label_width,
+    }
+}
+
+// Centres an optional icon and a label together in `rect`, icon first.
+// Returns the icon's rect and the x the label starts at.
+pub fn icon_label_layout(rect: Rect, has_icon: bool, label_width: f32) -> (Option<Rect>, f32) {
+    let left = rect.center_x() - icon_label_width(has_icon, label_width) / 2.0;
+    if !has_icon {
+        return (None, left);
+    }
+    let icon = Rect::from_xywh(left, rect.center_y() - ICON_SIZE / 2.0, ICON_SIZE, ICON_SIZE);
+    (Some(icon), left + ICON_SIZE + ICON_GAP)
+}
+
+// An SVG document drawn at the widget's size. Untinted by default, so
+// illustrations keep their own colors.
+pub struct Svg {
+    source: SvgSource,
+    dom: Option<Rc<RefCell<Dom>>>,
+    tint: Option<Color>,
+    size: (f32, f32),
+    rect: Rect,
+}
+
+impl Svg {
+    pub fn new(source: SvgSource) -> Self {
+        let dom = load(&source);
+        Self {
+            source,
+            dom,
+            tint: None,
+            size: (24.0, 24.0),
+            rect: Rect::default(),
+        }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Self {
+        Self::new(SvgSource::File(path.as_ref().to_path_buf()))
+    }
+
+    pub fn from_str(key: &str, svg: &str) -> Self {
+        Self::new(SvgSource::Markup {
+            key: key.to_string(),
+            svg: svg.into(),
+        })
+    }
+
+    pub fn with_tint(mut self, color: Color) -> Self {
+        self.tint = Some(color);
+        self
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    pub fn source(&self) -> &SvgSource {
+        &self.source
+    }
+
+    pub fn set_source(&mut self, source: SvgSource) {
+        self.dom = load(&source);
+        self.source = source;
+        context::request_redraw();
+    }
+
+    pub fn set_tint(&mut self, tint: Option<Color>) {
+        self.tint = tint;
+        context::request_redraw();
+    }
+}
+
+impl Widget for Svg {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        if let Some(dom) = &self.dom {
+            render(canvas, dom, self.rect, self.tint);
+        }
+    }
+
+    fn on_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("tint", self.tint.map_or_else(|| "none".to_string(), theme::color_hex)),
+            ("size", format!("{:?}", self.size)),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        self.size
+    }
+}
+
+// A bundled icon, square and tinted. Without an explicit color it follows
+// the current theme's foreground.
+pub struct Icon {
+    name: String,
+    color: Option<Color>,
+    size: f32,
+    rect: Rect,
+}
+
+impl Icon {
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            color: None,
+            size: ICON_SIZE,
+            rect: Rect::default(),
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+        context::request_redraw();
+    }
+
+    pub fn set_color(&mut self, color: Option<Color>) {
+        self.color = color;
+        context::request_redraw();
+    }
+}
+
+impl Widget for Icon {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let color = self.color.unwrap_or_else(|| theme::current().foreground);
+        let size = self.size.min(self.rect.width()).min(self.rect.height());
+        let rect = Rect::from_xywh(
+            self.rect.center_x() - size / 2.0,
+            self.rect.center_y() - size / 2.0,
+            size,
+            size,
+        );
+        draw_icon(canvas, &self.name, rect, color);
+    }
+
+    fn on_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("name", self.name.clone()),
+        ]
+    }
+
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("color", self.color.map_or_else(|| "theme".to_string(), theme::color_hex)),
+            ("size", self.size.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (self.size, self.size)
+    }
+}
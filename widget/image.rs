@@ -0,0 +1,387 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::{theme, Widget};
+use skia_safe::codec::{self, Codec, ZeroInitialized};
+use skia_safe::{
+    AlphaType, Canvas, ColorType, CubicResampler, Data, FilterMode, Image as SkImage, MipmapMode, Paint, RRect, Rect,
+    SamplingOptions,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+// GIFs that ask for shorter frames than this get it instead, like browsers do.
+const MIN_FRAME_DURATION: Duration = Duration::from_millis(20);
+
+// How an image is sized into its widget's rect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFit {
+    // Scaled to fit inside, keeping its aspect ratio.
+    Contain,
+    // Scaled to cover the rect, keeping its aspect ratio; the rest is cropped.
+    Cover,
+    // Stretched to the rect.
+    Fill,
+    // Drawn at its natural size, centred.
+    None,
+    // Like `None`, but shrinks like `Contain` when it doesn't fit.
+    ScaleDown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sampling {
+    Nearest,
+    Linear,
+    // Linear with mipmaps; best for large downscales such as thumbnails.
+    Mipmap,
+    Cubic,
+}
+
+impl Sampling {
+    fn options(self) -> SamplingOptions {
+        match self {
+            Sampling::Nearest => SamplingOptions::new(FilterMode::Nearest, MipmapMode::None),
+            Sampling::Linear => SamplingOptions::new(FilterMode::Linear, MipmapMode::None),
+            Sampling::Mipmap => SamplingOptions::new(FilterMode::Linear, MipmapMode::Linear),
+            Sampling::Cubic => SamplingOptions::from(CubicResampler::mitchell()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ImageSource {
+    File(PathBuf),
+    // Encoded bytes, e.g. from `include_bytes!`, cached under `key`.
+    Bytes { key: String, data: Rc<[u8]> },
+}
+
+impl ImageSource {
+    fn key(&self) -> String {
+        match self {
+            ImageSource::File(path) => format!("file:{}", path.display()),
+            ImageSource::Bytes { key, .. } => format!("bytes:{}", key),
+        }
+    }
+
+    fn read(&self) -> Option<Vec<u8>> {
+        match self {
+            ImageSource::File(path) => std::fs::read(path).ok(),
+            ImageSource::Bytes { data, .. } => Some(data.to_vec()),
+        }
+    }
+}
+
+// A decoded image: one frame for stills, several with their durations for
+// animated GIF/WebP.
+pub struct Decoded {
+    frames: Vec<(SkImage, Duration)>,
+    total: Duration,
+}
+
+impl Decoded {
+    fn new(frames: Vec<(SkImage, Duration)>) -> Self {
+        let total = frames.iter().map(|(_, duration)| *duration).sum();
+        Self { frames, total }
+    }
+
+    pub fn size(&self) -> (f32, f32) {
+        let image = &self.frames[0].0;
+        (image.width() as f32, image.height() as f32)
+    }
+
+    pub fn is_animated(&self) -> bool {
+        self.frames.len() > 1
+    }
+
+    pub fn frame_at(&self, elapsed: Duration) -> &SkImage {
+        if !self.is_animated() || self.total.is_zero() {
+            return &self.frames[0].0;
+        }
+        let mut t = Duration::from_nanos((elapsed.as_nanos() % self.total.as_nanos()) as u64);
+        for (image, duration) in &self.frames {
+            if t < *duration {
+                return image;
+            }
+            t -= *duration;
+        }
+        &self.frames[self.frames.len() - 1].0
+    }
+}
+
+fn decode(bytes: &[u8]) -> Option<Decoded> {
+    let data = Data::new_copy(bytes);
+    let mut codec = Codec::from_data(data.clone())?;
+    if codec.get_frame_count() <= 1 {
+        let image = SkImage::from_encoded(data)?;
+        return Some(Decoded::new(vec![(image, Duration::ZERO)]));
+    }
+
+    let info = codec
+        .info()
+        .with_color_type(ColorType::RGBA8888)
+        .with_alpha_type(AlphaType::Premul);
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; info.compute_min_byte_size()];
+    let mut frames = Vec::new();
+    for index in 0..codec.get_frame_count() {
+        let frame = codec.get_frame_info(index)?;
+        // Frames are decoded in order into the same buffer, so when a frame
+        // builds on the one just before it, that one is already in place.
+        // Otherwise the codec decodes whatever it depends on itself.
+        let prior_frame = (index > 0 && frame.required_frame == index as i32 - 1).then(|| index - 1);
+        let options = codec::Options {
+            zero_initialized: ZeroInitialized::No,
+            subset: None,
+            frame_index: index,
+            prior_frame,
+        };
+        codec.get_pixels_with_options(&info, &mut pixels, row_bytes, Some(&options));
+        let image = SkImage::from_raster_data(&info, Data::new_copy(&pixels), row_bytes)?;
+        let duration = Duration::from_millis(frame.duration.max(0) as u64).max(MIN_FRAME_DURATION);
+        frames.push((image, duration));
+    }
+    (!frames.is_empty()).then(|| Decoded::new(frames))
+}
+
+thread_local! {
+    // Keyed by source, so the same asset shown in many places is decoded
+    // once. Failed decodes are remembered too so they aren't retried every
+    // frame.
+    static CACHE: RefCell<HashMap<String, Option<Rc<Decoded>>>> = RefCell::new(HashMap::new());
+}
+
+pub fn load(source: &ImageSource) -> Option<Rc<Decoded>> {
+    let key = source.key();
+    if let Some(cached) = CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        return cached;
+    }
+    let decoded = source.read().and_then(|bytes| decode(&bytes)).map(Rc::new);
+    CACHE.with(|cache| cache.borrow_mut().insert(key, decoded.clone()));
+    decoded
+}
+
+// Drops a source from the cache, e.g. after the file on disk changed.
+pub fn evict(source: &ImageSource) {
+    CACHE.with(|cache| cache.borrow_mut().remove(&source.key()));
+}
+
+pub fn clear_cache() {
+    CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+// Where `fit` puts an image of `size` inside `rect`.
+pub(crate) fn fitted_rect(size: (f32, f32), rect: Rect, fit: ImageFit) -> Rect {
+    let (width, height) = size;
+    if width <= 0.0 || height <= 0.0 {
+        return rect;
+    }
+    let contain = (rect.width() / width).min(rect.height() / height);
+    let scale = match fit {
+        ImageFit::Fill => return rect,
+        ImageFit::Contain => contain,
+        ImageFit::Cover => (rect.width() / width).max(rect.height() / height),
+        ImageFit::None => 1.0,
+        ImageFit::ScaleDown => contain.min(1.0),
+    };
+    let (w, h) = (width * scale, height * scale);
+    Rect::from_xywh(rect.center_x() - w / 2.0, rect.center_y() - h / 2.0, w, h)
+}
+
+pub struct Image {
+    source: ImageSource,
+    decoded: Option<Rc<Decoded>>,
+    fit: ImageFit,
+    sampling: Sampling,
+    corner_radius: f32,
+    playing: bool,
+    start: Instant,
+    rect: Rect,
+}
+
+impl Image {
+    pub fn new(source: ImageSource) -> Self {
+        let decoded = load(&source);
+        Self {
+            source,
+            decoded,
+            fit: ImageFit::Contain,
+            sampling: Sampling::Linear,
+            corner_radius: 0.0,
+            playing: true,
+            start: Instant::now(),
+            rect: Rect::default(),
+        }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Self {
+        Self::new(ImageSource::File(path.as_ref().to_path_buf()))
+    }
+
+    pub fn from_bytes(key: &str, data: &[u8]) -> Self {
+        Self::new(ImageSource::Bytes {
+            key: key.to_string(),
+            data: data.into(),
+        })
+    }
+
+    pub fn with_fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    pub fn with_sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn with_corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = radius.max(0.0);
+        self
+    }
+
+    pub fn source(&self) -> &ImageSource {
+        &self.source
+    }
+
+    pub fn set_source(&mut self, source: ImageSource) {
+        self.decoded = load(&source);
+        self.source = source;
+        self.start = Instant::now();
+        context::request_redraw();
+    }
+
+    // Natural size in pixels, if the image decoded.
+    pub fn image_size(&self) -> Option<(f32, f32)> {
+        self.decoded.as_ref().map(|decoded| decoded.size())
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn set_playing(&mut self, playing: bool) {
+        self.playing = playing;
+        self.start = Instant::now();
+        context::request_redraw();
+    }
+}
+
+impl Widget for Image {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        canvas.save();
+        if self.corner_radius > 0.0 {
+            let clip = RRect::new_rect_xy(self.rect, self.corner_radius, self.corner_radius);
+            canvas.clip_rrect(clip, None, Some(true));
+        } else {
+            canvas.clip_rect(self.rect, None, Some(true));
+        }
+
+        match &self.decoded {
+            Some(decoded) => {
+                let elapsed = if self.playing { self.start.elapsed() } else { Duration::ZERO };
+                let frame = decoded.frame_at(elapsed);
+                let dst = fitted_rect(decoded.size(), self.rect, self.fit);
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true);
+                canvas.draw_image_rect_with_sampling_options(frame, None, dst, self.sampling.options(), &paint);
+                // Animated images only ask for frames while they are drawn.
+                if decoded.is_animated() && self.playing {
+                    context::request_animation_frame();
+                }
+            }
+            None => {
+                // Missing or undecodable: a plain placeholder.
+                let mut paint = Paint::default();
+                paint.set_color(theme::current().surface);
+                canvas.draw_rect(self.rect, &paint);
+            }
+        }
+        canvas.restore();
+    }
+
+    fn on_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("size", format!("{:?}", self.image_size())),
+            ("playing", self.playing.to_string()),
+        ]
+    }
+
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("fit", format!("{:?}", self.fit)),
+            ("sampling", format!("{:?}", self.sampling)),
+            ("corner_radius", self.corner_radius.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        self.image_size().unwrap_or((0.0, 0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 200x100 box at (10, 20).
+    fn frame() -> Rect {
+        Rect::from_xywh(10.0, 20.0, 200.0, 100.0)
+    }
+
+    #[test]
+    fn contain_fits_the_limiting_side_and_centres() {
+        let rect = fitted_rect((50.0, 50.0), frame(), ImageFit::Contain);
+        assert_eq!(rect, Rect::from_xywh(60.0, 20.0, 100.0, 100.0));
+        let wide = fitted_rect((400.0, 100.0), frame(), ImageFit::Contain);
+        assert_eq!(wide, Rect::from_xywh(10.0, 45.0, 200.0, 50.0));
+    }
+
+    #[test]
+    fn cover_fills_the_rect_and_overflows_the_other_side() {
+        let rect = fitted_rect((50.0, 50.0), frame(), ImageFit::Cover);
+        assert_eq!(rect, Rect::from_xywh(10.0, -30.0, 200.0, 200.0));
+    }
+
+    #[test]
+    fn fill_stretches_to_the_rect() {
+        assert_eq!(fitted_rect((50.0, 80.0), frame(), ImageFit::Fill), frame());
+    }
+
+    #[test]
+    fn none_keeps_the_natural_size() {
+        let rect = fitted_rect((40.0, 300.0), frame(), ImageFit::None);
+        assert_eq!(rect, Rect::from_xywh(90.0, -80.0, 40.0, 300.0));
+    }
+
+    #[test]
+    fn scale_down_only_ever_shrinks() {
+        let small = fitted_rect((40.0, 20.0), frame(), ImageFit::ScaleDown);
+        assert_eq!(small, Rect::from_xywh(90.0, 60.0, 40.0, 20.0));
+        let large = fitted_rect((400.0, 400.0), frame(), ImageFit::ScaleDown);
+        assert_eq!(large, Rect::from_xywh(60.0, 20.0, 100.0, 100.0));
+    }
+
+    #[test]
+    fn empty_images_take_the_whole_rect() {
+        for fit in [ImageFit::Contain, ImageFit::Cover, ImageFit::None] {
+            assert_eq!(fitted_rect((0.0, 10.0), frame(), fit), frame());
+        }
+    }
+}
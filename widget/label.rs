@@ -0,0 +1,77 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Color, Paint, Rect};
+
+const HEIGHT: f32 = 24.0;
+
+pub struct Label {
+    text: String,
+    // Theme foreground when unset.
+    color: Option<Color>,
+    rect: Rect,
+}
+
+impl Label {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            color: None,
+            rect: Rect::default(),
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        context::request_redraw();
+    }
+}
+
+impl Widget for Label {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let font = theme.font();
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(self.color.unwrap_or(theme.foreground));
+        let baseline = self.rect.center_y() + font.size() / 3.0;
+        canvas.draw_str(&self.text, (self.rect.left, baseline), &font, &paint);
+    }
+
+    fn on_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![("text", self.text.clone())]
+    }
+
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        self.color.map(|color| vec![("color", theme::color_hex(color))]).unwrap_or_default()
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let font = theme::current().font();
+        (font.measure_str(&self.text, None).0, HEIGHT)
+    }
+}
@@ -0,0 +1,558 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::scroll::{scroll_amount, Axis, Scrollbar, SCROLLBAR_SIZE};
+use crate::widget::selection::{Selection, SelectionMode};
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Point, Rect};
+use std::time::{Duration, Instant};
+use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
+
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+type SelectionChanged = Box<dyn FnMut(&Selection)>;
+
+// Row heights with prefix sums kept in a Fenwick tree, so finding the row at
+// a scroll offset stays cheap with hundreds of thousands of rows. Rows that
+// haven't been measured yet count as the estimate.
+#[derive(Debug, Clone)]
+pub struct HeightIndex {
+    tree: Vec<f32>,
+    heights: Vec<f32>,
+    measured: Vec<bool>,
+    estimate: f32,
+}
+
+impl HeightIndex {
+    pub fn new(len: usize, estimate: f32) -> Self {
+        let mut index = Self {
+            tree: Vec::new(),
+            heights: vec![estimate; len],
+            measured: vec![false; len],
+            estimate,
+        };
+        index.rebuild();
+        index
+    }
+
+    fn rebuild(&mut self) {
+        let n = self.heights.len();
+        self.tree = vec![0.0; n + 1];
+        for i in 1..=n {
+            self.tree[i] += self.heights[i - 1];
+            let parent = i + (i & i.wrapping_neg());
+            if parent <= n {
+                self.tree[parent] += self.tree[i];
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heights.is_empty()
+    }
+
+    pub fn estimate(&self) -> f32 {
+        self.estimate
+    }
+
+    pub fn height(&self, index: usize) -> f32 {
+        self.heights[index]
+    }
+
+    pub fn is_measured(&self, index: usize) -> bool {
+        self.measured[index]
+    }
+
+    pub fn set(&mut self, index: usize, height: f32) {
+        self.measured[index] = true;
+        let delta = height - self.heights[index];
+        if delta == 0.0 {
+            return;
+        }
+        self.heights[index] = height;
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // Top of row `index`, i.e. the total height of the rows before it.
+    pub fn offset_of(&self, index: usize) -> f32 {
+        let mut sum = 0.0;
+        let mut i = index.min(self.heights.len());
+        while i > 0 {
+            sum += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+
+    pub fn total(&self) -> f32 {
+        self.offset_of(self.heights.len())
+    }
+
+    // Row containing vertical offset `y`, clamped to the last row.
+    pub fn index_at(&self, y: f32) -> usize {
+        let n = self.heights.len();
+        if n == 0 {
+            return 0;
+        }
+        let mut pos = 0;
+        let mut remaining = y;
+        let mut step = n.next_power_of_two();
+        while step > 0 {
+            let next = pos + step;
+            if next <= n && self.tree[next] <= remaining {
+                pos = next;
+                remaining -= self.tree[next];
+            }
+            step /= 2;
+        }
+        pos.min(n - 1)
+    }
+}
+
+// Supplies rows to a `ListView`. Row widgets are created once and rebound
+// to whichever index scrolls into view, so `create_row` is only called for
+// as many rows as fit on screen.
+pub trait ListDelegate {
+    type Row: Widget;
+
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn create_row(&mut self) -> Self::Row;
+    fn bind_row(&mut self, row: &mut Self::Row, index: usize, selected: bool);
+
+    // Height used for rows that haven't been measured yet.
+    fn estimated_row_height(&self) -> f32 {
+        24.0
+    }
+
+    // Exact height of a row, if known without binding it. When this returns
+    // None the bound row's preferred height is used instead.
+    fn row_height(&self, _index: usize) -> Option<f32> {
+        None
+    }
+}
+
+pub struct ListView<D: ListDelegate> {
+    delegate: D,
+    visible: Vec<(usize, D::Row)>,
+    pool: Vec<D::Row>,
+    heights: HeightIndex,
+    selection: Selection,
+    offset: f32,
+    rect: Rect,
+    viewport: Rect,
+    scrollbar: Scrollbar,
+    focused: bool,
+    last_click: Option<(usize, Instant)>,
+    on_selection_changed: Option<SelectionChanged>,
+    on_activate: Option<Box<dyn FnMut(usize)>>,
+}
+
+impl<D: ListDelegate> ListView<D> {
+    pub fn new(delegate: D) -> Self {
+        let heights = HeightIndex::new(delegate.len(), delegate.estimated_row_height());
+        Self {
+            delegate,
+            visible: Vec::new(),
+            pool: Vec::new(),
+            heights,
+            selection: Selection::new(SelectionMode::Single),
+            offset: 0.0,
+            rect: Rect::default(),
+            viewport: Rect::default(),
+            scrollbar: Scrollbar::new(Axis::Vertical),
+            focused: false,
+            last_click: None,
+            on_selection_changed: None,
+            on_activate: None,
+        }
+    }
+
+    pub fn with_selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.selection.set_mode(mode);
+        self
+    }
+
+    pub fn on_selection_changed<F: FnMut(&Selection) + 'static>(mut self, f: F) -> Self {
+        self.on_selection_changed = Some(Box::new(f));
+        self
+    }
+
+    // Enter on the focused row, or a double-click.
+    pub fn on_activate<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_activate = Some(Box::new(f));
+        self
+    }
+
+    pub fn delegate(&self) -> &D {
+        &self.delegate
+    }
+
+    pub fn delegate_mut(&mut self) -> &mut D {
+        &mut self.delegate
+    }
+
+    pub fn selection(&self) -> &Selection {
+        &self.selection
+    }
+
+    pub fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heights.is_empty()
+    }
+
+    // Call after the delegate's data changes. Measured heights are thrown
+    // away since indices may now point at different items.
+    pub fn reload(&mut self) {
+        let len = self.delegate.len();
+        self.heights = HeightIndex::new(len, self.delegate.estimated_row_height());
+        self.selection.truncate(len);
+        self.recycle_all();
+        self.update_layout();
+        context::request_redraw();
+    }
+
+    // Like `reload`, for when the delegate reordered its rows: `f` maps each
+    // old row index to its new one, so the selection follows its items.
+    pub fn reload_remapped<F: Fn(usize) -> Option<usize>>(&mut self, f: F) {
+        let before: Vec<usize> = self.selection.selected().collect();
+        let focus = self.selection.focus();
+        self.selection.remap(f);
+        self.reload();
+        if !self.selection.selected().eq(before) || self.selection.focus() != focus {
+            self.selection_changed();
+        }
+    }
+
+    pub fn scroll_to_index(&mut self, index: usize) {
+        if index >= self.len() {
+            return;
+        }
+        let top = self.heights.offset_of(index);
+        let bottom = top + self.heights.height(index);
+        if top < self.offset {
+            self.offset = top;
+        } else if bottom > self.offset + self.viewport.height() {
+            self.offset = bottom - self.viewport.height();
+        }
+        self.update_layout();
+    }
+
+    pub fn set_offset(&mut self, offset: f32) {
+        self.offset = offset;
+        self.update_layout();
+    }
+
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    // Window-space rect of a row, whether or not it is on screen.
+    pub fn row_rect(&self, index: usize) -> Rect {
+        Rect::from_xywh(
+            self.viewport.left,
+            self.viewport.top + self.heights.offset_of(index) - self.offset,
+            self.viewport.width(),
+            self.heights.height(index),
+        )
+    }
+
+    pub fn row_at(&self, x: f32, y: f32) -> Option<usize> {
+        if self.is_empty() || !self.viewport.contains(Point::new(x, y)) {
+            return None;
+        }
+        let index = self.heights.index_at(y - self.viewport.top + self.offset);
+        self.row_rect(index).contains(Point::new(x, y)).then_some(index)
+    }
+
+    pub fn visible_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.visible.iter().map(|(index, _)| *index)
+    }
+
+    fn recycle_all(&mut self) {
+        self.pool.extend(self.visible.drain(..).map(|(_, row)| row));
+    }
+
+    fn clamp_offset(&mut self) {
+        let max = (self.heights.total() - self.viewport.height()).max(0.0);
+        self.offset = self.offset.clamp(0.0, max);
+    }
+
+    // Binds rows for everything in the viewport and lays them out. Rows that
+    // scrolled away go back to the pool.
+    fn update_layout(&mut self) {
+        self.clamp_offset();
+        let mut previous: Vec<(usize, D::Row)> = self.visible.drain(..).collect();
+
+        if !self.is_empty() {
+            let mut index = self.heights.index_at(self.offset);
+            while index < self.len() && self.heights.offset_of(index) < self.offset + self.viewport.height() {
+                let mut row = match previous.iter().position(|(i, _)| *i == index) {
+                    Some(pos) => previous.swap_remove(pos).1,
+                    None => match self.pool.pop().or_else(|| previous.pop().map(|(_, row)| row)) {
+                        Some(row) => row,
+                        None => self.delegate.create_row(),
+                    },
+                };
+                self.delegate.bind_row(&mut row, index, self.selection.is_selected(index));
+
+                if !self.heights.is_measured(index) {
+                    let height = self
+                        .delegate
+                        .row_height(index)
+                        .or_else(|| Some(row.preferred_size().1).filter(|h| *h > 0.0))
+                        .unwrap_or(self.heights.estimate());
+                    self.heights.set(index, height);
+                }
+
+                row.layout(self.row_rect(index));
+                self.visible.push((index, row));
+                index += 1;
+            }
+        }
+
+        self.pool.extend(previous.into_iter().map(|(_, row)| row));
+        self.scrollbar.update(
+            Rect::from_ltrb(self.viewport.right, self.rect.top, self.rect.right, self.rect.bottom),
+            self.viewport.height(),
+            self.heights.total(),
+        );
+    }
+
+    fn rebind_visible(&mut self) {
+        for (index, row) in &mut self.visible {
+            self.delegate.bind_row(row, *index, self.selection.is_selected(*index));
+        }
+    }
+
+    fn selection_changed(&mut self) {
+        self.rebind_visible();
+        if let Some(on_selection_changed) = &mut self.on_selection_changed {
+            on_selection_changed(&self.selection);
+        }
+        context::request_redraw();
+    }
+
+    // A zero or tiny estimate would otherwise make a page near-infinite.
+    fn rows_per_page(&self) -> usize {
+        (self.viewport.height() / self.heights.estimate().max(1.0)).max(1.0) as usize
+    }
+
+    fn on_key(&mut self, key: VirtualKeyCode, modifiers: ModifiersState) -> bool {
+        let len = self.len();
+        if len == 0 {
+            return false;
+        }
+        let current = self.selection.focus();
+        let last = len - 1;
+        let target = match key {
+            VirtualKeyCode::Up => current.map(|i| i.saturating_sub(1)).unwrap_or(0),
+            VirtualKeyCode::Down => current.map(|i| (i + 1).min(last)).unwrap_or(0),
+            VirtualKeyCode::PageUp => current.unwrap_or(0).saturating_sub(self.rows_per_page()),
+            VirtualKeyCode::PageDown => current.unwrap_or(0).saturating_add(self.rows_per_page()).min(last),
+            VirtualKeyCode::Home => 0,
+            VirtualKeyCode::End => last,
+            VirtualKeyCode::A if modifiers.ctrl() => {
+                self.selection.select_all(len);
+                self.selection_changed();
+                return true;
+            }
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                if let (Some(index), Some(on_activate)) = (current, &mut self.on_activate) {
+                    on_activate(index);
+                }
+                return current.is_some();
+            }
+            _ => return false,
+        };
+
+        if self.selection.move_focus(target, modifiers.shift()) {
+            self.selection_changed();
+        }
+        self.scroll_to_index(target);
+        true
+    }
+}
+
+impl<D: ListDelegate> Widget for ListView<D> {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+
+        canvas.save();
+        canvas.clip_rect(self.viewport, None, Some(true));
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        for (index, row) in &mut self.visible {
+            let rect = Rect::from_xywh(
+                self.viewport.left,
+                self.viewport.top + self.heights.offset_of(*index) - self.offset,
+                self.viewport.width(),
+                self.heights.height(*index),
+            );
+            if self.selection.is_selected(*index) {
+                paint.set_style(PaintStyle::Fill);
+                paint.set_color(if self.focused { theme.accent } else { theme.surface_pressed });
+                canvas.draw_rect(rect, &paint);
+            }
+            row.draw(canvas);
+            if self.focused && self.selection.focus() == Some(*index) {
+                paint.set_style(PaintStyle::Stroke);
+                paint.set_color(theme.border);
+                canvas.draw_rect(rect.with_inset((0.5, 0.5)), &paint);
+            }
+        }
+
+        canvas.restore();
+        self.scrollbar.draw(canvas, self.offset);
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        let mut offset = self.offset;
+        if self.scrollbar.on_event(event, &mut offset) {
+            if offset != self.offset {
+                self.set_offset(offset);
+            }
+            return true;
+        }
+
+        match event {
+            Event::Scroll { x, y, delta, .. } => {
+                if !self.rect.contains(Point::new(*x, *y)) {
+                    return false;
+                }
+                let (_, dy) = scroll_amount(*delta);
+                self.set_offset(self.offset + dy);
+                true
+            }
+            Event::MouseDown { x, y, button, modifiers } => {
+                self.focused = self.rect.contains(Point::new(*x, *y));
+                let index = match self.row_at(*x, *y) {
+                    Some(index) => index,
+                    None => return self.focused,
+                };
+                if *button == MouseButton::Left {
+                    if self.selection.click(index, *modifiers) {
+                        self.selection_changed();
+                    }
+                    let now = Instant::now();
+                    let double = matches!(self.last_click, Some((i, at)) if i == index && now - at < DOUBLE_CLICK);
+                    self.last_click = if double { None } else { Some((index, now)) };
+                    if double {
+                        if let Some(on_activate) = &mut self.on_activate {
+                            on_activate(index);
+                        }
+                    }
+                }
+                if let Some((_, row)) = self.visible.iter_mut().find(|(i, _)| *i == index) {
+                    row.on_event(event);
+                }
+                true
+            }
+            Event::KeyDown { key: Some(key), modifiers } if self.focused => self.on_key(*key, *modifiers),
+            Event::FocusLost => {
+                self.focused = false;
+                false
+            }
+            _ => {
+                let mut handled = false;
+                for (_, row) in &mut self.visible {
+                    handled |= row.on_event(event);
+                }
+                handled
+            }
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.viewport = rect;
+        // Lay out once to measure, then again if the rows turned out taller
+        // than the viewport and need a scrollbar.
+        self.update_layout();
+        if self.scrollbar.is_needed() {
+            self.viewport.right -= SCROLLBAR_SIZE;
+            self.update_layout();
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn visit_children(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        for (_, row) in &self.visible {
+            f(row);
+        }
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("rows", self.delegate.len().to_string()),
+            ("selected", format!("{:?}", self.selection.selected().collect::<Vec<_>>())),
+            ("offset", self.offset.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (self.rect.width(), self.heights.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unmeasured_rows_use_the_estimate() {
+        let index = HeightIndex::new(10, 20.0);
+        assert_eq!(index.total(), 200.0);
+        assert_eq!(index.offset_of(3), 60.0);
+        assert!(!index.is_measured(3));
+    }
+
+    #[test]
+    fn measuring_a_row_shifts_the_rows_after_it() {
+        let mut index = HeightIndex::new(10, 20.0);
+        index.set(2, 50.0);
+        assert!(index.is_measured(2));
+        assert_eq!(index.offset_of(2), 40.0);
+        assert_eq!(index.offset_of(3), 90.0);
+        assert_eq!(index.total(), 230.0);
+    }
+
+    #[test]
+    fn index_at_finds_the_row_under_an_offset() {
+        let mut index = HeightIndex::new(7, 10.0);
+        index.set(1, 30.0);
+        assert_eq!(index.index_at(0.0), 0);
+        assert_eq!(index.index_at(9.9), 0);
+        assert_eq!(index.index_at(10.0), 1);
+        assert_eq!(index.index_at(39.9), 1);
+        assert_eq!(index.index_at(40.0), 2);
+        assert_eq!(index.index_at(1000.0), 6);
+    }
+
+    #[test]
+    fn index_at_on_an_empty_index_is_zero() {
+        let index = HeightIndex::new(0, 10.0);
+        assert!(index.is_empty());
+        assert_eq!(index.total(), 0.0);
+        assert_eq!(index.index_at(50.0), 0);
+    }
+}
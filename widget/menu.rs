@@ -0,0 +1,792 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::accel::{char_for_key, KeyChord};
+use crate::event::Event;
+use crate::widget::overlay::{Placement, Popup, PopupId};
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Font, Paint, PaintStyle, Point, Rect};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const ITEM_HEIGHT: f32 = 26.0;
+const SEPARATOR_HEIGHT: f32 = 9.0;
+const MENU_PADDING: f32 = 4.0;
+const GUTTER: f32 = 24.0;
+const MIN_MENU_WIDTH: f32 = 160.0;
+pub const MENU_BAR_HEIGHT: f32 = 28.0;
+
+// "&File" -> ("File", Some('f'), Some(0)). "&&" is a literal ampersand.
+fn parse_mnemonic(text: &str) -> (String, Option<char>, Option<usize>) {
+    let mut label = String::new();
+    let mut mnemonic = None;
+    let mut index = None;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            match chars.next() {
+                Some('&') => label.push('&'),
+                Some(next) => {
+                    if mnemonic.is_none() {
+                        mnemonic = Some(next.to_ascii_lowercase());
+                        index = Some(label.len());
+                    }
+                    label.push(next);
+                }
+                None => {}
+            }
+        } else {
+            label.push(c);
+        }
+    }
+
+    (label, mnemonic, index)
+}
+
+#[derive(Clone)]
+pub enum MenuItemKind {
+    Action,
+    Check,
+    Radio(u32),
+    Separator,
+    Submenu(Menu),
+}
+
+// Items are cheap to clone and clones share their checked/enabled state, so
+// the copy shown in a popup and the one bound to an accelerator stay in sync.
+#[derive(Clone)]
+pub struct MenuItem {
+    label: String,
+    mnemonic: Option<char>,
+    mnemonic_index: Option<usize>,
+    kind: MenuItemKind,
+    checked: Rc<Cell<bool>>,
+    enabled: Rc<Cell<bool>>,
+    accelerator: Option<KeyChord>,
+    on_activate: Option<Rc<dyn Fn(bool)>>,
+}
+
+impl MenuItem {
+    fn new(label: &str, kind: MenuItemKind) -> Self {
+        let (label, mnemonic, mnemonic_index) = parse_mnemonic(label);
+        Self {
+            label,
+            mnemonic,
+            mnemonic_index,
+            kind,
+            checked: Rc::new(Cell::new(false)),
+            enabled: Rc::new(Cell::new(true)),
+            accelerator: None,
+            on_activate: None,
+        }
+    }
+
+    pub fn action<F: Fn() + 'static>(label: &str, f: F) -> Self {
+        let mut item = Self::new(label, MenuItemKind::Action);
+        item.on_activate = Some(Rc::new(move |_| f()));
+        item
+    }
+
+    pub fn check<F: Fn(bool) + 'static>(label: &str, checked: bool, f: F) -> Self {
+        let mut item = Self::new(label, MenuItemKind::Check);
+        item.checked.set(checked);
+        item.on_activate = Some(Rc::new(f));
+        item
+    }
+
+    // Only one item of a group in the same menu is checked at a time.
+    pub fn radio<F: Fn() + 'static>(label: &str, group: u32, checked: bool, f: F) -> Self {
+        let mut item = Self::new(label, MenuItemKind::Radio(group));
+        item.checked.set(checked);
+        item.on_activate = Some(Rc::new(move |_| f()));
+        item
+    }
+
+    pub fn separator() -> Self {
+        Self::new("", MenuItemKind::Separator)
+    }
+
+    pub fn submenu(label: &str, menu: Menu) -> Self {
+        Self::new(label, MenuItemKind::Submenu(menu))
+    }
+
+    // e.g. "Ctrl+S". Unparseable chords are ignored.
+    pub fn with_accelerator(mut self, chord: &str) -> Self {
+        self.accelerator = KeyChord::parse(chord);
+        self
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked.get()
+    }
+
+    pub fn set_checked(&self, checked: bool) {
+        self.checked.set(checked);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+
+    fn is_selectable(&self) -> bool {
+        !matches!(self.kind, MenuItemKind::Separator) && self.is_enabled()
+    }
+
+    fn height(&self) -> f32 {
+        match self.kind {
+            MenuItemKind::Separator => SEPARATOR_HEIGHT,
+            _ => ITEM_HEIGHT,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Menu {
+    items: Vec<MenuItem>,
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    // Toggles check and radio state, then runs the item's callback. Returns
+    // false for separators, submenus and disabled items.
+    pub fn activate(&self, index: usize) -> bool {
+        let item = match self.items.get(index) {
+            Some(item) if item.is_selectable() => item,
+            _ => return false,
+        };
+
+        match item.kind {
+            MenuItemKind::Check => item.checked.set(!item.checked.get()),
+            MenuItemKind::Radio(group) => {
+                for other in &self.items {
+                    if let MenuItemKind::Radio(g) = other.kind {
+                        if g == group {
+                            other.checked.set(false);
+                        }
+                    }
+                }
+                item.checked.set(true);
+            }
+            MenuItemKind::Submenu(_) | MenuItemKind::Separator => return false,
+            MenuItemKind::Action => {}
+        }
+
+        if let Some(on_activate) = &item.on_activate {
+            on_activate(item.checked.get());
+        }
+        true
+    }
+
+    // Registers every accelerator in this menu and its submenus with the
+    // window that is currently handling events.
+    fn bind_accelerators(&self) {
+        for (index, item) in self.items.iter().enumerate() {
+            if let MenuItemKind::Submenu(menu) = &item.kind {
+                menu.bind_accelerators();
+            }
+            if let Some(chord) = item.accelerator {
+                let menu = self.clone();
+                context::bind_accelerator(chord, Rc::new(move || {
+                    menu.activate(index);
+                }));
+            }
+        }
+    }
+
+    fn size(&self, font: &Font) -> (f32, f32) {
+        let mut width: f32 = MIN_MENU_WIDTH;
+        let mut height = MENU_PADDING * 2.0;
+        for item in &self.items {
+            let label = font.measure_str(&item.label, None).0;
+            let accel = item
+                .accelerator
+                .map(|chord| font.measure_str(chord.to_string(), None).0 + 24.0)
+                .unwrap_or(0.0);
+            width = width.max(GUTTER * 2.0 + label + accel);
+            height += item.height();
+        }
+        (width, height)
+    }
+}
+
+// Opens a popup menu at a point, for right-click menus.
+pub fn show_context_menu(menu: &Menu, x: f32, y: f32) {
+    let chain = Rc::new(RefCell::new(Vec::new()));
+    let anchor = Rect::from_xywh(x, y, 0.0, 0.0);
+    let open = OpenMenu::new(PopupId::next(), anchor, Placement::Below);
+    open_menu(menu, chain, open);
+}
+
+// Where a menu popup goes and how it reports back to whatever opened it.
+struct OpenMenu {
+    id: PopupId,
+    anchor: Rect,
+    placement: Placement,
+    select_first: bool,
+    // Left/Right past the edge of a top-level menu, for the menubar.
+    on_navigate: Option<Rc<dyn Fn(isize)>>,
+    on_dismiss: Option<Box<dyn FnOnce()>>,
+    // Outside clicks here reach the window instead of closing the menu.
+    passthrough: Option<Rect>,
+}
+
+impl OpenMenu {
+    fn new(id: PopupId, anchor: Rect, placement: Placement) -> Self {
+        Self {
+            id,
+            anchor,
+            placement,
+            select_first: false,
+            on_navigate: None,
+            on_dismiss: None,
+            passthrough: None,
+        }
+    }
+}
+
+fn open_menu(menu: &Menu, chain: Rc<RefCell<Vec<PopupId>>>, open: OpenMenu) {
+    let OpenMenu { id, anchor, placement, select_first, on_navigate, on_dismiss, passthrough } = open;
+    let size = menu.size(&theme::current().font());
+    let is_submenu = !chain.borrow().is_empty();
+    let mut view = MenuView {
+        menu: menu.clone(),
+        chain: chain.clone(),
+        id,
+        rect: Rect::default(),
+        hovered: None,
+        submenu: None,
+        is_submenu,
+        on_navigate,
+    };
+    if select_first {
+        view.hovered = view.step(None, 1);
+    }
+
+    // However the popup goes away (outside click, Escape, activation), it
+    // drops out of the chain.
+    let dismiss_chain = Rc::downgrade(&chain);
+    let mut popup = Popup::new(id, Box::new(view), anchor, size).with_placement(placement);
+    if let Some(rect) = passthrough {
+        popup = popup.with_passthrough(rect);
+    }
+    let popup = popup.on_dismiss(move || {
+        if let Some(chain) = dismiss_chain.upgrade() {
+            chain.borrow_mut().retain(|open| *open != id);
+        }
+        if let Some(on_dismiss) = on_dismiss {
+            on_dismiss();
+        }
+    });
+    chain.borrow_mut().push(id);
+    context::show_popup(popup);
+}
+
+fn close_chain(chain: &Rc<RefCell<Vec<PopupId>>>) {
+    for id in chain.borrow_mut().drain(..) {
+        context::dismiss_popup(id);
+    }
+}
+
+// The popup half of a menu. Each open submenu is its own popup; they share a
+// chain so activating an item closes all of them.
+struct MenuView {
+    menu: Menu,
+    chain: Rc<RefCell<Vec<PopupId>>>,
+    id: PopupId,
+    rect: Rect,
+    hovered: Option<usize>,
+    submenu: Option<(usize, PopupId)>,
+    is_submenu: bool,
+    on_navigate: Option<Rc<dyn Fn(isize)>>,
+}
+
+impl MenuView {
+    fn item_rect(&self, index: usize) -> Rect {
+        let mut top = self.rect.top + MENU_PADDING;
+        for item in &self.menu.items[..index] {
+            top += item.height();
+        }
+        Rect::from_xywh(self.rect.left, top, self.rect.width(), self.menu.items[index].height())
+    }
+
+    fn item_at(&self, x: f32, y: f32) -> Option<usize> {
+        (0..self.menu.items.len()).find(|i| self.item_rect(*i).contains(Point::new(x, y)))
+    }
+
+    // Next selectable item from `from` in direction `dir`, wrapping around.
+    fn step(&self, from: Option<usize>, dir: isize) -> Option<usize> {
+        let count = self.menu.items.len() as isize;
+        if count == 0 {
+            return None;
+        }
+        let mut index = from.map(|i| i as isize).unwrap_or(if dir > 0 { -1 } else { count });
+        for _ in 0..count {
+            index = (index + dir).rem_euclid(count);
+            if self.menu.items[index as usize].is_selectable() {
+                return Some(index as usize);
+            }
+        }
+        None
+    }
+
+    fn close_submenu(&mut self) {
+        if let Some((_, id)) = self.submenu.take() {
+            // Everything opened after the submenu belongs to it.
+            let mut chain = self.chain.borrow_mut();
+            if let Some(pos) = chain.iter().position(|open| *open == id) {
+                for id in chain.drain(pos..) {
+                    context::dismiss_popup(id);
+                }
+            }
+        }
+    }
+
+    fn open_submenu(&mut self, index: usize, select_first: bool) {
+        if let Some((open, id)) = self.submenu {
+            if open == index && self.chain.borrow().contains(&id) {
+                return;
+            }
+        }
+        self.close_submenu();
+        if let MenuItemKind::Submenu(menu) = &self.menu.items[index].kind {
+            let id = PopupId::next();
+            let mut open = OpenMenu::new(id, self.item_rect(index), Placement::Right);
+            open.select_first = select_first;
+            open_menu(menu, self.chain.clone(), open);
+            self.submenu = Some((index, id));
+        }
+    }
+
+    fn activate(&mut self, index: usize, from_keyboard: bool) {
+        if !self.menu.items[index].is_selectable() {
+            return;
+        }
+        if let MenuItemKind::Submenu(_) = self.menu.items[index].kind {
+            self.open_submenu(index, from_keyboard);
+        } else if self.menu.activate(index) {
+            close_chain(&self.chain);
+        }
+    }
+
+    fn draw_item(&self, canvas: &mut Canvas, index: usize, font: &Font) {
+        let theme = theme::current();
+        let item = &self.menu.items[index];
+        let rect = self.item_rect(index);
+
+        if let MenuItemKind::Separator = item.kind {
+            let mut paint = Paint::default();
+            paint.set_color(theme.border);
+            let y = rect.center_y();
+            canvas.draw_line((rect.left + 8.0, y), (rect.right - 8.0, y), &paint);
+            return;
+        }
+
+        let highlighted = self.hovered == Some(index) && item.is_enabled();
+        if highlighted {
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true);
+            paint.set_color(theme.accent);
+            canvas.draw_round_rect(rect.with_inset((4.0, 1.0)), 4.0, 4.0, &paint);
+        }
+
+        let mut text_paint = Paint::default();
+        text_paint.set_anti_alias(true);
+        text_paint.set_color(if !item.is_enabled() {
+            theme.disabled_foreground
+        } else if highlighted {
+            theme.accent_foreground
+        } else {
+            theme.foreground
+        });
+
+        let baseline = rect.center_y() + font.size() / 3.0;
+        let mark = match item.kind {
+            MenuItemKind::Check if item.is_checked() => Some("\u{2713}"),
+            MenuItemKind::Radio(_) if item.is_checked() => Some("\u{2022}"),
+            _ => None,
+        };
+        if let Some(mark) = mark {
+            canvas.draw_str(mark, (rect.left + 8.0, baseline), font, &text_paint);
+        }
+
+        let label_x = rect.left + GUTTER;
+        canvas.draw_str(&item.label, (label_x, baseline), font, &text_paint);
+        draw_mnemonic(canvas, &item.label, item.mnemonic_index, label_x, baseline, font, &text_paint);
+
+        let trailing = match &item.kind {
+            MenuItemKind::Submenu(_) => Some("\u{25B8}".to_string()),
+            _ => item.accelerator.map(|chord| chord.to_string()),
+        };
+        if let Some(trailing) = trailing {
+            let width = font.measure_str(&trailing, Some(&text_paint)).0;
+            canvas.draw_str(&trailing, (rect.right - 12.0 - width, baseline), font, &text_paint);
+        }
+    }
+}
+
+fn draw_mnemonic(
+    canvas: &mut Canvas,
+    label: &str,
+    index: Option<usize>,
+    x: f32,
+    baseline: f32,
+    font: &Font,
+    paint: &Paint,
+) {
+    if let Some(index) = index {
+        let c = match label[index..].chars().next() {
+            Some(c) => c,
+            None => return,
+        };
+        let start = x + font.measure_str(&label[..index], Some(paint)).0;
+        let width = font.measure_str(c.to_string(), Some(paint)).0;
+        canvas.draw_line((start, baseline + 2.0), (start + width, baseline + 2.0), paint);
+    }
+}
+
+impl Widget for MenuView {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let font = theme.font();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(theme.background);
+        canvas.draw_round_rect(self.rect, theme.corner_radius, theme.corner_radius, &paint);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_color(theme.border);
+        canvas.draw_round_rect(self.rect, theme.corner_radius, theme.corner_radius, &paint);
+
+        for index in 0..self.menu.items.len() {
+            self.draw_item(canvas, index, &font);
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                self.hovered = self.item_at(*x, *y);
+                match self.hovered {
+                    Some(index) if matches!(self.menu.items[index].kind, MenuItemKind::Submenu(_)) => {
+                        self.open_submenu(index, false)
+                    }
+                    Some(_) => self.close_submenu(),
+                    None => {}
+                }
+                true
+            }
+            Event::MouseUp { x, y, button: MouseButton::Left, .. } => {
+                if let Some(index) = self.item_at(*x, *y) {
+                    self.activate(index, false);
+                }
+                true
+            }
+            Event::MouseDown { .. } => true,
+            Event::KeyDown { key: Some(key), .. } => match key {
+                VirtualKeyCode::Down => {
+                    self.hovered = self.step(self.hovered, 1);
+                    true
+                }
+                VirtualKeyCode::Up => {
+                    self.hovered = self.step(self.hovered, -1);
+                    true
+                }
+                VirtualKeyCode::Right => {
+                    match self.hovered {
+                        Some(index) if matches!(self.menu.items[index].kind, MenuItemKind::Submenu(_)) => {
+                            self.open_submenu(index, true)
+                        }
+                        _ => {
+                            if let Some(on_navigate) = &self.on_navigate {
+                                on_navigate(1);
+                            }
+                        }
+                    }
+                    true
+                }
+                VirtualKeyCode::Left => {
+                    if self.is_submenu {
+                        context::dismiss_popup(self.id);
+                    } else if let Some(on_navigate) = &self.on_navigate {
+                        on_navigate(-1);
+                    }
+                    true
+                }
+                VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Space => {
+                    if let Some(index) = self.hovered {
+                        self.activate(index, true);
+                    }
+                    true
+                }
+                _ => false,
+            },
+            Event::CharInput(c) => {
+                let c = c.to_ascii_lowercase();
+                if let Some(index) = self.menu.items.iter().position(|item| item.mnemonic == Some(c)) {
+                    self.hovered = Some(index);
+                    self.activate(index, true);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+}
+
+struct MenuTitle {
+    label: String,
+    mnemonic: Option<char>,
+    mnemonic_index: Option<usize>,
+    menu: Menu,
+    rect: Rect,
+}
+
+struct MenuBarState {
+    titles: Vec<MenuTitle>,
+    open: Option<(usize, PopupId)>,
+    hovered: Option<usize>,
+    chain: Rc<RefCell<Vec<PopupId>>>,
+    // Cleared whenever a menu is added, so the next layout binds the
+    // accelerators again.
+    accelerators_bound: bool,
+    // The bar's rect, so clicks on other titles go through an open menu.
+    rect: Rect,
+}
+
+// A row of menu titles along the top of its rect. Alt+mnemonic opens a menu
+// from the keyboard, and Left/Right move between menus while one is open.
+pub struct MenuBar {
+    state: Rc<RefCell<MenuBarState>>,
+    rect: Rect,
+}
+
+impl Default for MenuBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MenuBar {
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(MenuBarState {
+                titles: Vec::new(),
+                open: None,
+                hovered: None,
+                chain: Rc::new(RefCell::new(Vec::new())),
+                accelerators_bound: false,
+                rect: Rect::default(),
+            })),
+            rect: Rect::default(),
+        }
+    }
+
+    pub fn menu(self, title: &str, menu: Menu) -> Self {
+        let (label, mnemonic, mnemonic_index) = parse_mnemonic(title);
+        let mut s = self.state.borrow_mut();
+        s.titles.push(MenuTitle {
+            label,
+            mnemonic,
+            mnemonic_index,
+            menu,
+            rect: Rect::default(),
+        });
+        s.accelerators_bound = false;
+        drop(s);
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state.borrow().open.is_some()
+    }
+
+    fn open(state: &Rc<RefCell<MenuBarState>>, index: usize, select_first: bool) {
+        let (menu, anchor, bar, chain) = {
+            let mut s = state.borrow_mut();
+            close_chain(&s.chain);
+            s.open = None;
+            let title = &s.titles[index];
+            (title.menu.clone(), title.rect, s.rect, s.chain.clone())
+        };
+
+        let nav_state = state.clone();
+        let on_navigate: Rc<dyn Fn(isize)> = Rc::new(move |dir| {
+            let next = {
+                let s = nav_state.borrow();
+                match s.open {
+                    Some((open, _)) => (open as isize + dir).rem_euclid(s.titles.len() as isize) as usize,
+                    None => return,
+                }
+            };
+            MenuBar::open(&nav_state, next, true);
+        });
+
+        let dismiss_state = Rc::downgrade(state);
+        let id = PopupId::next();
+        let mut open = OpenMenu::new(id, anchor, Placement::Below);
+        open.select_first = select_first;
+        open.on_navigate = Some(on_navigate);
+        open.passthrough = Some(bar);
+        open.on_dismiss = Some(Box::new(move || {
+            if let Some(state) = dismiss_state.upgrade() {
+                let mut s = state.borrow_mut();
+                if s.open.map(|(_, open_id)| open_id) == Some(id) {
+                    s.open = None;
+                }
+            }
+        }));
+        open_menu(&menu, chain, open);
+        state.borrow_mut().open = Some((index, id));
+    }
+
+    fn title_at(&self, x: f32, y: f32) -> Option<usize> {
+        let s = self.state.borrow();
+        s.titles.iter().position(|t| t.rect.contains(Point::new(x, y)))
+    }
+}
+
+impl Widget for MenuBar {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let font = theme.font();
+        let s = self.state.borrow();
+
+        let mut paint = Paint::default();
+        paint.set_color(theme.surface);
+        canvas.draw_rect(self.rect, &paint);
+        paint.set_color(theme.border);
+        canvas.draw_line(
+            (self.rect.left, self.rect.bottom - 0.5),
+            (self.rect.right, self.rect.bottom - 0.5),
+            &paint,
+        );
+
+        for (index, title) in s.titles.iter().enumerate() {
+            let open = s.open.map(|(i, _)| i) == Some(index);
+            if open || s.hovered == Some(index) {
+                let mut bg = Paint::default();
+                bg.set_anti_alias(true);
+                bg.set_color(if open { theme.accent } else { theme.surface_hover });
+                canvas.draw_round_rect(title.rect.with_inset((2.0, 3.0)), 4.0, 4.0, &bg);
+            }
+
+            let mut text_paint = Paint::default();
+            text_paint.set_anti_alias(true);
+            text_paint.set_color(if open { theme.accent_foreground } else { theme.foreground });
+            let x = title.rect.left + 10.0;
+            let baseline = title.rect.center_y() + font.size() / 3.0;
+            canvas.draw_str(&title.label, (x, baseline), &font, &text_paint);
+            draw_mnemonic(canvas, &title.label, title.mnemonic_index, x, baseline, &font, &text_paint);
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                let hovered = self.title_at(*x, *y);
+                let open = self.state.borrow().open.map(|(i, _)| i);
+                self.state.borrow_mut().hovered = hovered;
+                // Sliding across the bar with a menu open switches menus.
+                if let (Some(hovered), Some(open)) = (hovered, open) {
+                    if hovered != open {
+                        MenuBar::open(&self.state, hovered, false);
+                    }
+                }
+                hovered.is_some()
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => match self.title_at(*x, *y) {
+                Some(index) => {
+                    if self.state.borrow().open.map(|(i, _)| i) == Some(index) {
+                        let mut s = self.state.borrow_mut();
+                        close_chain(&s.chain);
+                        s.open = None;
+                    } else {
+                        MenuBar::open(&self.state, index, false);
+                    }
+                    true
+                }
+                None => {
+                    // The open menu lets clicks on the bar through; one that
+                    // misses every title still closes it.
+                    let mut s = self.state.borrow_mut();
+                    if s.open.is_some() && self.rect.contains(Point::new(*x, *y)) {
+                        close_chain(&s.chain);
+                        s.open = None;
+                        return true;
+                    }
+                    false
+                }
+            },
+            Event::KeyDown { key: Some(key), modifiers } if modifiers.alt() => {
+                let c = match char_for_key(*key) {
+                    Some(c) => c,
+                    None => return false,
+                };
+                let index = self.state.borrow().titles.iter().position(|t| t.mnemonic == Some(c));
+                match index {
+                    Some(index) => {
+                        MenuBar::open(&self.state, index, true);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = Rect::from_xywh(rect.left, rect.top, rect.width(), MENU_BAR_HEIGHT);
+        let font = theme::current().font();
+
+        let mut s = self.state.borrow_mut();
+        s.rect = self.rect;
+        let mut x = self.rect.left + 4.0;
+        for title in &mut s.titles {
+            let width = font.measure_str(&title.label, None).0 + 20.0;
+            title.rect = Rect::from_xywh(x, self.rect.top, width, MENU_BAR_HEIGHT);
+            x += width;
+        }
+        if !s.accelerators_bound {
+            for title in &s.titles {
+                title.menu.bind_accelerators();
+            }
+            s.accelerators_bound = true;
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+}
@@ -1,19 +1,104 @@
 // Copyright 2025 Bluegill Studios.
 // Licensed under the GNU General Public License v2.0.
 
+pub mod badge;
+pub mod canvas;
+pub mod chart;
 pub mod button;
+pub mod checkbox;
+pub mod combo;
+pub mod dialog;
+pub mod icon;
+pub mod image;
 pub mod label;
+pub mod list;
+pub mod menu;
+pub mod overlay;
 pub mod panel;
+pub mod progress;
+pub mod radio;
+pub mod scroll;
+pub mod selection;
+pub mod slider;
+pub mod spin;
+pub mod split;
+pub mod switch;
+pub mod table;
+pub mod tabs;
+#[cfg(test)]
+pub(crate) mod testing;
+pub mod textedit;
+pub mod theme;
+pub mod tree;
 
+pub use badge::{Badge, Status, StatusDot};
+pub use canvas::{Brush, CanvasWidget, DrawContext};
+pub use chart::{Chart, Series, SeriesKind};
 pub use button::Button;
+pub use checkbox::{CheckState, Checkbox};
+pub use combo::ComboBox;
+pub use dialog::{Dialog, DialogResult};
+pub use icon::{Icon, Svg, SvgSource};
+pub use image::{Image, ImageFit, ImageSource, Sampling};
 pub use label::Label;
+pub use list::{ListDelegate, ListView};
+pub use menu::{Menu, MenuBar, MenuItem};
+pub use overlay::{Overlay, Placement, Popup, PopupId};
 pub use panel::Panel;
+pub use progress::{ProgressBar, Spinner};
+pub use radio::RadioGroup;
+pub use scroll::ScrollView;
+pub use selection::{Selection, SelectionMode};
+pub use slider::Slider;
+pub use spin::{DecimalFormat, DurationFormat, HexFormat, SpinBox, ValueFormat};
+pub use split::{Pane, SplitPane};
+pub use switch::Switch;
+pub use table::{CellValue, Column, Table, TableSource};
+pub use tabs::TabView;
+pub use theme::Theme;
+pub use tree::{DropPosition, TreeProvider, TreeView};
 
-use skia_safe::Canvas;
+use skia_safe::Rect;
 use crate::event::Event;
 
 pub trait Widget {
     fn draw(&mut self, canvas: &mut skia_safe::Canvas);
     fn on_event(&mut self, event: &Event) -> bool; 
     fn layout(&mut self, rect: skia_safe::Rect);
+
+    // Size the widget would like if given the room, as (width, height).
+    // Scroll views use it as the content size.
+    fn preferred_size(&self) -> (f32, f32) {
+        (0.0, 0.0)
+    }
+
+    // The rest is for debugging tools, which walk the tree from the root.
+
+    // Where the last layout put the widget, in window coordinates. Empty if
+    // the widget doesn't say.
+    fn bounds(&self) -> Rect {
+        Rect::default()
+    }
+
+    // The type without its module path or type parameters, e.g. "ListView".
+    fn type_name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    // Calls `f` with each child currently laid out, in drawing order.
+    fn visit_children(&self, _f: &mut dyn FnMut(&dyn Widget)) {}
+
+    // Name/value pairs describing the widget's state, e.g. ("value", "0.5").
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    // Like `debug_properties`, for how the widget was set up to look rather
+    // than what it is showing, e.g. ("tint", "#ff0000ff"). Anything not
+    // listed comes from the theme.
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
 }
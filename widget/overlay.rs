@@ -0,0 +1,331 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::event::Event;
+use crate::widget::Widget;
+use skia_safe::{Canvas, Point, Rect};
+use std::sync::atomic::{AtomicU64, Ordering};
+use winit::event::VirtualKeyCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PopupId(u64);
+
+impl PopupId {
+    pub fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        PopupId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+// Where a popup goes relative to its anchor. It flips to the opposite side
+// when the preferred one would run off the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement {
+    Below,
+    Above,
+    Right,
+    Left,
+    // Covers the whole window; used by modal dialogs.
+    Fill,
+}
+
+pub struct Popup {
+    id: PopupId,
+    content: Box<dyn Widget>,
+    anchor: Rect,
+    size: (f32, f32),
+    placement: Placement,
+    modal: bool,
+    interactive: bool,
+    passthrough: Option<Rect>,
+    on_dismiss: Option<Box<dyn FnOnce()>>,
+    rect: Rect,
+}
+
+impl Popup {
+    pub fn new(id: PopupId, content: Box<dyn Widget>, anchor: Rect, size: (f32, f32)) -> Self {
+        Self {
+            id,
+            content,
+            anchor,
+            size,
+            placement: Placement::Below,
+            modal: false,
+            interactive: true,
+            passthrough: None,
+            on_dismiss: None,
+            rect: Rect::default(),
+        }
+    }
+
+    // Takes all input until it is dismissed, and outside clicks don't close it.
+    pub fn modal(id: PopupId, content: Box<dyn Widget>) -> Self {
+        let mut popup = Self::new(id, content, Rect::default(), (0.0, 0.0));
+        popup.placement = Placement::Fill;
+        popup.modal = true;
+        popup
+    }
+
+    // Drawn on top but never hit-tested, so the pointer passes straight through.
+    pub fn tooltip(id: PopupId, content: Box<dyn Widget>, anchor: Rect, size: (f32, f32)) -> Self {
+        let mut popup = Self::new(id, content, anchor, size);
+        popup.interactive = false;
+        popup
+    }
+
+    pub fn with_placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    // Clicks inside `rect` go to the window underneath instead of dismissing
+    // the popup, e.g. a menubar switching to another menu.
+    pub fn with_passthrough(mut self, rect: Rect) -> Self {
+        self.passthrough = Some(rect);
+        self
+    }
+
+    pub fn on_dismiss<F: FnOnce() + 'static>(mut self, f: F) -> Self {
+        self.on_dismiss = Some(Box::new(f));
+        self
+    }
+
+    pub fn id(&self) -> PopupId {
+        self.id
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn contains(&self, x: f32, y: f32) -> bool {
+        self.interactive && (self.modal || self.rect.contains(Point::new(x, y)))
+    }
+
+    fn dismiss(mut self) {
+        if let Some(on_dismiss) = self.on_dismiss.take() {
+            on_dismiss();
+        }
+    }
+}
+
+// Works out the popup rect for `anchor`, flipping and then clamping so it
+// stays inside `bounds`.
+pub fn place(anchor: Rect, size: (f32, f32), placement: Placement, bounds: Rect) -> Rect {
+    let (width, height) = size;
+    let below = Rect::from_xywh(anchor.left, anchor.bottom, width, height);
+    let above = Rect::from_xywh(anchor.left, anchor.top - height, width, height);
+    let right = Rect::from_xywh(anchor.right, anchor.top, width, height);
+    let left = Rect::from_xywh(anchor.left - width, anchor.top, width, height);
+
+    let mut rect = match placement {
+        Placement::Fill => return bounds,
+        Placement::Below if below.bottom > bounds.bottom && above.top >= bounds.top => above,
+        Placement::Below => below,
+        Placement::Above if above.top < bounds.top && below.bottom <= bounds.bottom => below,
+        Placement::Above => above,
+        Placement::Right if right.right > bounds.right && left.left >= bounds.left => left,
+        Placement::Right => right,
+        Placement::Left if left.left < bounds.left && right.right <= bounds.right => right,
+        Placement::Left => left,
+    };
+
+    let dx = if rect.right > bounds.right {
+        bounds.right - rect.right
+    } else {
+        0.0
+    };
+    let dy = if rect.bottom > bounds.bottom {
+        bounds.bottom - rect.bottom
+    } else {
+        0.0
+    };
+    rect.offset((dx, dy));
+    rect.offset((
+        (bounds.left - rect.left).max(0.0),
+        (bounds.top - rect.top).max(0.0),
+    ));
+    rect
+}
+
+// Popups drawn above a window's root widget. Later popups sit on top of
+// earlier ones and see events first.
+#[derive(Default)]
+pub struct Overlay {
+    popups: Vec<Popup>,
+    bounds: Rect,
+}
+
+impl Overlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.popups.is_empty()
+    }
+
+    pub fn has_modal(&self) -> bool {
+        self.popups.iter().any(|p| p.modal)
+    }
+
+    pub fn contains(&self, id: PopupId) -> bool {
+        self.popups.iter().any(|p| p.id == id)
+    }
+
+    pub fn show(&mut self, mut popup: Popup) {
+        popup.rect = place(popup.anchor, popup.size, popup.placement, self.bounds);
+        popup.content.layout(popup.rect);
+        self.popups.push(popup);
+    }
+
+    pub fn dismiss(&mut self, id: PopupId) {
+        if let Some(index) = self.popups.iter().position(|p| p.id == id) {
+            self.popups.remove(index).dismiss();
+        }
+    }
+
+    pub fn dismiss_all(&mut self) {
+        while let Some(popup) = self.popups.pop() {
+            popup.dismiss();
+        }
+    }
+
+    pub fn layout(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+        for popup in &mut self.popups {
+            popup.rect = place(popup.anchor, popup.size, popup.placement, bounds);
+            popup.content.layout(popup.rect);
+        }
+    }
+
+    pub fn draw(&mut self, canvas: &mut Canvas) {
+        for popup in &mut self.popups {
+            popup.content.draw(canvas);
+        }
+    }
+
+    // Calls `f` with each popup's content, bottom first.
+    pub fn visit_popups(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        for popup in &self.popups {
+            f(popup.content.as_ref());
+        }
+    }
+
+    // Returns true if the overlay consumed the event, in which case it must
+    // not reach the root widget.
+    pub fn on_event(&mut self, event: &Event) -> bool {
+        let point = match event {
+            Event::MouseDown { x, y, .. }
+            | Event::MouseUp { x, y, .. }
+            | Event::MouseMove { x, y, .. }
+            | Event::Scroll { x, y, .. } => Some((*x, *y)),
+            _ => None,
+        };
+
+        match point {
+            Some((x, y)) => {
+                if let Some(index) = self.popups.iter().rposition(|p| p.contains(x, y)) {
+                    self.popups[index].content.on_event(event);
+                    return true;
+                }
+                if let Event::MouseDown { .. } = event {
+                    if self.passes_through(x, y) {
+                        return false;
+                    }
+                    // Clicking outside closes the transient popups above the
+                    // topmost modal one and eats the click.
+                    let dismissed = self.dismiss_transient();
+                    return dismissed || self.has_modal();
+                }
+                self.has_modal()
+            }
+            None => {
+                let top = match self.popups.iter_mut().rev().find(|p| p.interactive) {
+                    Some(top) => top,
+                    None => return false,
+                };
+                if top.content.on_event(event) {
+                    return true;
+                }
+                if let Event::KeyDown { key: Some(VirtualKeyCode::Escape), .. } = event {
+                    if !top.modal {
+                        let id = top.id;
+                        self.dismiss(id);
+                        return true;
+                    }
+                }
+                top.modal || matches!(event, Event::KeyDown { .. } | Event::KeyUp { .. } | Event::CharInput(_))
+            }
+        }
+    }
+
+    fn passes_through(&self, x: f32, y: f32) -> bool {
+        !self.has_modal()
+            && self
+                .popups
+                .iter()
+                .any(|p| p.passthrough.is_some_and(|rect| rect.contains(Point::new(x, y))))
+    }
+
+    fn dismiss_transient(&mut self) -> bool {
+        let mut dismissed = false;
+        while let Some(top) = self.popups.last() {
+            if top.modal {
+                break;
+            }
+            self.popups.pop().unwrap().dismiss();
+            dismissed = true;
+        }
+        dismissed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::label::Label;
+    use winit::event::{ModifiersState, MouseButton};
+
+    fn press(x: f32, y: f32) -> Event {
+        Event::MouseDown {
+            x,
+            y,
+            button: MouseButton::Left,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    fn menu_overlay() -> (Overlay, PopupId) {
+        let mut overlay = Overlay::new();
+        overlay.layout(Rect::from_wh(400.0, 300.0));
+        let id = PopupId::next();
+        let bar = Rect::from_wh(400.0, 28.0);
+        let anchor = Rect::from_xywh(4.0, 0.0, 40.0, 28.0);
+        let popup = Popup::new(id, Box::new(Label::new("menu")), anchor, (160.0, 100.0)).with_passthrough(bar);
+        overlay.show(popup);
+        (overlay, id)
+    }
+
+    #[test]
+    fn clicks_in_the_passthrough_rect_reach_the_window() {
+        let (mut overlay, id) = menu_overlay();
+        assert!(!overlay.on_event(&press(100.0, 10.0)));
+        assert!(overlay.contains(id));
+    }
+
+    #[test]
+    fn clicks_elsewhere_still_dismiss() {
+        let (mut overlay, id) = menu_overlay();
+        assert!(overlay.on_event(&press(300.0, 200.0)));
+        assert!(!overlay.contains(id));
+    }
+
+    #[test]
+    fn modal_popups_block_the_passthrough() {
+        let (mut overlay, id) = menu_overlay();
+        overlay.show(Popup::modal(PopupId::next(), Box::new(Label::new("dialog"))));
+        assert!(overlay.on_event(&press(100.0, 10.0)));
+        assert!(overlay.contains(id));
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::event::Event;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Color, Paint, Rect};
+
+const PADDING: f32 = 12.0;
+const SPACING: f32 = 8.0;
+
+// Stacks its children top to bottom, each at its preferred height and the
+// panel's full width, over an optional background.
+pub struct Panel {
+    children: Vec<Box<dyn Widget>>,
+    background: Option<Color>,
+    padding: f32,
+    spacing: f32,
+    rect: Rect,
+}
+
+impl Panel {
+    pub fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            background: None,
+            padding: PADDING,
+            spacing: SPACING,
+            rect: Rect::default(),
+        }
+    }
+
+    pub fn with_child(mut self, child: Box<dyn Widget>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn add(&mut self, child: Box<dyn Widget>) {
+        self.children.push(child);
+    }
+
+    pub fn children(&self) -> &[Box<dyn Widget>] {
+        &self.children
+    }
+}
+
+impl Default for Panel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Panel {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        if let Some(color) = self.background {
+            let mut paint = Paint::default();
+            paint.set_color(color);
+            canvas.draw_rect(self.rect, &paint);
+        }
+        for child in &mut self.children {
+            child.draw(canvas);
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        // Every child sees the event until one handles it, so hover and
+        // focus state can clear on the others.
+        let mut handled = false;
+        for child in &mut self.children {
+            if !handled {
+                handled = child.on_event(event);
+            } else if matches!(event, Event::MouseMove { .. } | Event::FocusLost) {
+                child.on_event(event);
+            }
+        }
+        handled
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+        let inner = rect.with_inset((self.padding, self.padding));
+        let mut y = inner.top;
+        for child in &mut self.children {
+            let (_, height) = child.preferred_size();
+            child.layout(Rect::from_xywh(inner.left, y, inner.width(), height));
+            y += height + self.spacing;
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn visit_children(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        for child in &self.children {
+            f(child.as_ref());
+        }
+    }
+
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        let mut style = vec![
+            ("padding", self.padding.to_string()),
+            ("spacing", self.spacing.to_string()),
+        ];
+        if let Some(color) = self.background {
+            style.push(("background", theme::color_hex(color)));
+        }
+        style
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let sizes: Vec<(f32, f32)> = self.children.iter().map(|child| child.preferred_size()).collect();
+        let width = sizes.iter().map(|size| size.0).fold(0.0, f32::max);
+        let height: f32 = sizes.iter().map(|size| size.1).sum();
+        let gaps = sizes.len().saturating_sub(1) as f32 * self.spacing;
+        (width + self.padding * 2.0, height + gaps + self.padding * 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::testing::render;
+    use crate::widget::ProgressBar;
+
+    #[test]
+    fn panel_paints_its_background_behind_children() {
+        let theme = theme::current();
+        let background = Color::from_rgb(10, 20, 30);
+        let mut panel = Panel::new()
+            .with_background(background)
+            .with_padding(8.0)
+            .with_child(Box::new(ProgressBar::new()));
+        let mut backend = render(&mut panel, Rect::from_xywh(0.0, 0.0, 200.0, 60.0));
+        assert_eq!(backend.pixel(2, 2), Some(background));
+        // The bar's empty track, 8px in and at its preferred height.
+        let (_, height) = ProgressBar::new().preferred_size();
+        assert_eq!(backend.pixel(100, (8.0 + height / 2.0) as i32), Some(theme.surface));
+        assert_eq!(backend.pixel(210, 2), Some(Color::WHITE));
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::{theme, Widget};
+use skia_safe::paint::Cap;
+use skia_safe::{Canvas, Paint, PaintStyle, Rect};
+use std::f32::consts::PI;
+use std::time::Instant;
+
+const BAR_HEIGHT: f32 = 6.0;
+// Seconds for the indeterminate stripe to cross the bar once.
+const SWEEP_PERIOD: f32 = 1.5;
+// Fraction of the bar the indeterminate stripe covers.
+const SWEEP_WIDTH: f32 = 0.3;
+// Seconds per spinner revolution.
+const SPIN_PERIOD: f32 = 1.0;
+const SPINNER_SIZE: f32 = 20.0;
+
+// Seconds since `start`, wrapped to one animation period and scaled to 0..1.
+fn phase(start: Instant, period: f32) -> f32 {
+    (start.elapsed().as_secs_f32() % period) / period
+}
+
+// A horizontal progress bar. Indeterminate bars animate a stripe back and
+// forth; they only ask for frames while being drawn, so a hidden bar lets
+// the window go idle.
+pub struct ProgressBar {
+    value: f32,
+    indeterminate: bool,
+    show_percentage: bool,
+    start: Instant,
+    rect: Rect,
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressBar {
+    pub fn new() -> Self {
+        Self {
+            value: 0.0,
+            indeterminate: false,
+            show_percentage: false,
+            start: Instant::now(),
+            rect: Rect::default(),
+        }
+    }
+
+    pub fn indeterminate() -> Self {
+        let mut bar = Self::new();
+        bar.indeterminate = true;
+        bar
+    }
+
+    pub fn with_percentage(mut self, show: bool) -> Self {
+        self.show_percentage = show;
+        self
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    // 0.0 to 1.0.
+    pub fn set_value(&mut self, value: f32) {
+        self.value = value.clamp(0.0, 1.0);
+        context::request_redraw();
+    }
+
+    pub fn is_indeterminate(&self) -> bool {
+        self.indeterminate
+    }
+
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        self.indeterminate = indeterminate;
+        self.start = Instant::now();
+        context::request_redraw();
+    }
+
+    fn track_rect(&self) -> Rect {
+        let right = if self.show_percentage && !self.indeterminate {
+            self.rect.right - 44.0
+        } else {
+            self.rect.right
+        };
+        Rect::from_ltrb(
+            self.rect.left,
+            self.rect.center_y() - BAR_HEIGHT / 2.0,
+            right,
+            self.rect.center_y() + BAR_HEIGHT / 2.0,
+        )
+    }
+}
+
+impl Widget for ProgressBar {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let track = self.track_rect();
+        let radius = BAR_HEIGHT / 2.0;
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(theme.surface);
+        canvas.draw_round_rect(track, radius, radius, &paint);
+
+        paint.set_color(theme.accent);
+        if self.indeterminate {
+            // The stripe enters from the left and leaves on the right.
+            let t = phase(self.start, SWEEP_PERIOD);
+            let width = track.width() * SWEEP_WIDTH;
+            let left = track.left - width + (track.width() + width) * t;
+            let stripe = Rect::from_ltrb(left.max(track.left), track.top, (left + width).min(track.right), track.bottom);
+            if stripe.width() > 0.0 {
+                canvas.draw_round_rect(stripe, radius, radius, &paint);
+            }
+            context::request_animation_frame();
+        } else if self.value > 0.0 {
+            let fill = Rect::from_ltrb(track.left, track.top, track.left + track.width() * self.value, track.bottom);
+            canvas.draw_round_rect(fill, radius, radius, &paint);
+        }
+
+        if self.show_percentage && !self.indeterminate {
+            let font = theme.font();
+            let text = format!("{:.0}%", self.value * 100.0);
+            let width = font.measure_str(&text, None).0;
+            paint.set_color(theme.foreground);
+            let baseline = self.rect.center_y() + font.size() / 3.0;
+            canvas.draw_str(&text, (self.rect.right - width, baseline), &font, &paint);
+        }
+    }
+
+    fn on_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("value", self.value.to_string()),
+            ("indeterminate", self.indeterminate.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (200.0, 20.0)
+    }
+}
+
+// A spinning arc for work of unknown length.
+pub struct Spinner {
+    spinning: bool,
+    start: Instant,
+    rect: Rect,
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spinner {
+    pub fn new() -> Self {
+        Self {
+            spinning: true,
+            start: Instant::now(),
+            rect: Rect::default(),
+        }
+    }
+
+    pub fn is_spinning(&self) -> bool {
+        self.spinning
+    }
+
+    // A stopped spinner draws nothing.
+    pub fn set_spinning(&mut self, spinning: bool) {
+        self.spinning = spinning;
+        context::request_redraw();
+    }
+}
+
+impl Widget for Spinner {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        if !self.spinning {
+            return;
+        }
+        let theme = theme::current();
+        let size = self.rect.width().min(self.rect.height());
+        let stroke = (size / 8.0).max(2.0);
+        let oval = Rect::from_xywh(
+            self.rect.center_x() - size / 2.0,
+            self.rect.center_y() - size / 2.0,
+            size,
+            size,
+        )
+        .with_inset((stroke / 2.0, stroke / 2.0));
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(stroke);
+        paint.set_stroke_cap(Cap::Round);
+        paint.set_color(theme.surface);
+        canvas.draw_oval(oval, &paint);
+
+        let rotation = phase(self.start, SPIN_PERIOD) * 360.0;
+        // The arc also breathes between a quarter and three quarters of a turn.
+        let sweep = 180.0 + 90.0 * (phase(self.start, SPIN_PERIOD * 2.0) * 2.0 * PI).sin();
+        paint.set_color(theme.accent);
+        canvas.draw_arc(oval, rotation, sweep, false, &paint);
+        context::request_animation_frame();
+    }
+
+    fn on_event(&mut self, _event: &Event) -> bool {
+        false
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("spinning", self.spinning.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (SPINNER_SIZE, SPINNER_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::testing::render;
+
+    #[test]
+    fn progress_bar_fills_up_to_its_value() {
+        let theme = theme::current();
+        let mut bar = ProgressBar::new();
+        bar.set_value(0.5);
+        let mut backend = render(&mut bar, Rect::from_xywh(0.0, 10.0, 200.0, 20.0));
+        assert_eq!(backend.pixel(50, 20), Some(theme.accent));
+        assert_eq!(backend.pixel(150, 20), Some(theme.surface));
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::scroll::Axis;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Point, Rect};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const CIRCLE_SIZE: f32 = 16.0;
+const LABEL_GAP: f32 = 8.0;
+const ITEM_HEIGHT: f32 = 24.0;
+// Space between options laid out horizontally.
+const ITEM_SPACING: f32 = 16.0;
+
+// A set of mutually exclusive options. The arrow keys move the selection,
+// the way native radio groups behave.
+pub struct RadioGroup {
+    options: Vec<String>,
+    selected: Option<usize>,
+    axis: Axis,
+    enabled: bool,
+    hovered: Option<usize>,
+    pressed: Option<usize>,
+    focused: bool,
+    rect: Rect,
+    on_changed: Option<Box<dyn FnMut(usize)>>,
+}
+
+impl RadioGroup {
+    pub fn new(options: &[&str]) -> Self {
+        Self {
+            options: options.iter().map(|o| o.to_string()).collect(),
+            selected: None,
+            axis: Axis::Vertical,
+            enabled: true,
+            hovered: None,
+            pressed: None,
+            focused: false,
+            rect: Rect::default(),
+            on_changed: None,
+        }
+    }
+
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.selected = (index < self.options.len()).then_some(index);
+        self
+    }
+
+    pub fn horizontal(mut self) -> Self {
+        self.axis = Axis::Horizontal;
+        self
+    }
+
+    pub fn on_changed<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_changed = Some(Box::new(f));
+        self
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|i| *i < self.options.len());
+        context::request_redraw();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.hovered = None;
+            self.pressed = None;
+            self.focused = false;
+        }
+        context::request_redraw();
+    }
+
+    fn choose(&mut self, index: usize) {
+        if self.selected == Some(index) {
+            return;
+        }
+        self.selected = Some(index);
+        if let Some(on_changed) = &mut self.on_changed {
+            on_changed(index);
+        }
+        context::request_redraw();
+    }
+
+    fn item_width(&self, index: usize) -> f32 {
+        let font = theme::current().font();
+        CIRCLE_SIZE + LABEL_GAP + font.measure_str(&self.options[index], None).0
+    }
+
+    fn item_rect(&self, index: usize) -> Rect {
+        match self.axis {
+            Axis::Vertical => Rect::from_xywh(
+                self.rect.left,
+                self.rect.top + index as f32 * ITEM_HEIGHT,
+                self.rect.width(),
+                ITEM_HEIGHT,
+            ),
+            Axis::Horizontal => {
+                let left: f32 = (0..index).map(|i| self.item_width(i) + ITEM_SPACING).sum();
+                Rect::from_xywh(self.rect.left + left, self.rect.top, self.item_width(index), ITEM_HEIGHT)
+            }
+        }
+    }
+
+    fn item_at(&self, x: f32, y: f32) -> Option<usize> {
+        (0..self.options.len()).find(|i| self.item_rect(*i).contains(Point::new(x, y)))
+    }
+}
+
+impl Widget for RadioGroup {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let font = theme.font();
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+
+        // With nothing selected yet, the ring goes on the first option, which
+        // is where the arrow keys start.
+        let focus_index = self.selected.unwrap_or(0);
+        for index in 0..self.options.len() {
+            let rect = self.item_rect(index);
+            let center = Point::new(rect.left + CIRCLE_SIZE / 2.0, rect.center_y());
+            let radius = CIRCLE_SIZE / 2.0;
+            let selected = self.selected == Some(index);
+
+            paint.set_style(PaintStyle::Fill);
+            paint.set_color(if !self.enabled {
+                theme.surface
+            } else if self.pressed == Some(index) {
+                theme.surface_pressed
+            } else if selected {
+                theme.accent
+            } else if self.hovered == Some(index) {
+                theme.surface_hover
+            } else {
+                theme.background
+            });
+            canvas.draw_circle(center, radius, &paint);
+
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_stroke_width(1.0);
+            paint.set_color(theme.border);
+            canvas.draw_circle(center, radius, &paint);
+
+            if selected {
+                paint.set_style(PaintStyle::Fill);
+                paint.set_color(if self.enabled { theme.accent_foreground } else { theme.disabled_foreground });
+                canvas.draw_circle(center, radius * 0.4, &paint);
+            }
+            if self.focused && index == focus_index {
+                let circle = Rect::from_xywh(rect.left, center.y - radius, CIRCLE_SIZE, CIRCLE_SIZE);
+                theme::draw_focus_ring(canvas, circle, radius);
+            }
+
+            paint.set_style(PaintStyle::Fill);
+            paint.set_color(if self.enabled { theme.foreground } else { theme.disabled_foreground });
+            let baseline = rect.center_y() + font.size() / 3.0;
+            canvas.draw_str(&self.options[index], (rect.left + CIRCLE_SIZE + LABEL_GAP, baseline), &font, &paint);
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        if !self.enabled || self.options.is_empty() {
+            return false;
+        }
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                let hovered = self.item_at(*x, *y);
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    context::request_redraw();
+                }
+                false
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => {
+                self.pressed = self.item_at(*x, *y);
+                self.focused = self.rect.contains(Point::new(*x, *y));
+                self.focused
+            }
+            Event::MouseUp { x, y, button: MouseButton::Left, .. } => {
+                let pressed = match self.pressed.take() {
+                    Some(pressed) => pressed,
+                    None => return false,
+                };
+                if self.item_at(*x, *y) == Some(pressed) {
+                    self.choose(pressed);
+                }
+                true
+            }
+            Event::KeyDown { key: Some(key), .. } if self.focused => {
+                let last = self.options.len() - 1;
+                let target = match key {
+                    VirtualKeyCode::Up | VirtualKeyCode::Left => self.selected.map_or(0, |i| i.saturating_sub(1)),
+                    VirtualKeyCode::Down | VirtualKeyCode::Right => self.selected.map_or(0, |i| (i + 1).min(last)),
+                    VirtualKeyCode::Home => 0,
+                    VirtualKeyCode::End => last,
+                    VirtualKeyCode::Space => self.selected.unwrap_or(0),
+                    _ => return false,
+                };
+                self.choose(target);
+                true
+            }
+            Event::FocusLost => {
+                self.focused = false;
+                self.pressed = None;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("options", self.options.len().to_string()),
+            ("selected", format!("{:?}", self.selected)),
+            ("enabled", self.enabled.to_string()),
+        ]
+    }
+
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("axis", format!("{:?}", self.axis)),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let widths = (0..self.options.len()).map(|i| self.item_width(i));
+        match self.axis {
+            Axis::Vertical => (widths.fold(0.0, f32::max), ITEM_HEIGHT * self.options.len() as f32),
+            Axis::Horizontal => {
+                let spacing = ITEM_SPACING * self.options.len().saturating_sub(1) as f32;
+                (widths.sum::<f32>() + spacing, ITEM_HEIGHT)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::testing::{press, render};
+    use skia_safe::Color;
+    use winit::event::ModifiersState;
+
+    #[test]
+    fn focus_ring_shows_without_a_selection() {
+        let theme = theme::current();
+        let rect = Rect::from_xywh(10.0, 10.0, 200.0, 48.0);
+        let mut radio = RadioGroup::new(&["Low", "High"]);
+        radio.layout(rect);
+        // Press on the first option and release outside, which focuses the
+        // group without choosing anything.
+        radio.on_event(&press(20.0, 22.0));
+        radio.on_event(&Event::MouseUp {
+            x: 220.0,
+            y: 70.0,
+            button: MouseButton::Left,
+            modifiers: ModifiersState::empty(),
+        });
+        assert_eq!(radio.selected(), None);
+
+        let mut backend = render(&mut radio, rect);
+        assert_eq!(backend.pixel(8, 22), Some(theme.accent));
+        // Only one option gets the ring.
+        assert_eq!(backend.pixel(8, 46), Some(Color::WHITE));
+    }
+}
@@ -0,0 +1,498 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::{Event, ScrollDelta};
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, Point, Rect};
+use std::time::Instant;
+use winit::event::{MouseButton, TouchPhase};
+
+pub const SCROLLBAR_SIZE: f32 = 10.0;
+// How far one wheel notch scrolls.
+pub const LINE_HEIGHT: f32 = 40.0;
+const MIN_THUMB: f32 = 24.0;
+// Fraction of velocity kept per second of kinetic scrolling.
+const FRICTION: f32 = 0.05;
+const MIN_VELOCITY: f32 = 20.0;
+const OVERSCROLL_RESISTANCE: f32 = 0.4;
+const SPRING_BACK: f32 = 12.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+// A draggable scrollbar along one edge. It doesn't own the scroll offset;
+// the widget using it passes its offset in and gets the new one back.
+#[derive(Debug, Clone)]
+pub struct Scrollbar {
+    axis: Axis,
+    track: Rect,
+    viewport: f32,
+    content: f32,
+    hovered: bool,
+    // Pointer position and offset when the drag began.
+    drag: Option<(f32, f32)>,
+}
+
+impl Scrollbar {
+    pub fn new(axis: Axis) -> Self {
+        Self {
+            axis,
+            track: Rect::default(),
+            viewport: 0.0,
+            content: 0.0,
+            hovered: false,
+            drag: None,
+        }
+    }
+
+    pub fn update(&mut self, track: Rect, viewport: f32, content: f32) {
+        self.track = track;
+        self.viewport = viewport;
+        self.content = content;
+    }
+
+    pub fn is_needed(&self) -> bool {
+        self.content > self.viewport + 0.5
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    pub fn max_offset(&self) -> f32 {
+        (self.content - self.viewport).max(0.0)
+    }
+
+    fn track_length(&self) -> f32 {
+        match self.axis {
+            Axis::Horizontal => self.track.width(),
+            Axis::Vertical => self.track.height(),
+        }
+    }
+
+    fn thumb_length(&self) -> f32 {
+        if self.content <= 0.0 {
+            return self.track_length();
+        }
+        (self.track_length() * self.viewport / self.content).clamp(MIN_THUMB.min(self.track_length()), self.track_length())
+    }
+
+    pub fn thumb_rect(&self, offset: f32) -> Rect {
+        let travel = self.track_length() - self.thumb_length();
+        let max = self.max_offset();
+        let start = if max > 0.0 { travel * (offset.clamp(0.0, max) / max) } else { 0.0 };
+        match self.axis {
+            Axis::Horizontal => Rect::from_xywh(self.track.left + start, self.track.top, self.thumb_length(), self.track.height()),
+            Axis::Vertical => Rect::from_xywh(self.track.left, self.track.top + start, self.track.width(), self.thumb_length()),
+        }
+    }
+
+    fn along(&self, x: f32, y: f32) -> f32 {
+        match self.axis {
+            Axis::Horizontal => x,
+            Axis::Vertical => y,
+        }
+    }
+
+    // Handles thumb drags and clicks on the track, which page towards the
+    // pointer. Returns true if the event was used.
+    pub fn on_event(&mut self, event: &Event, offset: &mut f32) -> bool {
+        if !self.is_needed() {
+            return false;
+        }
+
+        match event {
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => {
+                if !self.track.contains(Point::new(*x, *y)) {
+                    return false;
+                }
+                let thumb = self.thumb_rect(*offset);
+                if thumb.contains(Point::new(*x, *y)) {
+                    self.drag = Some((self.along(*x, *y), *offset));
+                } else {
+                    let before = self.along(*x, *y) < self.along(thumb.left, thumb.top);
+                    let page = if before { -self.viewport } else { self.viewport };
+                    *offset = (*offset + page).clamp(0.0, self.max_offset());
+                }
+                true
+            }
+            Event::MouseMove { x, y, .. } => {
+                self.hovered = self.track.contains(Point::new(*x, *y));
+                if let Some((start, start_offset)) = self.drag {
+                    let travel = self.track_length() - self.thumb_length();
+                    if travel > 0.0 {
+                        let delta = (self.along(*x, *y) - start) / travel * self.max_offset();
+                        *offset = (start_offset + delta).clamp(0.0, self.max_offset());
+                    }
+                    return true;
+                }
+                false
+            }
+            Event::MouseUp { button: MouseButton::Left, .. } => self.drag.take().is_some(),
+            _ => false,
+        }
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas, offset: f32) {
+        if !self.is_needed() {
+            return;
+        }
+        let theme = theme::current();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(theme.surface);
+        canvas.draw_rect(self.track, &paint);
+
+        paint.set_color(if self.drag.is_some() || self.hovered {
+            theme.border
+        } else {
+            theme.surface_pressed
+        });
+        let thumb = self.thumb_rect(offset).with_inset((2.0, 2.0));
+        let radius = thumb.width().min(thumb.height()) / 2.0;
+        canvas.draw_round_rect(thumb, radius, radius, &paint);
+    }
+}
+
+// Pixels to scroll for a wheel/touchpad delta, in offset direction.
+pub fn scroll_amount(delta: ScrollDelta) -> (f32, f32) {
+    match delta {
+        ScrollDelta::Lines { x, y } => (-x * LINE_HEIGHT, -y * LINE_HEIGHT),
+        ScrollDelta::Pixels { x, y } => (-x, -y),
+    }
+}
+
+// Clips a single child to its viewport and scrolls it. The child is laid out
+// at its full content size, shifted by the scroll offset.
+pub struct ScrollView {
+    child: Box<dyn Widget>,
+    content_size: Option<(f32, f32)>,
+    offset: (f32, f32),
+    rect: Rect,
+    viewport: Rect,
+    horizontal: Scrollbar,
+    vertical: Scrollbar,
+    kinetic: bool,
+    overscroll: bool,
+    velocity: (f32, f32),
+    touching: bool,
+    last_tick: Option<Instant>,
+}
+
+impl ScrollView {
+    pub fn new(child: Box<dyn Widget>) -> Self {
+        Self {
+            child,
+            content_size: None,
+            offset: (0.0, 0.0),
+            rect: Rect::default(),
+            viewport: Rect::default(),
+            horizontal: Scrollbar::new(Axis::Horizontal),
+            vertical: Scrollbar::new(Axis::Vertical),
+            kinetic: false,
+            overscroll: false,
+            velocity: (0.0, 0.0),
+            touching: false,
+            last_tick: None,
+        }
+    }
+
+    // Overrides the child's preferred size.
+    pub fn with_content_size(mut self, width: f32, height: f32) -> Self {
+        self.content_size = Some((width, height));
+        self
+    }
+
+    // Keep scrolling after a touchpad flick.
+    pub fn with_kinetic(mut self, kinetic: bool) -> Self {
+        self.kinetic = kinetic;
+        self
+    }
+
+    // Let touchpad scrolling pull past the edges and spring back.
+    pub fn with_overscroll(mut self, overscroll: bool) -> Self {
+        self.overscroll = overscroll;
+        self
+    }
+
+    pub fn child(&self) -> &dyn Widget {
+        self.child.as_ref()
+    }
+
+    pub fn child_mut(&mut self) -> &mut dyn Widget {
+        self.child.as_mut()
+    }
+
+    pub fn offset(&self) -> (f32, f32) {
+        self.offset
+    }
+
+    pub fn set_offset(&mut self, x: f32, y: f32) {
+        self.offset = (
+            x.clamp(0.0, self.horizontal.max_offset()),
+            y.clamp(0.0, self.vertical.max_offset()),
+        );
+        self.velocity = (0.0, 0.0);
+        self.layout_child();
+    }
+
+    // Scrolls the least amount needed to show `rect`, given in content
+    // coordinates (0,0 is the top-left of the child).
+    pub fn scroll_to(&mut self, rect: Rect) {
+        let (mut x, mut y) = self.offset;
+        let (width, height) = (self.viewport.width(), self.viewport.height());
+
+        if rect.right > x + width {
+            x = rect.right - width;
+        }
+        if rect.left < x {
+            x = rect.left;
+        }
+        if rect.bottom > y + height {
+            y = rect.bottom - height;
+        }
+        if rect.top < y {
+            y = rect.top;
+        }
+        self.set_offset(x, y);
+    }
+
+    fn content_size(&self) -> (f32, f32) {
+        let (width, height) = self.content_size.unwrap_or_else(|| self.child.preferred_size());
+        (width.max(self.viewport.width()), height.max(self.viewport.height()))
+    }
+
+    fn child_rect(&self) -> Rect {
+        let (width, height) = self.content_size();
+        Rect::from_xywh(
+            self.viewport.left - self.offset.0,
+            self.viewport.top - self.offset.1,
+            width,
+            height,
+        )
+    }
+
+    fn layout_child(&mut self) {
+        let rect = self.child_rect();
+        self.child.layout(rect);
+    }
+
+    fn clamp_offset(&mut self) {
+        self.offset.0 = self.offset.0.clamp(0.0, self.horizontal.max_offset());
+        self.offset.1 = self.offset.1.clamp(0.0, self.vertical.max_offset());
+    }
+
+    // Offset with rubber-banding when overscroll is on.
+    fn scroll_by(&mut self, dx: f32, dy: f32) {
+        let max = (self.horizontal.max_offset(), self.vertical.max_offset());
+        for (offset, delta, max) in [(&mut self.offset.0, dx, max.0), (&mut self.offset.1, dy, max.1)] {
+            let outside = *offset < 0.0 || *offset > max;
+            *offset += if self.overscroll && outside { delta * OVERSCROLL_RESISTANCE } else { delta };
+        }
+        if !self.overscroll || !self.touching {
+            self.clamp_offset();
+        }
+    }
+
+    fn is_overscrolled(&self) -> bool {
+        self.offset.0 < 0.0
+            || self.offset.1 < 0.0
+            || self.offset.0 > self.horizontal.max_offset()
+            || self.offset.1 > self.vertical.max_offset()
+    }
+
+    // Advances kinetic scrolling and spring-back. Returns true while there is
+    // still motion left.
+    fn tick(&mut self) -> bool {
+        let now = Instant::now();
+        let dt = self.last_tick.map(|t| (now - t).as_secs_f32()).unwrap_or(0.0).min(0.05);
+        self.last_tick = Some(now);
+        if self.touching {
+            return false;
+        }
+
+        let mut moving = false;
+        if self.velocity.0.abs() > MIN_VELOCITY || self.velocity.1.abs() > MIN_VELOCITY {
+            self.scroll_by(self.velocity.0 * dt, self.velocity.1 * dt);
+            let decay = FRICTION.powf(dt);
+            self.velocity = (self.velocity.0 * decay, self.velocity.1 * decay);
+            moving = true;
+        } else {
+            self.velocity = (0.0, 0.0);
+        }
+
+        if self.overscroll && self.is_overscrolled() {
+            let target = (
+                self.offset.0.clamp(0.0, self.horizontal.max_offset()),
+                self.offset.1.clamp(0.0, self.vertical.max_offset()),
+            );
+            let step = (SPRING_BACK * dt).min(1.0);
+            self.offset.0 += (target.0 - self.offset.0) * step;
+            self.offset.1 += (target.1 - self.offset.1) * step;
+            if (target.0 - self.offset.0).abs() < 0.5 && (target.1 - self.offset.1).abs() < 0.5 {
+                self.offset = target;
+            } else {
+                moving = true;
+            }
+        }
+
+        if moving {
+            self.layout_child();
+        } else {
+            self.last_tick = None;
+        }
+        moving
+    }
+
+    fn on_scroll(&mut self, delta: ScrollDelta, phase: TouchPhase, shift: bool) {
+        let (mut dx, mut dy) = scroll_amount(delta);
+        if shift && dx == 0.0 {
+            // Shift+wheel scrolls sideways.
+            std::mem::swap(&mut dx, &mut dy);
+        }
+
+        match phase {
+            TouchPhase::Started => {
+                self.touching = true;
+                self.velocity = (0.0, 0.0);
+            }
+            TouchPhase::Moved => {
+                if let (ScrollDelta::Pixels { .. }, Some(last)) = (delta, self.last_tick) {
+                    let dt = last.elapsed().as_secs_f32().max(0.001);
+                    self.velocity = (dx / dt, dy / dt);
+                }
+                self.last_tick = Some(Instant::now());
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.touching = false;
+                if !self.kinetic {
+                    self.velocity = (0.0, 0.0);
+                }
+                self.last_tick = Some(Instant::now());
+                context::request_animation_frame();
+            }
+        }
+
+        self.scroll_by(dx, dy);
+        self.layout_child();
+    }
+}
+
+impl Widget for ScrollView {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        if self.tick() {
+            context::request_animation_frame();
+        }
+
+        canvas.save();
+        canvas.clip_rect(self.viewport, None, Some(true));
+        self.child.draw(canvas);
+        canvas.restore();
+
+        self.horizontal.draw(canvas, self.offset.0);
+        self.vertical.draw(canvas, self.offset.1);
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        // Scrollbars first, so a drag that leaves the bar keeps going.
+        let (mut x, mut y) = self.offset;
+        if self.vertical.on_event(event, &mut y) || self.horizontal.on_event(event, &mut x) {
+            if (x, y) != self.offset {
+                self.set_offset(x, y);
+            }
+            return true;
+        }
+
+        match event {
+            Event::Scroll { x, y, delta, phase, modifiers } => {
+                if !self.viewport.contains(Point::new(*x, *y)) {
+                    return false;
+                }
+                // Nested scrollables get the first go.
+                if self.child.on_event(event) {
+                    return true;
+                }
+                self.on_scroll(*delta, *phase, modifiers.shift());
+                true
+            }
+            Event::ScrollIntoView(rect) => {
+                self.child.on_event(event);
+                if rect.intersects(self.child_rect()) {
+                    let child = self.child_rect();
+                    let mut local = *rect;
+                    local.offset((-child.left, -child.top));
+                    self.scroll_to(local);
+                }
+                false
+            }
+            Event::MouseDown { x, y, .. } | Event::MouseMove { x, y, .. } => {
+                // Nothing outside the viewport is visible, so don't let the
+                // child react to it.
+                if self.viewport.contains(Point::new(*x, *y)) {
+                    self.child.on_event(event)
+                } else {
+                    false
+                }
+            }
+            _ => self.child.on_event(event),
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.viewport = rect;
+
+        // Showing one scrollbar can make room too tight for the other axis.
+        let (width, height) = self.content_size.unwrap_or_else(|| self.child.preferred_size());
+        let mut need_v = height > rect.height();
+        let need_h = width > rect.width() - if need_v { SCROLLBAR_SIZE } else { 0.0 };
+        if need_h && !need_v {
+            need_v = height > rect.height() - SCROLLBAR_SIZE;
+        }
+        if need_v {
+            self.viewport.right -= SCROLLBAR_SIZE;
+        }
+        if need_h {
+            self.viewport.bottom -= SCROLLBAR_SIZE;
+        }
+
+        let (content_w, content_h) = self.content_size();
+        self.vertical.update(
+            Rect::from_ltrb(self.viewport.right, rect.top, rect.right, self.viewport.bottom),
+            self.viewport.height(),
+            content_h,
+        );
+        self.horizontal.update(
+            Rect::from_ltrb(rect.left, self.viewport.bottom, self.viewport.right, rect.bottom),
+            self.viewport.width(),
+            content_w,
+        );
+
+        self.clamp_offset();
+        self.layout_child();
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn visit_children(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        f(self.child.as_ref());
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("offset", format!("{:?}", self.offset)),
+            ("content", format!("{:?}", self.content_size())),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        self.child.preferred_size()
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use std::collections::BTreeSet;
+use winit::event::ModifiersState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    None,
+    Single,
+    Multiple,
+}
+
+// Row selection shared by the list, table and tree widgets. `anchor` is where
+// a Shift range starts; `focus` is the row the keyboard is on.
+#[derive(Debug, Clone)]
+pub struct Selection {
+    mode: SelectionMode,
+    selected: BTreeSet<usize>,
+    anchor: Option<usize>,
+    focus: Option<usize>,
+}
+
+impl Selection {
+    pub fn new(mode: SelectionMode) -> Self {
+        Self {
+            mode,
+            selected: BTreeSet::new(),
+            anchor: None,
+            focus: None,
+        }
+    }
+
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: SelectionMode) {
+        self.mode = mode;
+        self.clear();
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    pub fn selected(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selected.iter().copied()
+    }
+
+    pub fn focus(&self) -> Option<usize> {
+        self.focus
+    }
+
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+        self.focus = None;
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if self.mode == SelectionMode::None {
+            return;
+        }
+        self.selected.clear();
+        self.selected.insert(index);
+        self.anchor = Some(index);
+        self.focus = Some(index);
+    }
+
+    pub fn select_all(&mut self, len: usize) {
+        if self.mode == SelectionMode::Multiple {
+            self.selected = (0..len).collect();
+        }
+    }
+
+    // Click handling: plain click selects one row, Ctrl toggles, Shift
+    // extends from the anchor. Returns true if the selection changed.
+    pub fn click(&mut self, index: usize, modifiers: ModifiersState) -> bool {
+        let before = self.selected.clone();
+        match self.mode {
+            SelectionMode::None => return false,
+            SelectionMode::Single => self.select(index),
+            SelectionMode::Multiple => {
+                if modifiers.shift() {
+                    self.extend_to(index, modifiers.ctrl());
+                } else if modifiers.ctrl() || modifiers.logo() {
+                    if !self.selected.remove(&index) {
+                        self.selected.insert(index);
+                    }
+                    self.anchor = Some(index);
+                    self.focus = Some(index);
+                } else {
+                    self.select(index);
+                }
+            }
+        }
+        before != self.selected
+    }
+
+    // Keyboard movement. With Shift held (and multiple selection) the range
+    // from the anchor grows; otherwise the focused row becomes the selection.
+    pub fn move_focus(&mut self, index: usize, extend: bool) -> bool {
+        let before = self.selected.clone();
+        if extend && self.mode == SelectionMode::Multiple {
+            self.extend_to(index, false);
+        } else {
+            self.select(index);
+        }
+        before != self.selected
+    }
+
+    fn extend_to(&mut self, index: usize, keep: bool) {
+        let anchor = self.anchor.unwrap_or(index);
+        if !keep {
+            self.selected.clear();
+        }
+        let (start, end) = if anchor <= index { (anchor, index) } else { (index, anchor) };
+        self.selected.extend(start..=end);
+        self.anchor = Some(anchor);
+        self.focus = Some(index);
+    }
+
+    // Moves the selection to new row indices after rows were inserted or
+    // removed. Rows mapped to None are dropped.
+    pub fn remap<F: Fn(usize) -> Option<usize>>(&mut self, f: F) {
+        self.selected = self.selected.iter().filter_map(|i| f(*i)).collect();
+        self.anchor = self.anchor.and_then(&f);
+        self.focus = self.focus.and_then(&f);
+    }
+
+    // Drops rows at or past `len`, after the data shrinks.
+    pub fn truncate(&mut self, len: usize) {
+        self.selected.retain(|i| *i < len);
+        self.anchor = self.anchor.filter(|i| *i < len);
+        self.focus = self.focus.filter(|i| *i < len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selected(selection: &Selection) -> Vec<usize> {
+        selection.selected().collect()
+    }
+
+    #[test]
+    fn plain_click_replaces_the_selection() {
+        let mut selection = Selection::new(SelectionMode::Multiple);
+        assert!(selection.click(2, ModifiersState::empty()));
+        assert!(selection.click(5, ModifiersState::empty()));
+        assert_eq!(selected(&selection), vec![5]);
+        assert!(!selection.click(5, ModifiersState::empty()));
+    }
+
+    #[test]
+    fn ctrl_click_toggles_and_shift_click_extends() {
+        let mut selection = Selection::new(SelectionMode::Multiple);
+        selection.click(2, ModifiersState::empty());
+        selection.click(4, ModifiersState::CTRL);
+        assert_eq!(selected(&selection), vec![2, 4]);
+        selection.click(2, ModifiersState::CTRL);
+        assert_eq!(selected(&selection), vec![4]);
+        // The Ctrl click on 2 moved the anchor there.
+        selection.click(7, ModifiersState::SHIFT);
+        assert_eq!(selected(&selection), vec![2, 3, 4, 5, 6, 7]);
+        assert_eq!(selection.focus(), Some(7));
+    }
+
+    #[test]
+    fn single_mode_ignores_modifiers() {
+        let mut selection = Selection::new(SelectionMode::Single);
+        selection.click(1, ModifiersState::empty());
+        selection.click(3, ModifiersState::SHIFT);
+        assert_eq!(selected(&selection), vec![3]);
+        selection.select_all(10);
+        assert_eq!(selected(&selection), vec![3]);
+    }
+
+    #[test]
+    fn none_mode_selects_nothing() {
+        let mut selection = Selection::new(SelectionMode::None);
+        assert!(!selection.click(1, ModifiersState::empty()));
+        assert!(!selection.move_focus(2, false));
+        assert_eq!(selection.focus(), None);
+    }
+
+    #[test]
+    fn shift_movement_grows_from_the_anchor() {
+        let mut selection = Selection::new(SelectionMode::Multiple);
+        selection.move_focus(5, false);
+        selection.move_focus(3, true);
+        assert_eq!(selected(&selection), vec![3, 4, 5]);
+        selection.move_focus(6, true);
+        assert_eq!(selected(&selection), vec![5, 6]);
+    }
+
+    #[test]
+    fn remap_moves_rows_and_drops_unmapped_ones() {
+        let mut selection = Selection::new(SelectionMode::Multiple);
+        selection.click(1, ModifiersState::empty());
+        selection.click(3, ModifiersState::CTRL);
+        selection.remap(|i| if i == 1 { None } else { Some(i + 10) });
+        assert_eq!(selected(&selection), vec![13]);
+        assert_eq!(selection.focus(), Some(13));
+    }
+
+    #[test]
+    fn truncate_drops_rows_past_the_end() {
+        let mut selection = Selection::new(SelectionMode::Multiple);
+        selection.select(0);
+        selection.move_focus(5, true);
+        selection.truncate(4);
+        assert_eq!(selected(&selection), vec![0, 1, 2, 3]);
+        assert_eq!(selection.focus(), None);
+    }
+}
@@ -0,0 +1,312 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::scroll::Axis;
+use crate::widget::{theme, Widget};
+use skia_safe::paint::Cap;
+use skia_safe::{Canvas, Paint, PaintStyle, Point, Rect};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const TRACK_THICKNESS: f32 = 4.0;
+const THUMB_RADIUS: f32 = 8.0;
+const TICK_LENGTH: f32 = 4.0;
+// Stepped sliders with more steps than this don't draw tick marks.
+const MAX_TICKS: f64 = 50.0;
+// Continuous sliders move by this fraction of the range per arrow key.
+const KEY_FRACTION: f64 = 0.01;
+const PAGE_STEPS: f64 = 10.0;
+
+pub struct Slider {
+    value: f64,
+    min: f64,
+    max: f64,
+    // None for a continuous slider.
+    step: Option<f64>,
+    axis: Axis,
+    enabled: bool,
+    hovered: bool,
+    dragging: bool,
+    focused: bool,
+    rect: Rect,
+    on_changed: Option<Box<dyn FnMut(f64)>>,
+}
+
+impl Slider {
+    pub fn new(min: f64, max: f64, value: f64) -> Self {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        Self {
+            value: value.clamp(min, max),
+            min,
+            max,
+            step: None,
+            axis: Axis::Horizontal,
+            enabled: true,
+            hovered: false,
+            dragging: false,
+            focused: false,
+            rect: Rect::default(),
+            on_changed: None,
+        }
+    }
+
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = (step > 0.0).then_some(step);
+        self.value = self.snap(self.value);
+        self
+    }
+
+    pub fn vertical(mut self) -> Self {
+        self.axis = Axis::Vertical;
+        self
+    }
+
+    pub fn on_changed<F: FnMut(f64) + 'static>(mut self, f: F) -> Self {
+        self.on_changed = Some(Box::new(f));
+        self
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn set_value(&mut self, value: f64) {
+        self.value = self.snap(value);
+        context::request_redraw();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.hovered = false;
+            self.dragging = false;
+            self.focused = false;
+        }
+        context::request_redraw();
+    }
+
+    fn snap(&self, value: f64) -> f64 {
+        let value = value.clamp(self.min, self.max);
+        match self.step {
+            Some(step) => (self.min + ((value - self.min) / step).round() * step).min(self.max),
+            None => value,
+        }
+    }
+
+    fn change(&mut self, value: f64) {
+        let value = self.snap(value);
+        if value == self.value {
+            return;
+        }
+        self.value = value;
+        if let Some(on_changed) = &mut self.on_changed {
+            on_changed(value);
+        }
+        context::request_redraw();
+    }
+
+    fn key_step(&self) -> f64 {
+        self.step.unwrap_or((self.max - self.min) * KEY_FRACTION)
+    }
+
+    // The thumb travels between these, inset so it never overhangs the rect.
+    fn track(&self) -> (Point, Point) {
+        match self.axis {
+            Axis::Horizontal => (
+                Point::new(self.rect.left + THUMB_RADIUS, self.rect.center_y()),
+                Point::new(self.rect.right - THUMB_RADIUS, self.rect.center_y()),
+            ),
+            // Minimum at the bottom.
+            Axis::Vertical => (
+                Point::new(self.rect.center_x(), self.rect.bottom - THUMB_RADIUS),
+                Point::new(self.rect.center_x(), self.rect.top + THUMB_RADIUS),
+            ),
+        }
+    }
+
+    fn fraction(&self, value: f64) -> f32 {
+        if self.max > self.min {
+            ((value - self.min) / (self.max - self.min)) as f32
+        } else {
+            0.0
+        }
+    }
+
+    fn point_for(&self, value: f64) -> Point {
+        let (start, end) = self.track();
+        let t = self.fraction(value);
+        Point::new(start.x + (end.x - start.x) * t, start.y + (end.y - start.y) * t)
+    }
+
+    fn value_at(&self, x: f32, y: f32) -> f64 {
+        let (start, end) = self.track();
+        let t = match self.axis {
+            Axis::Horizontal if end.x > start.x => (x - start.x) / (end.x - start.x),
+            Axis::Vertical if start.y > end.y => (start.y - y) / (start.y - end.y),
+            _ => 0.0,
+        };
+        self.min + (self.max - self.min) * t.clamp(0.0, 1.0) as f64
+    }
+}
+
+impl Widget for Slider {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let (start, end) = self.track();
+        let thumb = self.point_for(self.value);
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(TRACK_THICKNESS);
+        paint.set_stroke_cap(Cap::Round);
+        paint.set_color(theme.surface_pressed);
+        canvas.draw_line(start, end, &paint);
+        paint.set_color(if self.enabled { theme.accent } else { theme.disabled_foreground });
+        canvas.draw_line(start, thumb, &paint);
+
+        if let Some(step) = self.step {
+            let steps = ((self.max - self.min) / step).floor();
+            if steps <= MAX_TICKS {
+                paint.set_stroke_width(1.0);
+                paint.set_color(theme.border);
+                let offset = THUMB_RADIUS + 2.0;
+                for i in 0..=steps as usize {
+                    let p = self.point_for(self.min + i as f64 * step);
+                    match self.axis {
+                        Axis::Horizontal => {
+                            canvas.draw_line((p.x, p.y + offset), (p.x, p.y + offset + TICK_LENGTH), &paint)
+                        }
+                        Axis::Vertical => {
+                            canvas.draw_line((p.x + offset, p.y), (p.x + offset + TICK_LENGTH, p.y), &paint)
+                        }
+                    };
+                }
+            }
+        }
+
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(if !self.enabled {
+            theme.surface
+        } else if self.dragging {
+            theme.surface_pressed
+        } else if self.hovered {
+            theme.surface_hover
+        } else {
+            theme.background
+        });
+        canvas.draw_circle(thumb, THUMB_RADIUS, &paint);
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+        paint.set_color(theme.border);
+        canvas.draw_circle(thumb, THUMB_RADIUS, &paint);
+
+        if self.focused {
+            let bounds = Rect::from_xywh(thumb.x - THUMB_RADIUS, thumb.y - THUMB_RADIUS, THUMB_RADIUS * 2.0, THUMB_RADIUS * 2.0);
+            theme::draw_focus_ring(canvas, bounds, THUMB_RADIUS);
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                if self.dragging {
+                    self.change(self.value_at(*x, *y));
+                    return true;
+                }
+                let hovered = self.rect.contains(Point::new(*x, *y));
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    context::request_redraw();
+                }
+                false
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => {
+                self.focused = self.rect.contains(Point::new(*x, *y));
+                if self.focused {
+                    self.dragging = true;
+                    self.change(self.value_at(*x, *y));
+                }
+                self.focused
+            }
+            Event::MouseUp { button: MouseButton::Left, .. } if self.dragging => {
+                self.dragging = false;
+                context::request_redraw();
+                true
+            }
+            Event::KeyDown { key: Some(key), .. } if self.focused => {
+                let step = self.key_step();
+                let value = match key {
+                    VirtualKeyCode::Left | VirtualKeyCode::Down => self.value - step,
+                    VirtualKeyCode::Right | VirtualKeyCode::Up => self.value + step,
+                    VirtualKeyCode::PageDown => self.value - step * PAGE_STEPS,
+                    VirtualKeyCode::PageUp => self.value + step * PAGE_STEPS,
+                    VirtualKeyCode::Home => self.min,
+                    VirtualKeyCode::End => self.max,
+                    _ => return false,
+                };
+                self.change(value);
+                true
+            }
+            Event::FocusLost => {
+                self.focused = false;
+                self.dragging = false;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("value", self.value.to_string()),
+            ("range", format!("{}..={}", self.min, self.max)),
+            ("enabled", self.enabled.to_string()),
+        ]
+    }
+
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("axis", format!("{:?}", self.axis)),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let thickness = (THUMB_RADIUS + 2.0 + TICK_LENGTH) * 2.0;
+        match self.axis {
+            Axis::Horizontal => (160.0, thickness),
+            Axis::Vertical => (thickness, 160.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::testing::render;
+
+    #[test]
+    fn slider_track_is_filled_up_to_the_thumb() {
+        let theme = theme::current();
+        let mut slider = Slider::new(0.0, 1.0, 0.5);
+        let mut backend = render(&mut slider, Rect::from_xywh(0.0, 10.0, 200.0, 24.0));
+        assert_eq!(backend.pixel(50, 22), Some(theme.accent));
+        assert_eq!(backend.pixel(150, 22), Some(theme.surface_pressed));
+    }
+}
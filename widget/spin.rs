@@ -0,0 +1,576 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::scroll::scroll_amount;
+use crate::widget::textedit::TextBuffer;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Path, Point, RRect, Rect};
+use std::time::{Duration, Instant};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const HEIGHT: f32 = 28.0;
+const BUTTON_WIDTH: f32 = 20.0;
+const PADDING: f32 = 6.0;
+const PAGE_STEPS: f64 = 10.0;
+// Holding a step button repeats after the delay, once per interval.
+const REPEAT_DELAY: Duration = Duration::from_millis(400);
+const REPEAT_INTERVAL: Duration = Duration::from_millis(50);
+
+// Converts between a spin box's value and the text in its field. Values are
+// always f64 underneath; the format decides how they read.
+pub trait ValueFormat {
+    fn format(&self, value: f64) -> String;
+    fn parse(&self, text: &str) -> Option<f64>;
+}
+
+// Plain decimal number with a fixed number of places and an optional units
+// suffix such as " mm".
+#[derive(Debug, Clone)]
+pub struct DecimalFormat {
+    pub precision: usize,
+    pub suffix: String,
+}
+
+impl ValueFormat for DecimalFormat {
+    fn format(&self, value: f64) -> String {
+        format!("{:.*}{}", self.precision, value, self.suffix)
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        let text = text.trim();
+        let text = text.strip_suffix(self.suffix.trim()).unwrap_or(text);
+        text.trim().parse().ok().filter(|value: &f64| value.is_finite())
+    }
+}
+
+// Whole numbers shown as "0x1F", padded to `digits`. Negative values keep
+// their sign in front, as "-0x1F".
+#[derive(Debug, Clone)]
+pub struct HexFormat {
+    pub digits: usize,
+}
+
+impl ValueFormat for HexFormat {
+    fn format(&self, value: f64) -> String {
+        let n = value.round() as i64;
+        let sign = if n < 0 { "-" } else { "" };
+        format!("{}0x{:0width$X}", sign, n.unsigned_abs(), width = self.digits)
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        let text = text.trim();
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let digits = text
+            .strip_prefix("0x")
+            .or_else(|| text.strip_prefix("0X"))
+            .unwrap_or(text);
+        // from_str_radix would take a second sign here.
+        if digits.starts_with(['+', '-']) {
+            return None;
+        }
+        let n = u64::from_str_radix(digits, 16).ok()? as f64;
+        Some(if negative { -n } else { n })
+    }
+}
+
+// A number of seconds shown as "1:02:03" (or "2:03" under an hour). Accepts
+// that form, plain seconds, or units like "1h 30m 5s".
+#[derive(Debug, Clone, Default)]
+pub struct DurationFormat;
+
+impl ValueFormat for DurationFormat {
+    fn format(&self, value: f64) -> String {
+        let total = value.max(0.0).round() as u64;
+        let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+        if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        }
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        self.parse_seconds(text.trim()).filter(|seconds| seconds.is_finite())
+    }
+}
+
+impl DurationFormat {
+    fn parse_seconds(&self, text: &str) -> Option<f64> {
+        if text.contains(':') {
+            let parts: Vec<&str> = text.split(':').collect();
+            if parts.len() > 3 {
+                return None;
+            }
+            return parts
+                .iter()
+                .try_fold(0.0, |total, part| part.trim().parse::<f64>().ok().map(|n| total * 60.0 + n));
+        }
+        if let Ok(seconds) = text.parse::<f64>() {
+            return Some(seconds);
+        }
+
+        let mut total = 0.0;
+        let mut number = String::new();
+        for c in text.chars() {
+            match c {
+                '0'..='9' | '.' => number.push(c),
+                'h' | 'm' | 's' => {
+                    let n: f64 = number.parse().ok()?;
+                    number.clear();
+                    total += n * match c {
+                        'h' => 3600.0,
+                        'm' => 60.0,
+                        _ => 1.0,
+                    };
+                }
+                c if c.is_whitespace() => {}
+                _ => return None,
+            }
+        }
+        number.is_empty().then_some(total)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpinButton {
+    Up,
+    Down,
+}
+
+pub struct SpinBox {
+    value: f64,
+    min: f64,
+    max: f64,
+    step: f64,
+    format: Box<dyn ValueFormat>,
+    // Settings behind `with_precision` and `with_suffix`.
+    decimal: DecimalFormat,
+    text: TextBuffer,
+    // The field holds typed text that hasn't been committed yet.
+    editing: bool,
+    enabled: bool,
+    hovered: bool,
+    hovered_button: Option<SpinButton>,
+    // Held button and when it next repeats.
+    pressed: Option<(SpinButton, Instant)>,
+    focused: bool,
+    rect: Rect,
+    on_changed: Option<Box<dyn FnMut(f64)>>,
+}
+
+impl SpinBox {
+    pub fn new(min: f64, max: f64, value: f64) -> Self {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        let decimal = DecimalFormat { precision: 0, suffix: String::new() };
+        let mut spin = Self {
+            value: value.clamp(min, max),
+            min,
+            max,
+            step: 1.0,
+            format: Box::new(decimal.clone()),
+            decimal,
+            text: TextBuffer::default(),
+            editing: false,
+            enabled: true,
+            hovered: false,
+            hovered_button: None,
+            pressed: None,
+            focused: false,
+            rect: Rect::default(),
+            on_changed: None,
+        };
+        spin.reset_text();
+        spin
+    }
+
+    pub fn with_step(mut self, step: f64) -> Self {
+        if step > 0.0 {
+            self.step = step;
+        }
+        self
+    }
+
+    // Decimal places and units suffix for the default decimal format.
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.decimal.precision = precision;
+        let decimal = self.decimal.clone();
+        self.with_format(decimal)
+    }
+
+    pub fn with_suffix(mut self, suffix: &str) -> Self {
+        self.decimal.suffix = suffix.to_string();
+        let decimal = self.decimal.clone();
+        self.with_format(decimal)
+    }
+
+    pub fn with_format<F: ValueFormat + 'static>(mut self, format: F) -> Self {
+        self.format = Box::new(format);
+        self.reset_text();
+        self
+    }
+
+    pub fn on_changed<F: FnMut(f64) + 'static>(mut self, f: F) -> Self {
+        self.on_changed = Some(Box::new(f));
+        self
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value.clamp(self.min, self.max);
+        self.reset_text();
+        context::request_redraw();
+    }
+
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        self.min = min.min(max);
+        self.max = max.max(min);
+        self.set_value(self.value);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.commit();
+            self.hovered = false;
+            self.hovered_button = None;
+            self.pressed = None;
+            self.focused = false;
+        }
+        self.enabled = enabled;
+        context::request_redraw();
+    }
+
+    fn reset_text(&mut self) {
+        self.text.set_text(&self.format.format(self.value));
+        self.editing = false;
+    }
+
+    fn change(&mut self, value: f64) {
+        let value = value.clamp(self.min, self.max);
+        let changed = value != self.value;
+        self.value = value;
+        self.reset_text();
+        if changed {
+            if let Some(on_changed) = &mut self.on_changed {
+                on_changed(value);
+            }
+        }
+        context::request_redraw();
+    }
+
+    // Takes the typed text as the new value, or puts the old one back if it
+    // doesn't parse.
+    fn commit(&mut self) {
+        if !self.editing {
+            return;
+        }
+        match self.format.parse(self.text.text()) {
+            Some(value) => self.change(value),
+            None => {
+                self.reset_text();
+                context::request_redraw();
+            }
+        }
+    }
+
+    // Moves by `steps` steps, landing on the step grid that starts at `min`.
+    fn step_by(&mut self, steps: f64) {
+        self.commit();
+        let position = ((self.value - self.min) / self.step).round() + steps;
+        self.change(self.min + position * self.step);
+    }
+
+    fn button_rect(&self, button: SpinButton) -> Rect {
+        let half = self.rect.height() / 2.0;
+        let top = match button {
+            SpinButton::Up => self.rect.top,
+            SpinButton::Down => self.rect.top + half,
+        };
+        Rect::from_xywh(self.rect.right - BUTTON_WIDTH, top, BUTTON_WIDTH, half)
+    }
+
+    fn button_at(&self, x: f32, y: f32) -> Option<SpinButton> {
+        [SpinButton::Up, SpinButton::Down]
+            .into_iter()
+            .find(|b| self.button_rect(*b).contains(Point::new(x, y)))
+    }
+
+    fn repeat(&mut self) {
+        let (button, next) = match self.pressed {
+            Some(pressed) => pressed,
+            None => return,
+        };
+        let now = Instant::now();
+        if now >= next {
+            self.step_by(if button == SpinButton::Up { 1.0 } else { -1.0 });
+            self.pressed = Some((button, now + REPEAT_INTERVAL));
+        }
+        context::request_animation_frame();
+    }
+
+    fn draw_button(&self, canvas: &mut Canvas, button: SpinButton) {
+        let theme = theme::current();
+        let rect = self.button_rect(button);
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(if !self.enabled {
+            theme.surface
+        } else if self.pressed.map(|(b, _)| b) == Some(button) {
+            theme.surface_pressed
+        } else if self.hovered_button == Some(button) {
+            theme.surface_hover
+        } else {
+            theme.surface
+        });
+        canvas.draw_rect(rect, &paint);
+
+        let (cx, cy) = (rect.center_x(), rect.center_y());
+        let dy = if button == SpinButton::Up { -1.5 } else { 1.5 };
+        let mut path = Path::new();
+        path.move_to((cx - 3.5, cy - dy));
+        path.line_to((cx, cy + dy));
+        path.line_to((cx + 3.5, cy - dy));
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.5);
+        paint.set_color(if self.enabled { theme.foreground } else { theme.disabled_foreground });
+        canvas.draw_path(&path, &paint);
+    }
+}
+
+impl Widget for SpinBox {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        self.repeat();
+        let theme = theme::current();
+        let radius = 4.0;
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(if self.enabled { theme.background } else { theme.surface });
+        canvas.draw_round_rect(self.rect, radius, radius, &paint);
+
+        canvas.save();
+        canvas.clip_rrect(RRect::new_rect_xy(self.rect, radius, radius), None, Some(true));
+        self.draw_button(canvas, SpinButton::Up);
+        self.draw_button(canvas, SpinButton::Down);
+        canvas.restore();
+
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+        paint.set_color(theme.border);
+        canvas.draw_round_rect(self.rect, radius, radius, &paint);
+        let divider = self.rect.right - BUTTON_WIDTH;
+        canvas.draw_line((divider, self.rect.top), (divider, self.rect.bottom), &paint);
+        canvas.draw_line((divider, self.rect.center_y()), (self.rect.right, self.rect.center_y()), &paint);
+        if self.focused {
+            theme::draw_focus_ring(canvas, self.rect, radius);
+        }
+
+        let field = Rect::from_ltrb(self.rect.left + PADDING, self.rect.top, divider - PADDING, self.rect.bottom);
+        let color = if self.enabled { theme.foreground } else { theme.disabled_foreground };
+        self.text.draw(canvas, field, &theme.font(), color, self.focused);
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                let hovered = self.rect.contains(Point::new(*x, *y));
+                let hovered_button = self.button_at(*x, *y);
+                if hovered != self.hovered || hovered_button != self.hovered_button {
+                    self.hovered = hovered;
+                    self.hovered_button = hovered_button;
+                    context::request_redraw();
+                }
+                false
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => {
+                let inside = self.rect.contains(Point::new(*x, *y));
+                if !inside {
+                    if self.focused {
+                        self.commit();
+                        self.focused = false;
+                        context::request_redraw();
+                    }
+                    return false;
+                }
+                self.focused = true;
+                if let Some(button) = self.button_at(*x, *y) {
+                    self.step_by(if button == SpinButton::Up { 1.0 } else { -1.0 });
+                    self.pressed = Some((button, Instant::now() + REPEAT_DELAY));
+                    context::request_animation_frame();
+                }
+                context::request_redraw();
+                true
+            }
+            Event::MouseUp { button: MouseButton::Left, .. } if self.pressed.is_some() => {
+                self.pressed = None;
+                context::request_redraw();
+                true
+            }
+            Event::Scroll { x, y, delta, .. } if self.rect.contains(Point::new(*x, *y)) => {
+                let (_, dy) = scroll_amount(*delta);
+                // Wheel up (negative offset change) counts up.
+                if dy != 0.0 {
+                    self.step_by(if dy < 0.0 { 1.0 } else { -1.0 });
+                }
+                true
+            }
+            Event::KeyDown { key: Some(key), .. } if self.focused => match key {
+                VirtualKeyCode::Up => {
+                    self.step_by(1.0);
+                    true
+                }
+                VirtualKeyCode::Down => {
+                    self.step_by(-1.0);
+                    true
+                }
+                VirtualKeyCode::PageUp => {
+                    self.step_by(PAGE_STEPS);
+                    true
+                }
+                VirtualKeyCode::PageDown => {
+                    self.step_by(-PAGE_STEPS);
+                    true
+                }
+                VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                    self.commit();
+                    true
+                }
+                VirtualKeyCode::Escape if self.editing => {
+                    self.reset_text();
+                    context::request_redraw();
+                    true
+                }
+                _ => {
+                    if self.text.handle_event(event) {
+                        self.editing = true;
+                        context::request_redraw();
+                        return true;
+                    }
+                    false
+                }
+            },
+            Event::CharInput(_) if self.focused => {
+                if self.text.handle_event(event) {
+                    self.editing = true;
+                    context::request_redraw();
+                }
+                true
+            }
+            Event::FocusLost => {
+                self.commit();
+                self.focused = false;
+                self.pressed = None;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("value", self.value.to_string()),
+            ("range", format!("{}..={}", self.min, self.max)),
+            ("enabled", self.enabled.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let font = theme::current().font();
+        let widest = [self.min, self.max]
+            .iter()
+            .map(|v| font.measure_str(self.format.format(*v), None).0)
+            .fold(0.0, f32::max);
+        (widest + PADDING * 2.0 + BUTTON_WIDTH + 8.0, HEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_round_trips_with_its_suffix() {
+        let format = DecimalFormat { precision: 2, suffix: " mm".to_string() };
+        assert_eq!(format.format(7.5), "7.50 mm");
+        assert_eq!(format.parse("2.5 mm"), Some(2.5));
+        assert_eq!(format.parse(" 12mm "), Some(12.0));
+        assert_eq!(format.parse("-4"), Some(-4.0));
+        assert_eq!(format.parse("mm"), None);
+    }
+
+    #[test]
+    fn decimal_rejects_non_finite_values() {
+        let format = DecimalFormat { precision: 0, suffix: String::new() };
+        for text in ["nan", "NaN", "inf", "-inf", "infinity"] {
+            assert_eq!(format.parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn hex_pads_and_keeps_the_sign() {
+        let format = HexFormat { digits: 2 };
+        assert_eq!(format.format(31.0), "0x1F");
+        assert_eq!(format.format(5.0), "0x05");
+        assert_eq!(format.format(-31.0), "-0x1F");
+        assert_eq!(format.parse("0x1f"), Some(31.0));
+        assert_eq!(format.parse("0X1F"), Some(31.0));
+        assert_eq!(format.parse("ff"), Some(255.0));
+        assert_eq!(format.parse("-0x1F"), Some(-31.0));
+        assert_eq!(format.parse(&format.format(-31.0)), Some(-31.0));
+    }
+
+    #[test]
+    fn hex_rejects_junk() {
+        let format = HexFormat { digits: 0 };
+        for text in ["", "0x", "0xG1", "--1", "-0x-1", "0x+1"] {
+            assert_eq!(format.parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn duration_formats_minutes_and_hours() {
+        assert_eq!(DurationFormat.format(123.0), "2:03");
+        assert_eq!(DurationFormat.format(3723.0), "1:02:03");
+        assert_eq!(DurationFormat.format(-5.0), "0:00");
+    }
+
+    #[test]
+    fn duration_parses_every_accepted_form() {
+        assert_eq!(DurationFormat.parse("2:03"), Some(123.0));
+        assert_eq!(DurationFormat.parse("1:02:03"), Some(3723.0));
+        assert_eq!(DurationFormat.parse("90"), Some(90.0));
+        assert_eq!(DurationFormat.parse("1h 30m 5s"), Some(5405.0));
+        assert_eq!(DurationFormat.parse("1:2:3:4"), None);
+        assert_eq!(DurationFormat.parse("5m 3"), None);
+        assert_eq!(DurationFormat.parse("5x"), None);
+    }
+
+    #[test]
+    fn duration_rejects_non_finite_values() {
+        for text in ["nan", "inf", "infinity", "inf:00", "1:nan"] {
+            assert_eq!(DurationFormat.parse(text), None, "{}", text);
+        }
+    }
+}
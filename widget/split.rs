@@ -0,0 +1,389 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::scroll::Axis;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, Point, Rect};
+use std::time::{Duration, Instant};
+use winit::event::MouseButton;
+use winit::window::CursorIcon;
+
+// Width of the divider's hit area; only a 1px line is drawn.
+const DIVIDER: f32 = 6.0;
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    First,
+    Second,
+}
+
+// Two widgets side by side (`Axis::Horizontal`) or stacked (`Axis::Vertical`)
+// with a draggable divider between them. The divider position is kept as a
+// fraction so the panes scale when the window is resized.
+pub struct SplitPane {
+    axis: Axis,
+    first: Box<dyn Widget>,
+    second: Box<dyn Widget>,
+    ratio: f32,
+    min_first: f32,
+    min_second: f32,
+    // Which pane may be collapsed by dragging past its minimum or by
+    // double-clicking the divider.
+    collapsible: Option<Pane>,
+    collapsed: Option<Pane>,
+    rect: Rect,
+    hovered: bool,
+    // Pointer offset from the divider's start when the drag began.
+    drag: Option<f32>,
+    last_click: Option<Instant>,
+    on_moved: Option<Box<dyn FnMut(f32)>>,
+}
+
+impl SplitPane {
+    pub fn new(axis: Axis, first: Box<dyn Widget>, second: Box<dyn Widget>) -> Self {
+        Self {
+            axis,
+            first,
+            second,
+            ratio: 0.5,
+            min_first: 0.0,
+            min_second: 0.0,
+            collapsible: None,
+            collapsed: None,
+            rect: Rect::default(),
+            hovered: false,
+            drag: None,
+            last_click: None,
+            on_moved: None,
+        }
+    }
+
+    pub fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_min_sizes(mut self, first: f32, second: f32) -> Self {
+        self.min_first = first.max(0.0);
+        self.min_second = second.max(0.0);
+        self
+    }
+
+    pub fn collapsible(mut self, pane: Pane) -> Self {
+        self.collapsible = Some(pane);
+        self
+    }
+
+    // Called with the new ratio whenever the user moves the divider.
+    pub fn on_moved<F: FnMut(f32) + 'static>(mut self, f: F) -> Self {
+        self.on_moved = Some(Box::new(f));
+        self
+    }
+
+    pub fn first_mut(&mut self) -> &mut dyn Widget {
+        self.first.as_mut()
+    }
+
+    pub fn second_mut(&mut self) -> &mut dyn Widget {
+        self.second.as_mut()
+    }
+
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self.layout(self.rect);
+        context::request_redraw();
+    }
+
+    pub fn collapsed(&self) -> Option<Pane> {
+        self.collapsed
+    }
+
+    pub fn collapse(&mut self, pane: Pane) {
+        self.collapsed = Some(pane);
+        self.layout(self.rect);
+        context::request_redraw();
+    }
+
+    pub fn expand(&mut self) {
+        self.collapsed = None;
+        self.layout(self.rect);
+        context::request_redraw();
+    }
+
+    fn length(&self) -> f32 {
+        match self.axis {
+            Axis::Horizontal => self.rect.width(),
+            Axis::Vertical => self.rect.height(),
+        }
+    }
+
+    fn start(&self) -> f32 {
+        match self.axis {
+            Axis::Horizontal => self.rect.left,
+            Axis::Vertical => self.rect.top,
+        }
+    }
+
+    fn along(&self, x: f32, y: f32) -> f32 {
+        match self.axis {
+            Axis::Horizontal => x,
+            Axis::Vertical => y,
+        }
+    }
+
+    // Size of the first pane, honouring collapse and the minimum sizes.
+    fn first_size(&self) -> f32 {
+        let available = (self.length() - DIVIDER).max(0.0);
+        match self.collapsed {
+            Some(Pane::First) => 0.0,
+            Some(Pane::Second) => available,
+            None => {
+                let max = (available - self.min_second).max(self.min_first.min(available));
+                (available * self.ratio).clamp(self.min_first.min(max), max)
+            }
+        }
+    }
+
+    fn divider_rect(&self) -> Rect {
+        let offset = self.start() + self.first_size();
+        match self.axis {
+            Axis::Horizontal => Rect::from_xywh(offset, self.rect.top, DIVIDER, self.rect.height()),
+            Axis::Vertical => Rect::from_xywh(self.rect.left, offset, self.rect.width(), DIVIDER),
+        }
+    }
+
+    fn pane_rects(&self) -> (Rect, Rect) {
+        let divider = self.divider_rect();
+        match self.axis {
+            Axis::Horizontal => (
+                Rect::from_ltrb(self.rect.left, self.rect.top, divider.left, self.rect.bottom),
+                Rect::from_ltrb(divider.right, self.rect.top, self.rect.right, self.rect.bottom),
+            ),
+            Axis::Vertical => (
+                Rect::from_ltrb(self.rect.left, self.rect.top, self.rect.right, divider.top),
+                Rect::from_ltrb(self.rect.left, divider.bottom, self.rect.right, self.rect.bottom),
+            ),
+        }
+    }
+
+    fn cursor(&self) -> CursorIcon {
+        match self.axis {
+            Axis::Horizontal => CursorIcon::ColResize,
+            Axis::Vertical => CursorIcon::RowResize,
+        }
+    }
+
+    fn drag_to(&mut self, position: f32) {
+        let available = (self.length() - DIVIDER).max(1.0);
+        let size = position - self.start();
+        // Dragging well past a collapsible pane's minimum folds it away.
+        self.collapsed = match self.collapsible {
+            Some(Pane::First) if size < self.min_first / 2.0 => Some(Pane::First),
+            Some(Pane::Second) if available - size < self.min_second / 2.0 => Some(Pane::Second),
+            _ => None,
+        };
+        if self.collapsed.is_none() {
+            self.ratio = (size / available).clamp(0.0, 1.0);
+            if let Some(on_moved) = &mut self.on_moved {
+                on_moved(self.ratio);
+            }
+        }
+        self.layout(self.rect);
+        context::request_redraw();
+    }
+
+    fn toggle_collapse(&mut self) {
+        match (self.collapsed, self.collapsible) {
+            (Some(_), _) => self.expand(),
+            (None, Some(pane)) => self.collapse(pane),
+            (None, None) => {}
+        }
+    }
+
+    fn forward(&mut self, event: &Event) -> bool {
+        match self.collapsed {
+            Some(Pane::First) => self.second.on_event(event),
+            Some(Pane::Second) => self.first.on_event(event),
+            None => self.first.on_event(event) || self.second.on_event(event),
+        }
+    }
+}
+
+impl Widget for SplitPane {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let (first, second) = self.pane_rects();
+        for (pane, rect) in [(Pane::First, first), (Pane::Second, second)] {
+            if self.collapsed == Some(pane) {
+                continue;
+            }
+            canvas.save();
+            canvas.clip_rect(rect, None, Some(true));
+            match pane {
+                Pane::First => self.first.draw(canvas),
+                Pane::Second => self.second.draw(canvas),
+            }
+            canvas.restore();
+        }
+
+        let theme = theme::current();
+        let divider = self.divider_rect();
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        if self.hovered || self.drag.is_some() {
+            paint.set_color(theme.surface_hover);
+            canvas.draw_rect(divider, &paint);
+        }
+        paint.set_color(if self.drag.is_some() { theme.accent } else { theme.border });
+        let (cx, cy) = (divider.center_x(), divider.center_y());
+        match self.axis {
+            Axis::Horizontal => canvas.draw_line((cx, divider.top), (cx, divider.bottom), &paint),
+            Axis::Vertical => canvas.draw_line((divider.left, cy), (divider.right, cy), &paint),
+        };
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                if let Some(grab) = self.drag {
+                    self.drag_to(self.along(*x, *y) - grab);
+                    context::set_cursor(self.cursor());
+                    return true;
+                }
+                let hovered = self.divider_rect().contains(Point::new(*x, *y));
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    context::request_redraw();
+                }
+                if hovered {
+                    context::set_cursor(self.cursor());
+                }
+                self.forward(event)
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. }
+                if self.divider_rect().contains(Point::new(*x, *y)) =>
+            {
+                let now = Instant::now();
+                if self.last_click.is_some_and(|at| now - at < DOUBLE_CLICK) {
+                    self.last_click = None;
+                    self.toggle_collapse();
+                    return true;
+                }
+                self.last_click = Some(now);
+                let divider = self.divider_rect();
+                let divider_start = self.along(divider.left, divider.top);
+                self.drag = Some(self.along(*x, *y) - divider_start);
+                context::request_redraw();
+                true
+            }
+            Event::MouseUp { button: MouseButton::Left, .. } if self.drag.is_some() => {
+                self.drag = None;
+                context::request_redraw();
+                true
+            }
+            _ => self.forward(event),
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+        let (first, second) = self.pane_rects();
+        if self.collapsed != Some(Pane::First) {
+            self.first.layout(first);
+        }
+        if self.collapsed != Some(Pane::Second) {
+            self.second.layout(second);
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn visit_children(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        if self.collapsed != Some(Pane::First) {
+            f(self.first.as_ref());
+        }
+        if self.collapsed != Some(Pane::Second) {
+            f(self.second.as_ref());
+        }
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("ratio", self.ratio.to_string()),
+            ("collapsed", format!("{:?}", self.collapsed)),
+        ]
+    }
+
+    fn debug_style(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("axis", format!("{:?}", self.axis)),
+            ("min_sizes", format!("({}, {})", self.min_first, self.min_second)),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let (a, b) = (self.first.preferred_size(), self.second.preferred_size());
+        match self.axis {
+            Axis::Horizontal => (a.0 + b.0 + DIVIDER, a.1.max(b.1)),
+            Axis::Vertical => (a.0.max(b.0), a.1 + b.1 + DIVIDER),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::label::Label;
+
+    fn pane(axis: Axis, length: f32) -> SplitPane {
+        let mut split = SplitPane::new(axis, Box::new(Label::new("a")), Box::new(Label::new("b")));
+        split.layout(match axis {
+            Axis::Horizontal => Rect::from_xywh(10.0, 0.0, length, 50.0),
+            Axis::Vertical => Rect::from_xywh(0.0, 10.0, 50.0, length),
+        });
+        split
+    }
+
+    #[test]
+    fn first_size_follows_the_ratio() {
+        let split = pane(Axis::Horizontal, 206.0).with_ratio(0.25);
+        assert_eq!(split.first_size(), 50.0);
+        assert_eq!(split.pane_rects().0.width(), 50.0);
+        assert_eq!(split.pane_rects().1.left, 10.0 + 50.0 + DIVIDER);
+    }
+
+    #[test]
+    fn first_size_respects_both_minimums() {
+        let low = pane(Axis::Vertical, 206.0).with_ratio(0.0).with_min_sizes(30.0, 40.0);
+        assert_eq!(low.first_size(), 30.0);
+        let high = pane(Axis::Vertical, 206.0).with_ratio(1.0).with_min_sizes(30.0, 40.0);
+        assert_eq!(high.first_size(), 160.0);
+    }
+
+    #[test]
+    fn first_minimum_wins_when_both_cannot_fit() {
+        let split = pane(Axis::Horizontal, 106.0).with_ratio(0.5).with_min_sizes(80.0, 80.0);
+        assert_eq!(split.first_size(), 80.0);
+        let tiny = pane(Axis::Horizontal, 20.0).with_min_sizes(80.0, 80.0);
+        assert_eq!(tiny.first_size(), 14.0);
+    }
+
+    #[test]
+    fn collapsed_panes_take_no_space() {
+        let mut split = pane(Axis::Horizontal, 206.0).with_min_sizes(30.0, 40.0);
+        split.collapse(Pane::First);
+        assert_eq!(split.first_size(), 0.0);
+        split.collapse(Pane::Second);
+        assert_eq!(split.first_size(), 200.0);
+        split.expand();
+        assert_eq!(split.first_size(), 100.0);
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Color, Paint, PaintStyle, Point, Rect};
+use std::time::Instant;
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const TRACK_WIDTH: f32 = 36.0;
+const TRACK_HEIGHT: f32 = 20.0;
+const KNOB_INSET: f32 = 2.0;
+const LABEL_GAP: f32 = 8.0;
+const HEIGHT: f32 = 24.0;
+// Knob travel per second, as a fraction of the whole track.
+const KNOB_SPEED: f32 = 8.0;
+
+// An on/off toggle. The knob slides across when flipped.
+pub struct Switch {
+    label: String,
+    on: bool,
+    enabled: bool,
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+    rect: Rect,
+    // 0.0 is fully off, 1.0 fully on.
+    knob: f32,
+    last_tick: Option<Instant>,
+    on_changed: Option<Box<dyn FnMut(bool)>>,
+}
+
+impl Switch {
+    pub fn new(label: &str, on: bool) -> Self {
+        Self {
+            label: label.to_string(),
+            on,
+            enabled: true,
+            hovered: false,
+            pressed: false,
+            focused: false,
+            rect: Rect::default(),
+            knob: if on { 1.0 } else { 0.0 },
+            last_tick: None,
+            on_changed: None,
+        }
+    }
+
+    pub fn on_changed<F: FnMut(bool) + 'static>(mut self, f: F) -> Self {
+        self.on_changed = Some(Box::new(f));
+        self
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    // Jumps straight to the new position without animating or firing the
+    // callback.
+    pub fn set_on(&mut self, on: bool) {
+        self.on = on;
+        self.knob = if on { 1.0 } else { 0.0 };
+        context::request_redraw();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.hovered = false;
+            self.pressed = false;
+            self.focused = false;
+        }
+        context::request_redraw();
+    }
+
+    fn toggle(&mut self) {
+        self.on = !self.on;
+        self.last_tick = None;
+        if let Some(on_changed) = &mut self.on_changed {
+            on_changed(self.on);
+        }
+        context::request_animation_frame();
+    }
+
+    fn tick(&mut self) {
+        let target = if self.on { 1.0 } else { 0.0 };
+        if self.knob == target {
+            self.last_tick = None;
+            return;
+        }
+        let now = Instant::now();
+        let dt = self.last_tick.map_or(0.0, |last| (now - last).as_secs_f32());
+        self.last_tick = Some(now);
+        let step = KNOB_SPEED * dt;
+        self.knob = if self.knob < target {
+            (self.knob + step).min(target)
+        } else {
+            (self.knob - step).max(target)
+        };
+        context::request_animation_frame();
+    }
+
+    fn track_rect(&self) -> Rect {
+        Rect::from_xywh(self.rect.left, self.rect.center_y() - TRACK_HEIGHT / 2.0, TRACK_WIDTH, TRACK_HEIGHT)
+    }
+}
+
+// Linear blend between two colors, for the track fading between states.
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color::from_argb(
+        channel(a.a(), b.a()),
+        channel(a.r(), b.r()),
+        channel(a.g(), b.g()),
+        channel(a.b(), b.b()),
+    )
+}
+
+impl Widget for Switch {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        self.tick();
+        let theme = theme::current();
+        let track = self.track_rect();
+        let radius = TRACK_HEIGHT / 2.0;
+
+        let off_color = if self.hovered && !self.pressed { theme.border } else { theme.surface_pressed };
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(if self.enabled { mix(off_color, theme.accent, self.knob) } else { theme.surface });
+        canvas.draw_round_rect(track, radius, radius, &paint);
+
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+        paint.set_color(theme.border);
+        canvas.draw_round_rect(track, radius, radius, &paint);
+
+        let knob_radius = radius - KNOB_INSET;
+        let travel = TRACK_WIDTH - TRACK_HEIGHT;
+        let center = Point::new(track.left + radius + travel * self.knob, track.center_y());
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(if self.enabled { theme.background } else { theme.surface_hover });
+        canvas.draw_circle(center, knob_radius, &paint);
+
+        if self.focused {
+            theme::draw_focus_ring(canvas, track, radius);
+        }
+
+        let font = theme.font();
+        paint.set_color(if self.enabled { theme.foreground } else { theme.disabled_foreground });
+        let baseline = self.rect.center_y() + font.size() / 3.0;
+        canvas.draw_str(&self.label, (track.right + LABEL_GAP, baseline), &font, &paint);
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                let hovered = self.rect.contains(Point::new(*x, *y));
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    context::request_redraw();
+                }
+                false
+            }
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } => {
+                self.focused = self.rect.contains(Point::new(*x, *y));
+                self.pressed = self.focused;
+                self.pressed
+            }
+            Event::MouseUp { x, y, button: MouseButton::Left, .. } if self.pressed => {
+                self.pressed = false;
+                if self.rect.contains(Point::new(*x, *y)) {
+                    self.toggle();
+                }
+                true
+            }
+            Event::KeyDown { key: Some(VirtualKeyCode::Space), .. }
+            | Event::KeyDown { key: Some(VirtualKeyCode::Return), .. }
+                if self.focused =>
+            {
+                self.toggle();
+                true
+            }
+            Event::FocusLost => {
+                self.focused = false;
+                self.pressed = false;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("label", self.label.clone()),
+            ("on", self.on.to_string()),
+            ("enabled", self.enabled.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let font = theme::current().font();
+        let label = if self.label.is_empty() {
+            0.0
+        } else {
+            LABEL_GAP + font.measure_str(&self.label, None).0
+        };
+        (TRACK_WIDTH + label, HEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::testing::render;
+
+    #[test]
+    fn switch_track_shows_its_state() {
+        let theme = theme::current();
+        // The knob sits at the far end, so sample the other one.
+        let mut on = Switch::new("Sync", true);
+        let mut backend = render(&mut on, Rect::from_xywh(10.0, 10.0, 120.0, 24.0));
+        assert_eq!(backend.pixel(15, 22), Some(theme.accent));
+
+        let mut off = Switch::new("Sync", false);
+        let mut backend = render(&mut off, Rect::from_xywh(10.0, 10.0, 120.0, 24.0));
+        assert_eq!(backend.pixel(41, 22), Some(theme.surface_pressed));
+    }
+}
@@ -0,0 +1,849 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::checkbox::{self, CheckState};
+use crate::widget::list::{ListDelegate, ListView};
+use crate::widget::scroll::{scroll_amount, Axis, Scrollbar, SCROLLBAR_SIZE};
+use crate::widget::selection::{Selection, SelectionMode};
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Point, Rect};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+use winit::event::MouseButton;
+
+const HEADER_HEIGHT: f32 = 28.0;
+const ROW_HEIGHT: f32 = 24.0;
+const CELL_PADDING: f32 = 6.0;
+// How close to a column edge the pointer has to be to start a resize.
+const RESIZE_GRIP: f32 = 4.0;
+// How far a header has to be dragged before it counts as a reorder.
+const DRAG_THRESHOLD: f32 = 5.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    Empty,
+    Text(String),
+    Number(f64),
+    Bool(bool),
+}
+
+impl CellValue {
+    fn compare(&self, other: &CellValue) -> Ordering {
+        match (self, other) {
+            (CellValue::Number(a), CellValue::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+            (CellValue::Text(a), CellValue::Text(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+            (CellValue::Bool(a), CellValue::Bool(b)) => a.cmp(b),
+            (CellValue::Empty, CellValue::Empty) => Ordering::Equal,
+            // Empty cells sort last.
+            (CellValue::Empty, _) => Ordering::Greater,
+            (_, CellValue::Empty) => Ordering::Less,
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+// Where a table's rows come from. Cells are only asked for when a row is on
+// screen (and for every row when sorting).
+pub trait TableSource {
+    fn row_count(&self) -> usize;
+    fn cell(&self, row: usize, column: usize) -> CellValue;
+
+    fn compare(&self, a: usize, b: usize, column: usize) -> Ordering {
+        self.cell(a, column).compare(&self.cell(b, column))
+    }
+
+    // Called when the user toggles a checkbox cell.
+    fn set_cell(&mut self, _row: usize, _column: usize, _value: CellValue) {}
+}
+
+// A widget used as a cell renderer. One is created per visible row and
+// rebound as rows scroll.
+pub trait CellWidget: Widget {
+    fn bind(&mut self, row: usize, value: &CellValue, selected: bool);
+}
+
+#[derive(Clone)]
+pub enum ColumnKind {
+    Text,
+    Number { precision: usize },
+    Checkbox,
+    Custom(Rc<dyn Fn() -> Box<dyn CellWidget>>),
+}
+
+#[derive(Clone)]
+pub struct Column {
+    title: String,
+    kind: ColumnKind,
+    // Column index passed to `TableSource::cell`; stays put when the column
+    // is moved.
+    data: usize,
+    width: f32,
+    min_width: f32,
+    sortable: bool,
+    resizable: bool,
+}
+
+impl Column {
+    pub fn new(title: &str, data: usize, kind: ColumnKind, width: f32) -> Self {
+        Self {
+            title: title.to_string(),
+            kind,
+            data,
+            width,
+            min_width: 32.0,
+            sortable: true,
+            resizable: true,
+        }
+    }
+
+    pub fn text(title: &str, data: usize, width: f32) -> Self {
+        Self::new(title, data, ColumnKind::Text, width)
+    }
+
+    pub fn number(title: &str, data: usize, width: f32, precision: usize) -> Self {
+        Self::new(title, data, ColumnKind::Number { precision }, width)
+    }
+
+    pub fn checkbox(title: &str, data: usize, width: f32) -> Self {
+        Self::new(title, data, ColumnKind::Checkbox, width)
+    }
+
+    pub fn custom<F>(title: &str, data: usize, width: f32, factory: F) -> Self
+    where
+        F: Fn() -> Box<dyn CellWidget> + 'static,
+    {
+        Self::new(title, data, ColumnKind::Custom(Rc::new(factory)), width)
+    }
+
+    pub fn with_min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self.width = self.width.max(min_width);
+        self
+    }
+
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+struct TableState<S: TableSource> {
+    source: S,
+    columns: Vec<Column>,
+    // The first `frozen` columns don't scroll horizontally.
+    frozen: usize,
+    h_offset: f32,
+    // (data column, direction)
+    sort: Option<(usize, SortDirection)>,
+    // Display row -> source row.
+    order: Vec<usize>,
+    left: f32,
+}
+
+impl<S: TableSource> TableState<S> {
+    fn resort(&mut self) {
+        self.order = (0..self.source.row_count()).collect();
+        if let Some((column, direction)) = self.sort {
+            let source = &self.source;
+            self.order.sort_by(|a, b| {
+                let ordering = source.compare(*a, *b, column);
+                match direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            });
+        }
+    }
+
+    // Re-sorts and returns, for each old display row, where its source row
+    // is displayed now.
+    fn resort_mapping(&mut self) -> Vec<Option<usize>> {
+        let old = std::mem::take(&mut self.order);
+        self.resort();
+        let mut display = vec![None; self.order.len()];
+        for (row, source) in self.order.iter().enumerate() {
+            display[*source] = Some(row);
+        }
+        old.iter().map(|source| display.get(*source).copied().flatten()).collect()
+    }
+
+    fn frozen_width(&self) -> f32 {
+        self.columns[..self.frozen.min(self.columns.len())].iter().map(|c| c.width).sum()
+    }
+
+    fn total_width(&self) -> f32 {
+        self.columns.iter().map(|c| c.width).sum()
+    }
+
+    // Screen x of each displayed column's left edge.
+    fn column_lefts(&self) -> Vec<f32> {
+        let mut x = self.left;
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let left = if i < self.frozen { x } else { x - self.h_offset };
+                x += column.width;
+                left
+            })
+            .collect()
+    }
+}
+
+// Draws the text, number and checkbox column kinds.
+fn draw_cell(canvas: &mut Canvas, rect: Rect, kind: &ColumnKind, value: &CellValue, selected: bool) {
+    let theme = theme::current();
+    let font = theme.font();
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_color(if selected { theme.accent_foreground } else { theme.foreground });
+    let baseline = rect.center_y() + font.size() / 3.0;
+
+    match (kind, value) {
+        (ColumnKind::Checkbox, CellValue::Bool(checked)) => {
+            let size = 14.0;
+            let bx = Rect::from_xywh(rect.center_x() - size / 2.0, rect.center_y() - size / 2.0, size, size);
+            let state = if *checked { CheckState::Checked } else { CheckState::Unchecked };
+            checkbox::draw_box(canvas, bx, state, false, false, true);
+        }
+        (ColumnKind::Number { precision }, CellValue::Number(n)) => {
+            let text = format!("{:.*}", *precision, n);
+            let width = font.measure_str(&text, Some(&paint)).0;
+            canvas.draw_str(&text, (rect.right - CELL_PADDING - width, baseline), &font, &paint);
+        }
+        (_, CellValue::Text(text)) => {
+            canvas.draw_str(text, (rect.left + CELL_PADDING, baseline), &font, &paint);
+        }
+        (_, CellValue::Number(n)) => {
+            canvas.draw_str(n.to_string(), (rect.left + CELL_PADDING, baseline), &font, &paint);
+        }
+        (_, CellValue::Bool(b)) => {
+            canvas.draw_str(b.to_string(), (rect.left + CELL_PADDING, baseline), &font, &paint);
+        }
+        (_, CellValue::Empty) => {}
+    }
+}
+
+pub struct TableRow<S: TableSource> {
+    state: Rc<RefCell<TableState<S>>>,
+    // Custom cell widgets, keyed by data column.
+    cells: Vec<(usize, Box<dyn CellWidget>)>,
+    row: usize,
+    selected: bool,
+    rect: Rect,
+}
+
+impl<S: TableSource> TableRow<S> {
+    fn cell_rects(&self) -> Vec<(usize, Rect)> {
+        let state = self.state.borrow();
+        state
+            .column_lefts()
+            .into_iter()
+            .zip(state.columns.iter())
+            .enumerate()
+            .map(|(i, (left, column))| (i, Rect::from_xywh(left, self.rect.top, column.width, self.rect.height())))
+            .collect()
+    }
+}
+
+impl<S: TableSource> Widget for TableRow<S> {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let rects = self.cell_rects();
+        let state = self.state.borrow();
+        let frozen_right = state.left + state.frozen_width();
+
+        for (i, rect) in rects {
+            let column = &state.columns[i];
+            canvas.save();
+            let mut clip = rect;
+            if i >= state.frozen {
+                // Scrolled columns slide underneath the frozen ones.
+                clip.left = clip.left.max(frozen_right);
+            }
+            canvas.clip_rect(clip, None, Some(true));
+
+            match &column.kind {
+                ColumnKind::Custom(_) => {
+                    if let Some((_, cell)) = self.cells.iter_mut().find(|(data, _)| *data == column.data) {
+                        cell.layout(rect);
+                        cell.draw(canvas);
+                    }
+                }
+                kind => {
+                    let value = state.source.cell(self.row, column.data);
+                    draw_cell(canvas, rect, kind, &value, self.selected);
+                }
+            }
+            canvas.restore();
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        if let Event::MouseDown { x, y, button: MouseButton::Left, .. } = event {
+            for (i, rect) in self.cell_rects() {
+                if !rect.contains(Point::new(*x, *y)) {
+                    continue;
+                }
+                let (kind, data) = {
+                    let state = self.state.borrow();
+                    (state.columns[i].kind.clone(), state.columns[i].data)
+                };
+                match kind {
+                    ColumnKind::Checkbox => {
+                        let mut state = self.state.borrow_mut();
+                        if let CellValue::Bool(checked) = state.source.cell(self.row, data) {
+                            state.source.set_cell(self.row, data, CellValue::Bool(!checked));
+                            context::request_redraw();
+                        }
+                        return true;
+                    }
+                    ColumnKind::Custom(_) => {
+                        if let Some((_, cell)) = self.cells.iter_mut().find(|(d, _)| *d == data) {
+                            return cell.on_event(event);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            return false;
+        }
+
+        let mut handled = false;
+        for (_, cell) in &mut self.cells {
+            handled |= cell.on_event(event);
+        }
+        handled
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("row", self.row.to_string()),
+            ("selected", self.selected.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (self.state.borrow().total_width(), ROW_HEIGHT)
+    }
+}
+
+// The list delegate behind a table: one `TableRow` per visible row.
+pub struct TableRows<S: TableSource> {
+    state: Rc<RefCell<TableState<S>>>,
+}
+
+impl<S: TableSource> ListDelegate for TableRows<S> {
+    type Row = TableRow<S>;
+
+    fn len(&self) -> usize {
+        self.state.borrow().order.len()
+    }
+
+    fn create_row(&mut self) -> TableRow<S> {
+        let cells = self
+            .state
+            .borrow()
+            .columns
+            .iter()
+            .filter_map(|column| match &column.kind {
+                ColumnKind::Custom(factory) => Some((column.data, factory())),
+                _ => None,
+            })
+            .collect();
+        TableRow {
+            state: self.state.clone(),
+            cells,
+            row: 0,
+            selected: false,
+            rect: Rect::default(),
+        }
+    }
+
+    fn bind_row(&mut self, row: &mut TableRow<S>, index: usize, selected: bool) {
+        let state = self.state.borrow();
+        row.row = state.order[index];
+        row.selected = selected;
+        for (data, cell) in &mut row.cells {
+            cell.bind(row.row, &state.source.cell(row.row, *data), selected);
+        }
+    }
+
+    fn estimated_row_height(&self) -> f32 {
+        ROW_HEIGHT
+    }
+
+    fn row_height(&self, _index: usize) -> Option<f32> {
+        Some(ROW_HEIGHT)
+    }
+}
+
+enum HeaderDrag {
+    // Displayed column index, pointer x and width at the start.
+    Resize(usize, f32, f32),
+    // Displayed column index, pointer x at the start, current pointer x.
+    Move(usize, f32, f32),
+}
+
+// A sortable, resizable, reorderable table built on `ListView`. Click a
+// header to sort, drag its right edge to resize, or drag it sideways to move
+// it. Frozen columns stay put while the rest scroll horizontally.
+pub struct Table<S: TableSource + 'static> {
+    state: Rc<RefCell<TableState<S>>>,
+    list: ListView<TableRows<S>>,
+    h_scrollbar: Scrollbar,
+    rect: Rect,
+    header: Rect,
+    pressed: Option<(usize, f32)>,
+    drag: Option<HeaderDrag>,
+    on_sort: Option<Box<dyn FnMut(usize, SortDirection)>>,
+}
+
+impl<S: TableSource + 'static> Table<S> {
+    pub fn new(source: S, columns: Vec<Column>) -> Self {
+        let mut state = TableState {
+            source,
+            columns,
+            frozen: 0,
+            h_offset: 0.0,
+            sort: None,
+            order: Vec::new(),
+            left: 0.0,
+        };
+        state.resort();
+        let state = Rc::new(RefCell::new(state));
+        let list = ListView::new(TableRows { state: state.clone() });
+
+        Self {
+            state,
+            list,
+            h_scrollbar: Scrollbar::new(Axis::Horizontal),
+            rect: Rect::default(),
+            header: Rect::default(),
+            pressed: None,
+            drag: None,
+            on_sort: None,
+        }
+    }
+
+    pub fn with_frozen_columns(self, frozen: usize) -> Self {
+        self.state.borrow_mut().frozen = frozen;
+        self
+    }
+
+    pub fn with_selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.list = self.list.with_selection_mode(mode);
+        self
+    }
+
+    pub fn on_selection_changed<F: FnMut(&Selection) + 'static>(mut self, f: F) -> Self {
+        self.list = self.list.on_selection_changed(f);
+        self
+    }
+
+    pub fn on_sort<F: FnMut(usize, SortDirection) + 'static>(mut self, f: F) -> Self {
+        self.on_sort = Some(Box::new(f));
+        self
+    }
+
+    // Source row behind a displayed row.
+    pub fn source_row(&self, display_row: usize) -> Option<usize> {
+        self.state.borrow().order.get(display_row).copied()
+    }
+
+    pub fn selection(&self) -> &Selection {
+        self.list.selection()
+    }
+
+    pub fn with_source<R>(&self, f: impl FnOnce(&S) -> R) -> R {
+        f(&self.state.borrow().source)
+    }
+
+    // Lets the caller change the data, then re-sorts and reloads the rows.
+    // Selected source rows stay selected wherever they end up.
+    pub fn update_source(&mut self, f: impl FnOnce(&mut S)) {
+        let mapping = {
+            let mut state = self.state.borrow_mut();
+            f(&mut state.source);
+            state.resort_mapping()
+        };
+        self.list.reload_remapped(|row| mapping.get(row).copied().flatten());
+    }
+
+    pub fn sort_by(&mut self, data_column: usize, direction: SortDirection) {
+        let mapping = {
+            let mut state = self.state.borrow_mut();
+            state.sort = Some((data_column, direction));
+            state.resort_mapping()
+        };
+        self.list.reload_remapped(|row| mapping.get(row).copied().flatten());
+        if let Some(on_sort) = &mut self.on_sort {
+            on_sort(data_column, direction);
+        }
+    }
+
+    pub fn columns(&self) -> Vec<Column> {
+        self.state.borrow().columns.clone()
+    }
+
+    fn header_rects(&self) -> Vec<Rect> {
+        let state = self.state.borrow();
+        state
+            .column_lefts()
+            .into_iter()
+            .zip(state.columns.iter())
+            .map(|(left, column)| Rect::from_xywh(left, self.header.top, column.width, HEADER_HEIGHT))
+            .collect()
+    }
+
+    fn header_at(&self, x: f32) -> Option<usize> {
+        let (frozen, frozen_right) = {
+            let state = self.state.borrow();
+            (state.frozen, state.left + state.frozen_width())
+        };
+        self.header_rects().iter().enumerate().position(|(i, rect)| {
+            // Scrolled headers hidden under the frozen ones can't be hit.
+            (i < frozen || x >= frozen_right) && x >= rect.left && x < rect.right
+        })
+    }
+
+    fn resize_grip_at(&self, x: f32) -> Option<usize> {
+        let state = self.state.borrow();
+        self.header_rects()
+            .iter()
+            .position(|rect| (x - rect.right).abs() <= RESIZE_GRIP)
+            .filter(|i| state.columns[*i].resizable)
+    }
+
+    fn set_h_offset(&mut self, offset: f32) {
+        let mut state = self.state.borrow_mut();
+        let viewport = self.rect.width() - SCROLLBAR_SIZE - state.frozen_width();
+        let max = (state.total_width() - state.frozen_width() - viewport).max(0.0);
+        state.h_offset = offset.clamp(0.0, max);
+        context::request_redraw();
+    }
+
+    fn update_h_scrollbar(&mut self) {
+        let state = self.state.borrow();
+        let frozen = state.frozen_width();
+        let track = Rect::from_ltrb(
+            self.rect.left + frozen,
+            self.rect.bottom - SCROLLBAR_SIZE,
+            self.rect.right - SCROLLBAR_SIZE,
+            self.rect.bottom,
+        );
+        self.h_scrollbar.update(track, track.width(), state.total_width() - frozen);
+    }
+
+    fn toggle_sort(&mut self, index: usize) {
+        let (data, sortable, current) = {
+            let state = self.state.borrow();
+            let column = &state.columns[index];
+            (column.data, column.sortable, state.sort)
+        };
+        if !sortable {
+            return;
+        }
+        let direction = match current {
+            Some((column, SortDirection::Ascending)) if column == data => SortDirection::Descending,
+            _ => SortDirection::Ascending,
+        };
+        self.sort_by(data, direction);
+    }
+
+    fn draw_header(&self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let font = theme.font();
+        let state = self.state.borrow();
+        let rects = self.header_rects();
+        let frozen_right = state.left + state.frozen_width();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(theme.surface);
+        canvas.draw_rect(self.header, &paint);
+
+        // Scrolled headers first, then frozen ones on top.
+        let order = (state.frozen..state.columns.len()).chain(0..state.frozen.min(state.columns.len()));
+        for i in order {
+            let column = &state.columns[i];
+            let mut rect = rects[i];
+            if let Some(HeaderDrag::Move(moving, start, current)) = &self.drag {
+                if *moving == i {
+                    rect.offset((current - start, 0.0));
+                }
+            }
+
+            canvas.save();
+            let mut clip = rect;
+            if i >= state.frozen {
+                clip.left = clip.left.max(frozen_right);
+            }
+            canvas.clip_rect(clip, None, Some(true));
+
+            paint.set_style(PaintStyle::Fill);
+            paint.set_color(if self.pressed.map(|(p, _)| p) == Some(i) { theme.surface_pressed } else { theme.surface });
+            canvas.draw_rect(rect, &paint);
+
+            let mut text_paint = Paint::default();
+            text_paint.set_anti_alias(true);
+            text_paint.set_color(theme.foreground);
+            let baseline = rect.center_y() + font.size() / 3.0;
+            canvas.draw_str(&column.title, (rect.left + CELL_PADDING, baseline), &font, &text_paint);
+
+            if let Some((data, direction)) = state.sort {
+                if data == column.data {
+                    let arrow = match direction {
+                        SortDirection::Ascending => "\u{25B2}",
+                        SortDirection::Descending => "\u{25BC}",
+                    };
+                    let width = font.measure_str(arrow, Some(&text_paint)).0;
+                    canvas.draw_str(arrow, (rect.right - CELL_PADDING - width, baseline), &font, &text_paint);
+                }
+            }
+
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_color(theme.border);
+            canvas.draw_line((rect.right - 0.5, rect.top + 4.0), (rect.right - 0.5, rect.bottom - 4.0), &paint);
+            canvas.restore();
+        }
+
+        paint.set_color(theme.border);
+        canvas.draw_line(
+            (self.header.left, self.header.bottom - 0.5),
+            (self.header.right, self.header.bottom - 0.5),
+            &paint,
+        );
+    }
+
+    fn finish_move(&mut self, index: usize, x: f32) {
+        let target = match self.header_at(x) {
+            Some(target) => target,
+            None => return,
+        };
+        let mut state = self.state.borrow_mut();
+        // Columns stay on their side of the frozen boundary.
+        if (index < state.frozen) != (target < state.frozen) {
+            return;
+        }
+        let column = state.columns.remove(index);
+        state.columns.insert(target, column);
+    }
+}
+
+impl<S: TableSource + 'static> Widget for Table<S> {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        canvas.save();
+        canvas.clip_rect(self.rect, None, Some(true));
+        self.list.draw(canvas);
+        self.draw_header(canvas);
+        let offset = self.state.borrow().h_offset;
+        self.h_scrollbar.draw(canvas, offset);
+        canvas.restore();
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        let mut offset = self.state.borrow().h_offset;
+        if self.h_scrollbar.on_event(event, &mut offset) {
+            self.set_h_offset(offset);
+            return true;
+        }
+
+        match event {
+            Event::MouseDown { x, y, button: MouseButton::Left, .. } if self.header.contains(Point::new(*x, *y)) => {
+                if let Some(index) = self.resize_grip_at(*x) {
+                    let width = self.state.borrow().columns[index].width;
+                    self.drag = Some(HeaderDrag::Resize(index, *x, width));
+                } else if let Some(index) = self.header_at(*x) {
+                    self.pressed = Some((index, *x));
+                }
+                context::request_redraw();
+                true
+            }
+            Event::MouseMove { x, .. } if self.drag.is_some() || self.pressed.is_some() => {
+                match &mut self.drag {
+                    Some(HeaderDrag::Resize(index, start, width)) => {
+                        let mut state = self.state.borrow_mut();
+                        let column = &mut state.columns[*index];
+                        column.width = (*width + x - *start).max(column.min_width);
+                    }
+                    Some(HeaderDrag::Move(_, _, current)) => *current = *x,
+                    None => {
+                        if let Some((index, start)) = self.pressed {
+                            if (x - start).abs() > DRAG_THRESHOLD {
+                                self.drag = Some(HeaderDrag::Move(index, start, *x));
+                            }
+                        }
+                    }
+                }
+                self.update_h_scrollbar();
+                context::request_redraw();
+                true
+            }
+            Event::MouseUp { x, button: MouseButton::Left, .. } if self.drag.is_some() || self.pressed.is_some() => {
+                match self.drag.take() {
+                    Some(HeaderDrag::Move(index, _, _)) => self.finish_move(index, *x),
+                    Some(HeaderDrag::Resize(..)) => {}
+                    None => {
+                        if let Some((index, _)) = self.pressed {
+                            self.toggle_sort(index);
+                        }
+                    }
+                }
+                self.pressed = None;
+                self.update_h_scrollbar();
+                context::request_redraw();
+                true
+            }
+            Event::Scroll { x, y, delta, modifiers, .. } if self.rect.contains(Point::new(*x, *y)) => {
+                let (dx, dy) = scroll_amount(*delta);
+                if modifiers.shift() || dx != 0.0 {
+                    let offset = self.state.borrow().h_offset;
+                    self.set_h_offset(offset + if dx != 0.0 { dx } else { dy });
+                    true
+                } else {
+                    self.list.on_event(event)
+                }
+            }
+            _ => self.list.on_event(event),
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.header = Rect::from_xywh(rect.left, rect.top, rect.width(), HEADER_HEIGHT);
+        self.state.borrow_mut().left = rect.left;
+        self.list.layout(Rect::from_ltrb(
+            rect.left,
+            rect.top + HEADER_HEIGHT,
+            rect.right,
+            rect.bottom - SCROLLBAR_SIZE,
+        ));
+        self.update_h_scrollbar();
+        let offset = self.state.borrow().h_offset;
+        self.set_h_offset(offset);
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn visit_children(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        f(&self.list);
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("columns", self.state.borrow().columns.len().to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let (_, height) = self.list.preferred_size();
+        (self.state.borrow().total_width(), height + HEADER_HEIGHT + SCROLLBAR_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::event::ModifiersState;
+
+    struct Names(Vec<&'static str>);
+
+    impl TableSource for Names {
+        fn row_count(&self) -> usize {
+            self.0.len()
+        }
+
+        fn cell(&self, row: usize, _column: usize) -> CellValue {
+            CellValue::Text(self.0[row].to_string())
+        }
+    }
+
+    fn table(names: Vec<&'static str>) -> Table<Names> {
+        let mut table = Table::new(Names(names), vec![Column::text("Name", 0, 120.0)])
+            .with_selection_mode(SelectionMode::Multiple);
+        table.layout(Rect::from_wh(200.0, 300.0));
+        table
+    }
+
+    // Clicks the middle of display row `row`.
+    fn click_row(table: &mut Table<Names>, row: usize) {
+        table.on_event(&Event::MouseDown {
+            x: 20.0,
+            y: HEADER_HEIGHT + ROW_HEIGHT * (row as f32 + 0.5),
+            button: MouseButton::Left,
+            modifiers: ModifiersState::empty(),
+        });
+    }
+
+    fn selected_names(table: &Table<Names>) -> Vec<&'static str> {
+        let rows: Vec<usize> = table.selection().selected().collect();
+        rows.into_iter()
+            .map(|row| table.with_source(|names| names.0[table.source_row(row).unwrap()]))
+            .collect()
+    }
+
+    #[test]
+    fn selection_follows_its_row_through_a_sort() {
+        let mut table = table(vec!["pear", "apple", "fig"]);
+        click_row(&mut table, 0);
+        assert_eq!(selected_names(&table), vec!["pear"]);
+
+        table.sort_by(0, SortDirection::Ascending);
+        assert_eq!(selected_names(&table), vec!["pear"]);
+        assert_eq!(table.selection().focus(), Some(2));
+
+        table.sort_by(0, SortDirection::Descending);
+        assert_eq!(selected_names(&table), vec!["pear"]);
+        assert_eq!(table.selection().focus(), Some(0));
+    }
+
+    #[test]
+    fn selection_follows_its_row_through_an_update() {
+        let mut table = table(vec!["pear", "apple", "fig"]);
+        table.sort_by(0, SortDirection::Ascending);
+        click_row(&mut table, 1);
+        assert_eq!(selected_names(&table), vec!["fig"]);
+
+        table.update_source(|names| names.0.push("banana"));
+        assert_eq!(selected_names(&table), vec!["fig"]);
+        assert_eq!(table.selection().focus(), Some(2));
+    }
+
+    #[test]
+    fn removed_rows_drop_out_of_the_selection() {
+        let mut table = table(vec!["pear", "apple", "fig"]);
+        click_row(&mut table, 2);
+        table.update_source(|names| {
+            names.0.pop();
+        });
+        assert!(selected_names(&table).is_empty());
+    }
+}
@@ -0,0 +1,541 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Point, Rect};
+use winit::event::{MouseButton, VirtualKeyCode};
+
+const STRIP_HEIGHT: f32 = 32.0;
+const TAB_PADDING: f32 = 12.0;
+const MIN_TAB_WIDTH: f32 = 64.0;
+const MAX_TAB_WIDTH: f32 = 220.0;
+const CLOSE_SIZE: f32 = 16.0;
+const DRAG_THRESHOLD: f32 = 5.0;
+
+struct Tab {
+    title: String,
+    content: Box<dyn Widget>,
+}
+
+// Where a press on the strip landed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Press {
+    Tab { index: usize, x: f32 },
+    Close(usize),
+}
+
+// A strip of tabs above a content area showing the current tab's widget.
+// Tabs can be dragged to reorder them and, if closable, closed with their
+// button or a middle click.
+pub struct TabView {
+    tabs: Vec<Tab>,
+    current: usize,
+    closable: bool,
+    rect: Rect,
+    hovered: Option<usize>,
+    hovered_close: Option<usize>,
+    press: Option<Press>,
+    // Tab being dragged and the pointer's x offset into it.
+    drag: Option<(usize, f32, f32)>,
+    on_changed: Option<Box<dyn FnMut(usize)>>,
+    on_close_requested: Option<Box<dyn FnMut(usize) -> bool>>,
+    on_moved: Option<Box<dyn FnMut(usize, usize)>>,
+}
+
+impl Default for TabView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TabView {
+    pub fn new() -> Self {
+        Self {
+            tabs: Vec::new(),
+            current: 0,
+            closable: false,
+            rect: Rect::default(),
+            hovered: None,
+            hovered_close: None,
+            press: None,
+            drag: None,
+            on_changed: None,
+            on_close_requested: None,
+            on_moved: None,
+        }
+    }
+
+    pub fn tab(mut self, title: &str, content: Box<dyn Widget>) -> Self {
+        self.add_tab(title, content);
+        self
+    }
+
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    pub fn on_changed<F: FnMut(usize) + 'static>(mut self, f: F) -> Self {
+        self.on_changed = Some(Box::new(f));
+        self
+    }
+
+    // Asked before a tab closes; returning false keeps it open (e.g. to ask
+    // about unsaved changes first).
+    pub fn on_close_requested<F: FnMut(usize) -> bool + 'static>(mut self, f: F) -> Self {
+        self.on_close_requested = Some(Box::new(f));
+        self
+    }
+
+    // Called with (from, to) after the user drags a tab to a new place.
+    pub fn on_moved<F: FnMut(usize, usize) + 'static>(mut self, f: F) -> Self {
+        self.on_moved = Some(Box::new(f));
+        self
+    }
+
+    pub fn add_tab(&mut self, title: &str, content: Box<dyn Widget>) -> usize {
+        self.tabs.push(Tab {
+            title: title.to_string(),
+            content,
+        });
+        self.layout(self.rect);
+        self.tabs.len() - 1
+    }
+
+    pub fn remove_tab(&mut self, index: usize) -> Box<dyn Widget> {
+        let tab = self.tabs.remove(index);
+        if self.current > index || self.current >= self.tabs.len() {
+            self.current = self.current.saturating_sub(1);
+        }
+        self.hovered = None;
+        self.hovered_close = None;
+        self.layout(self.rect);
+        context::request_redraw();
+        tab.content
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn set_current(&mut self, index: usize) {
+        if index < self.tabs.len() {
+            self.current = index;
+            self.layout(self.rect);
+            context::request_redraw();
+        }
+    }
+
+    pub fn set_title(&mut self, index: usize, title: &str) {
+        self.tabs[index].title = title.to_string();
+        context::request_redraw();
+    }
+
+    pub fn content_mut(&mut self, index: usize) -> &mut dyn Widget {
+        self.tabs[index].content.as_mut()
+    }
+
+    fn select(&mut self, index: usize) {
+        if index == self.current {
+            return;
+        }
+        self.set_current(index);
+        if let Some(on_changed) = &mut self.on_changed {
+            on_changed(index);
+        }
+    }
+
+    fn request_close(&mut self, index: usize) {
+        let allowed = match &mut self.on_close_requested {
+            Some(on_close_requested) => on_close_requested(index),
+            None => true,
+        };
+        if allowed {
+            let was_current = index == self.current;
+            self.remove_tab(index);
+            if was_current && !self.tabs.is_empty() {
+                if let Some(on_changed) = &mut self.on_changed {
+                    on_changed(self.current);
+                }
+            }
+        }
+    }
+
+    fn strip_rect(&self) -> Rect {
+        Rect::from_xywh(self.rect.left, self.rect.top, self.rect.width(), STRIP_HEIGHT)
+    }
+
+    fn content_rect(&self) -> Rect {
+        Rect::from_ltrb(self.rect.left, self.rect.top + STRIP_HEIGHT, self.rect.right, self.rect.bottom)
+    }
+
+    // Tabs size to their titles, then shrink evenly if they don't all fit.
+    fn tab_widths(&self) -> Vec<f32> {
+        let font = theme::current().font();
+        let close = if self.closable { CLOSE_SIZE + 4.0 } else { 0.0 };
+        let mut widths: Vec<f32> = self
+            .tabs
+            .iter()
+            .map(|tab| {
+                let width = font.measure_str(&tab.title, None).0 + TAB_PADDING * 2.0 + close;
+                width.clamp(MIN_TAB_WIDTH, MAX_TAB_WIDTH)
+            })
+            .collect();
+        let total: f32 = widths.iter().sum();
+        if total > self.rect.width() && total > 0.0 {
+            let scale = self.rect.width() / total;
+            for width in &mut widths {
+                *width = (*width * scale).max(MIN_TAB_WIDTH.min(*width));
+            }
+        }
+        widths
+    }
+
+    fn tab_rects(&self) -> Vec<Rect> {
+        let mut x = self.rect.left;
+        self.tab_widths()
+            .into_iter()
+            .map(|width| {
+                let rect = Rect::from_xywh(x, self.rect.top, width, STRIP_HEIGHT);
+                x += width;
+                rect
+            })
+            .collect()
+    }
+
+    fn close_rect(tab: Rect) -> Rect {
+        Rect::from_xywh(
+            tab.right - TAB_PADDING / 2.0 - CLOSE_SIZE,
+            tab.center_y() - CLOSE_SIZE / 2.0,
+            CLOSE_SIZE,
+            CLOSE_SIZE,
+        )
+    }
+
+    fn tab_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.tab_rects().iter().position(|rect| rect.contains(Point::new(x, y)))
+    }
+
+    fn close_at(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.closable {
+            return None;
+        }
+        self.tab_rects()
+            .iter()
+            .position(|rect| Self::close_rect(*rect).contains(Point::new(x, y)))
+    }
+
+    // Moves the dragged tab to the slot under its middle.
+    fn drag_to(&mut self, x: f32) {
+        let (from, grab, _) = match self.drag {
+            Some(drag) => drag,
+            None => return,
+        };
+        let rects = self.tab_rects();
+        let center = x - grab + rects[from].width() / 2.0;
+        let to = rects
+            .iter()
+            .position(|rect| center < rect.right)
+            .unwrap_or(rects.len() - 1);
+        if to != from {
+            let tab = self.tabs.remove(from);
+            self.tabs.insert(to, tab);
+            if self.current == from {
+                self.current = to;
+            } else if from < self.current && to >= self.current {
+                self.current -= 1;
+            } else if from > self.current && to <= self.current {
+                self.current += 1;
+            }
+            if let Some(on_moved) = &mut self.on_moved {
+                on_moved(from, to);
+            }
+        }
+        self.drag = Some((to, grab, x));
+        context::request_redraw();
+    }
+
+    fn draw_tab(&self, canvas: &mut Canvas, index: usize, rect: Rect) {
+        let theme = theme::current();
+        let current = index == self.current;
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(if current {
+            theme.background
+        } else if self.hovered == Some(index) {
+            theme.surface_hover
+        } else {
+            theme.surface
+        });
+        canvas.draw_rect(rect, &paint);
+
+        paint.set_style(PaintStyle::Stroke);
+        paint.set_stroke_width(1.0);
+        paint.set_color(theme.border);
+        canvas.draw_line((rect.right - 0.5, rect.top + 6.0), (rect.right - 0.5, rect.bottom - 6.0), &paint);
+        if current {
+            paint.set_color(theme.accent);
+            paint.set_stroke_width(2.0);
+            canvas.draw_line((rect.left, rect.top + 1.0), (rect.right, rect.top + 1.0), &paint);
+        }
+
+        let font = theme.font();
+        let close = self.closable && (current || self.hovered == Some(index));
+        let text_right = if self.closable { Self::close_rect(rect).left - 4.0 } else { rect.right - TAB_PADDING };
+        let mut text_paint = Paint::default();
+        text_paint.set_anti_alias(true);
+        text_paint.set_color(if current { theme.foreground } else { theme.disabled_foreground });
+        canvas.save();
+        canvas.clip_rect(Rect::from_ltrb(rect.left, rect.top, text_right, rect.bottom), None, Some(true));
+        let baseline = rect.center_y() + font.size() / 3.0;
+        canvas.draw_str(&self.tabs[index].title, (rect.left + TAB_PADDING, baseline), &font, &text_paint);
+        canvas.restore();
+
+        if close {
+            let bx = Self::close_rect(rect);
+            if self.hovered_close == Some(index) {
+                paint.set_style(PaintStyle::Fill);
+                paint.set_color(theme.surface_pressed);
+                canvas.draw_round_rect(bx, 3.0, 3.0, &paint);
+            }
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_stroke_width(1.5);
+            paint.set_color(theme.foreground);
+            let inset = bx.with_inset((4.5, 4.5));
+            canvas.draw_line((inset.left, inset.top), (inset.right, inset.bottom), &paint);
+            canvas.draw_line((inset.right, inset.top), (inset.left, inset.bottom), &paint);
+        }
+    }
+}
+
+impl Widget for TabView {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let theme = theme::current();
+        let strip = self.strip_rect();
+        let mut paint = Paint::default();
+        paint.set_color(theme.surface);
+        canvas.draw_rect(strip, &paint);
+
+        canvas.save();
+        canvas.clip_rect(strip, None, Some(true));
+        let rects = self.tab_rects();
+        let dragged = self.drag.map(|(index, _, _)| index);
+        for (index, rect) in rects.iter().enumerate() {
+            if dragged != Some(index) {
+                self.draw_tab(canvas, index, *rect);
+            }
+        }
+        // The dragged tab follows the pointer, drawn over its neighbours.
+        if let Some((index, grab, x)) = self.drag {
+            let rect = rects[index];
+            self.draw_tab(canvas, index, Rect::from_xywh(x - grab, rect.top, rect.width(), rect.height()));
+        }
+        canvas.restore();
+
+        paint.set_color(theme.border);
+        canvas.draw_line((strip.left, strip.bottom - 0.5), (strip.right, strip.bottom - 0.5), &paint);
+
+        let content = self.content_rect();
+        if let Some(tab) = self.tabs.get_mut(self.current) {
+            canvas.save();
+            canvas.clip_rect(content, None, Some(true));
+            tab.content.draw(canvas);
+            canvas.restore();
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        let in_strip = |x: f32, y: f32, strip: Rect| strip.contains(Point::new(x, y));
+        match event {
+            Event::MouseMove { x, y, .. } => {
+                if let Some(Press::Tab { index, x: start }) = self.press {
+                    if self.drag.is_none() && (x - start).abs() > DRAG_THRESHOLD {
+                        let grab = start - self.tab_rects()[index].left;
+                        self.drag = Some((index, grab, *x));
+                    }
+                }
+                if self.drag.is_some() {
+                    self.drag_to(*x);
+                    return true;
+                }
+                let hovered = self.tab_at(*x, *y);
+                let hovered_close = self.close_at(*x, *y);
+                if hovered != self.hovered || hovered_close != self.hovered_close {
+                    self.hovered = hovered;
+                    self.hovered_close = hovered_close;
+                    context::request_redraw();
+                }
+            }
+            Event::MouseDown { x, y, button, .. } if in_strip(*x, *y, self.strip_rect()) => {
+                match (button, self.close_at(*x, *y), self.tab_at(*x, *y)) {
+                    (MouseButton::Left, Some(index), _) => self.press = Some(Press::Close(index)),
+                    (MouseButton::Left, None, Some(index)) => {
+                        self.select(index);
+                        self.press = Some(Press::Tab { index, x: *x });
+                    }
+                    (MouseButton::Middle, _, Some(index)) if self.closable => self.request_close(index),
+                    _ => {}
+                }
+                return true;
+            }
+            Event::MouseUp { x, y, button: MouseButton::Left, .. } if self.press.is_some() => {
+                let press = self.press.take();
+                if self.drag.take().is_some() {
+                    self.layout(self.rect);
+                    context::request_redraw();
+                } else if let Some(Press::Close(index)) = press {
+                    if self.close_at(*x, *y) == Some(index) {
+                        self.request_close(index);
+                    }
+                }
+                return true;
+            }
+            Event::KeyDown { key: Some(VirtualKeyCode::Tab), modifiers } if modifiers.ctrl() && !self.tabs.is_empty() => {
+                let len = self.tabs.len();
+                let next = if modifiers.shift() { (self.current + len - 1) % len } else { (self.current + 1) % len };
+                self.select(next);
+                return true;
+            }
+            _ => {}
+        }
+
+        match self.tabs.get_mut(self.current) {
+            Some(tab) => tab.content.on_event(event),
+            None => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+        let content = self.content_rect();
+        if let Some(tab) = self.tabs.get_mut(self.current) {
+            tab.content.layout(content);
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn visit_children(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        if let Some(tab) = self.tabs.get(self.current) {
+            f(tab.content.as_ref());
+        }
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("tabs", self.tabs.len().to_string()),
+            ("current", self.current.to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let (width, height) = self
+            .tabs
+            .get(self.current)
+            .map_or((0.0, 0.0), |tab| tab.content.preferred_size());
+        (width, height + STRIP_HEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::label::Label;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use winit::event::ModifiersState;
+
+    // Four short titles, so every tab is MIN_TAB_WIDTH wide.
+    fn tabs() -> TabView {
+        let mut tabs = TabView::new();
+        for title in ["A", "B", "C", "D"] {
+            tabs.add_tab(title, Box::new(Label::new(title)));
+        }
+        tabs.layout(Rect::from_wh(400.0, 200.0));
+        tabs
+    }
+
+    fn titles(tabs: &TabView) -> Vec<&str> {
+        tabs.tabs.iter().map(|tab| tab.title.as_str()).collect()
+    }
+
+    // Drags tab `from` so its middle lands on `x`, as if grabbed in its middle.
+    fn drag(tabs: &mut TabView, from: usize, x: f32) {
+        tabs.drag = Some((from, MIN_TAB_WIDTH / 2.0, 0.0));
+        tabs.drag_to(x);
+    }
+
+    #[test]
+    fn dragging_a_tab_moves_it_and_reports_the_move() {
+        let moves = Rc::new(RefCell::new(Vec::new()));
+        let recorded = moves.clone();
+        let mut tabs = tabs().on_moved(move |from, to| recorded.borrow_mut().push((from, to)));
+        let modifiers = ModifiersState::empty();
+        tabs.on_event(&Event::MouseDown { x: 32.0, y: 16.0, button: MouseButton::Left, modifiers });
+        tabs.on_event(&Event::MouseMove { x: 150.0, y: 16.0, modifiers });
+        tabs.on_event(&Event::MouseUp { x: 150.0, y: 16.0, button: MouseButton::Left, modifiers });
+
+        assert_eq!(titles(&tabs), vec!["B", "C", "A", "D"]);
+        assert_eq!(tabs.current(), 2);
+        assert_eq!(*moves.borrow(), vec![(0, 2)]);
+        assert!(tabs.drag.is_none());
+    }
+
+    #[test]
+    fn moving_a_tab_past_the_current_one_shifts_it_back() {
+        let mut tabs = tabs();
+        tabs.set_current(2);
+        drag(&mut tabs, 0, 240.0);
+        assert_eq!(titles(&tabs), vec!["B", "C", "D", "A"]);
+        assert_eq!(tabs.current(), 1);
+    }
+
+    #[test]
+    fn moving_a_tab_in_front_of_the_current_one_shifts_it_on() {
+        let mut tabs = tabs();
+        tabs.set_current(1);
+        drag(&mut tabs, 3, 10.0);
+        assert_eq!(titles(&tabs), vec!["D", "A", "B", "C"]);
+        assert_eq!(tabs.current(), 2);
+    }
+
+    #[test]
+    fn moves_that_stay_on_one_side_leave_the_current_tab_alone() {
+        let mut tabs = tabs();
+        tabs.set_current(3);
+        drag(&mut tabs, 0, 100.0);
+        assert_eq!(titles(&tabs), vec!["B", "A", "C", "D"]);
+        assert_eq!(tabs.current(), 3);
+    }
+
+    #[test]
+    fn dragging_past_the_last_tab_drops_it_at_the_end() {
+        let mut tabs = tabs();
+        drag(&mut tabs, 1, 390.0);
+        assert_eq!(titles(&tabs), vec!["A", "C", "D", "B"]);
+        assert_eq!(tabs.drag.map(|(index, _, _)| index), Some(3));
+    }
+
+    #[test]
+    fn removing_a_tab_before_the_current_one_keeps_it_current() {
+        let mut tabs = tabs();
+        tabs.set_current(2);
+        tabs.remove_tab(0);
+        assert_eq!(titles(&tabs)[tabs.current()], "C");
+        tabs.set_current(2);
+        tabs.remove_tab(2);
+        assert_eq!(titles(&tabs)[tabs.current()], "C");
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+// Shared by the widget tests: draws a widget into memory so its pixels can
+// be checked, and builds the input events they feed it.
+
+use crate::event::Event;
+use crate::renderer::{MemoryBackend, RenderBackend};
+use crate::widget::Widget;
+use skia_safe::Rect;
+use winit::event::{ModifiersState, MouseButton};
+
+// Lays `widget` out in `rect` and draws one frame of it.
+pub(crate) fn render(widget: &mut dyn Widget, rect: Rect) -> MemoryBackend {
+    let mut backend = MemoryBackend::new(240, 80);
+    backend.begin_frame();
+    widget.layout(rect);
+    widget.draw(backend.canvas());
+    backend.present();
+    backend
+}
+
+pub(crate) fn press(x: f32, y: f32) -> Event {
+    Event::MouseDown {
+        x,
+        y,
+        button: MouseButton::Left,
+        modifiers: ModifiersState::empty(),
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::event::Event;
+use skia_safe::{Canvas, Color, Font, Paint, Rect};
+use winit::event::VirtualKeyCode;
+
+// Single-line text editing state shared by the widgets that take typed input.
+// The cursor is a byte offset that always sits on a char boundary.
+#[derive(Debug, Clone, Default)]
+pub struct TextBuffer {
+    text: String,
+    cursor: usize,
+}
+
+impl TextBuffer {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            cursor: text.len(),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.cursor = self.text.len();
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.text.remove(self.cursor);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if let Some(c) = self.text[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    // Returns true if the text or cursor changed. Enter, Escape and Tab are
+    // left for the owning widget.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::CharInput(c) if !c.is_control() => {
+                self.insert(*c);
+                true
+            }
+            Event::KeyDown { key: Some(key), .. } => match key {
+                VirtualKeyCode::Back => {
+                    self.backspace();
+                    true
+                }
+                VirtualKeyCode::Delete => {
+                    self.delete();
+                    true
+                }
+                VirtualKeyCode::Left => {
+                    self.move_left();
+                    true
+                }
+                VirtualKeyCode::Right => {
+                    self.move_right();
+                    true
+                }
+                VirtualKeyCode::Home => {
+                    self.cursor = 0;
+                    true
+                }
+                VirtualKeyCode::End => {
+                    self.cursor = self.text.len();
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    // Draws the text left-aligned and vertically centred in `rect`, with a
+    // caret when focused.
+    pub fn draw(&self, canvas: &mut Canvas, rect: Rect, font: &Font, color: Color, focused: bool) {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(color);
+
+        let baseline = rect.top + rect.height() / 2.0 + font.size() / 3.0;
+
+        canvas.save();
+        canvas.clip_rect(rect, None, Some(true));
+        canvas.draw_str(&self.text, (rect.left, baseline), font, &paint);
+
+        if focused {
+            let caret_x = rect.left + font.measure_str(&self.text[..self.cursor], Some(&paint)).0;
+            paint.set_stroke_width(1.0);
+            canvas.draw_line(
+                (caret_x, baseline - font.size() * 0.8),
+                (caret_x, baseline + font.size() * 0.2),
+                &paint,
+            );
+        }
+        canvas.restore();
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use skia_safe::{Canvas, Color, Font, Paint, PaintStyle, Rect, Typeface};
+use std::cell::RefCell;
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub background: Color,
+    pub surface: Color,
+    pub surface_hover: Color,
+    pub surface_pressed: Color,
+    pub border: Color,
+    pub foreground: Color,
+    pub disabled_foreground: Color,
+    pub accent: Color,
+    pub accent_foreground: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub backdrop: Color,
+    pub corner_radius: f32,
+    pub font_size: f32,
+}
+
+impl Default for Theme {
+    // Same palette `Renderer::draw_button` has always used.
+    fn default() -> Self {
+        Self {
+            background: Color::WHITE,
+            surface: Color::from_rgb(230, 230, 230),
+            surface_hover: Color::from_rgb(240, 240, 240),
+            surface_pressed: Color::from_rgb(180, 180, 180),
+            border: Color::from_rgb(160, 160, 160),
+            foreground: Color::BLACK,
+            disabled_foreground: Color::from_rgb(150, 150, 150),
+            accent: Color::from_rgb(0, 122, 255),
+            accent_foreground: Color::WHITE,
+            success: Color::from_rgb(52, 199, 89),
+            warning: Color::from_rgb(255, 149, 0),
+            error: Color::from_rgb(255, 59, 48),
+            backdrop: Color::from_argb(100, 0, 0, 0),
+            corner_radius: 6.0,
+            font_size: 16.0,
+        }
+    }
+}
+
+impl Theme {
+    pub fn font(&self) -> Font {
+        Font::new(Typeface::default(), self.font_size)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Theme> = RefCell::new(Theme::default());
+}
+
+pub fn current() -> Theme {
+    CURRENT.with(|theme| theme.borrow().clone())
+}
+
+pub fn set_theme(theme: Theme) {
+    CURRENT.with(|current| *current.borrow_mut() = theme);
+}
+
+// "#rrggbbaa", for debugging tools.
+pub fn color_hex(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}{:02x}", color.r(), color.g(), color.b(), color.a())
+}
+
+// Outline drawn around the control that has keyboard focus.
+pub fn draw_focus_ring(canvas: &mut Canvas, rect: Rect, radius: f32) {
+    let theme = current();
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+    paint.set_style(PaintStyle::Stroke);
+    paint.set_stroke_width(2.0);
+    paint.set_color(theme.accent);
+    canvas.draw_round_rect(rect.with_outset((2.0, 2.0)), radius + 2.0, radius + 2.0, &paint);
+}
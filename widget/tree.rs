@@ -0,0 +1,642 @@
+// Copyright 2025 Bluegill Studios.
+// Licensed under the GNU General Public License v2.0.
+
+use crate::context;
+use crate::event::Event;
+use crate::widget::scroll::{scroll_amount, Axis, Scrollbar, SCROLLBAR_SIZE};
+use crate::widget::selection::{Selection, SelectionMode};
+use crate::widget::{theme, Widget};
+use skia_safe::{Canvas, Paint, PaintStyle, Path, Point, Rect};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
+
+const ROW_HEIGHT: f32 = 24.0;
+// Width of one indentation level, which is also the disclosure triangle's
+// hit area.
+const INDENT: f32 = 18.0;
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+const DRAG_THRESHOLD: f32 = 5.0;
+
+type SelectionChanged<Id> = Box<dyn FnMut(&[Id])>;
+type Activate<Id> = Box<dyn FnMut(&Id)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropPosition {
+    Before,
+    After,
+    // Becomes a child of the target.
+    Into,
+}
+
+// Supplies the nodes of a `TreeView`. Children are only asked for when a
+// node is first expanded, so large or slow hierarchies (file systems) load
+// as the user opens them.
+pub trait TreeProvider {
+    type Id: Clone + Eq + Hash;
+
+    fn roots(&mut self) -> Vec<Self::Id>;
+    fn has_children(&self, id: &Self::Id) -> bool;
+    fn children(&mut self, id: &Self::Id) -> Vec<Self::Id>;
+    fn label(&self, id: &Self::Id) -> String;
+
+    // Drag-to-reorder hooks. Dragging is off unless `can_drop` says yes for
+    // some target. `drop` returns true if the tree changed and needs reloading.
+    fn can_drop(&self, _dragged: &[Self::Id], _target: &Self::Id, _position: DropPosition) -> bool {
+        false
+    }
+
+    fn drop(&mut self, _dragged: Vec<Self::Id>, _target: &Self::Id, _position: DropPosition) -> bool {
+        false
+    }
+}
+
+// One visible row of the flattened tree.
+struct TreeRow<Id> {
+    id: Id,
+    depth: usize,
+    parent: Option<usize>,
+    expandable: bool,
+}
+
+pub struct TreeView<P: TreeProvider> {
+    provider: P,
+    roots: Option<Vec<P::Id>>,
+    children: HashMap<P::Id, Vec<P::Id>>,
+    expanded: HashSet<P::Id>,
+    rows: Vec<TreeRow<P::Id>>,
+    selection: Selection,
+    offset: f32,
+    rect: Rect,
+    viewport: Rect,
+    scrollbar: Scrollbar,
+    focused: bool,
+    last_click: Option<(usize, Instant)>,
+    // Row and position of a press that may turn into a drag. Clicking an
+    // already selected row only changes the selection on release, so a
+    // multi-row selection can be dragged.
+    press: Option<(usize, f32, f32, bool)>,
+    dragging: bool,
+    drop_target: Option<(usize, DropPosition)>,
+    on_selection_changed: Option<SelectionChanged<P::Id>>,
+    on_activate: Option<Activate<P::Id>>,
+}
+
+impl<P: TreeProvider> TreeView<P> {
+    pub fn new(provider: P) -> Self {
+        let mut tree = Self {
+            provider,
+            roots: None,
+            children: HashMap::new(),
+            expanded: HashSet::new(),
+            rows: Vec::new(),
+            selection: Selection::new(SelectionMode::Single),
+            offset: 0.0,
+            rect: Rect::default(),
+            viewport: Rect::default(),
+            scrollbar: Scrollbar::new(Axis::Vertical),
+            focused: false,
+            last_click: None,
+            press: None,
+            dragging: false,
+            drop_target: None,
+            on_selection_changed: None,
+            on_activate: None,
+        };
+        tree.rebuild();
+        tree
+    }
+
+    pub fn with_selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.selection.set_mode(mode);
+        self
+    }
+
+    pub fn on_selection_changed<F: FnMut(&[P::Id]) + 'static>(mut self, f: F) -> Self {
+        self.on_selection_changed = Some(Box::new(f));
+        self
+    }
+
+    // Enter on the focused node, or a double-click.
+    pub fn on_activate<F: FnMut(&P::Id) + 'static>(mut self, f: F) -> Self {
+        self.on_activate = Some(Box::new(f));
+        self
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    pub fn provider_mut(&mut self) -> &mut P {
+        &mut self.provider
+    }
+
+    pub fn selected(&self) -> Vec<P::Id> {
+        self.selection.selected().map(|i| self.rows[i].id.clone()).collect()
+    }
+
+    pub fn focused_node(&self) -> Option<&P::Id> {
+        self.selection.focus().map(|i| &self.rows[i].id)
+    }
+
+    pub fn is_expanded(&self, id: &P::Id) -> bool {
+        self.expanded.contains(id)
+    }
+
+    pub fn expand(&mut self, id: &P::Id) {
+        if self.expanded.insert(id.clone()) {
+            self.rebuild();
+        }
+    }
+
+    pub fn collapse(&mut self, id: &P::Id) {
+        if !self.expanded.remove(id) {
+            return;
+        }
+        // Focus inside the collapsed subtree moves up to the node itself.
+        let node = self.row_of(id);
+        let hidden_focus = match (node, self.selection.focus()) {
+            (Some(node), Some(focus)) => self.is_descendant(focus, node),
+            _ => false,
+        };
+        self.rebuild();
+        if let (true, Some(node)) = (hidden_focus, self.row_of(id)) {
+            self.selection.select(node);
+            self.selection_changed();
+        }
+    }
+
+    pub fn toggle(&mut self, id: &P::Id) {
+        if self.is_expanded(id) {
+            self.collapse(id);
+        } else {
+            self.expand(id);
+        }
+    }
+
+    // Forgets every loaded node and asks the provider again. Expanded nodes
+    // that still exist stay expanded.
+    pub fn reload(&mut self) {
+        self.roots = None;
+        self.children.clear();
+        self.rebuild();
+    }
+
+    // Reloads just the children of `id`, e.g. after a directory changed.
+    pub fn reload_children(&mut self, id: &P::Id) {
+        self.children.remove(id);
+        self.rebuild();
+    }
+
+    pub fn scroll_to(&mut self, id: &P::Id) {
+        if let Some(index) = self.row_of(id) {
+            self.scroll_to_index(index);
+        }
+    }
+
+    fn row_of(&self, id: &P::Id) -> Option<usize> {
+        self.rows.iter().position(|row| row.id == *id)
+    }
+
+    fn is_descendant(&self, row: usize, ancestor: usize) -> bool {
+        let mut current = self.rows[row].parent;
+        while let Some(parent) = current {
+            if parent == ancestor {
+                return true;
+            }
+            current = self.rows[parent].parent;
+        }
+        false
+    }
+
+    fn load_children(&mut self, id: &P::Id) -> Vec<P::Id> {
+        let provider = &mut self.provider;
+        self.children.entry(id.clone()).or_insert_with(|| provider.children(id)).clone()
+    }
+
+    fn push_rows(&mut self, rows: &mut Vec<TreeRow<P::Id>>, ids: &[P::Id], depth: usize, parent: Option<usize>) {
+        for id in ids {
+            let expandable = self.provider.has_children(id);
+            let index = rows.len();
+            rows.push(TreeRow {
+                id: id.clone(),
+                depth,
+                parent,
+                expandable,
+            });
+            if expandable && self.expanded.contains(id) {
+                let children = self.load_children(id);
+                self.push_rows(rows, &children, depth + 1, Some(index));
+            }
+        }
+    }
+
+    // Flattens the expanded part of the tree into rows, carrying the
+    // selection over by node id.
+    fn rebuild(&mut self) {
+        let roots = match &self.roots {
+            Some(roots) => roots.clone(),
+            None => {
+                let roots = self.provider.roots();
+                self.roots = Some(roots.clone());
+                roots
+            }
+        };
+        let mut rows = Vec::new();
+        self.push_rows(&mut rows, &roots, 0, None);
+
+        let positions: HashMap<&P::Id, usize> = rows.iter().enumerate().map(|(i, row)| (&row.id, i)).collect();
+        let old = &self.rows;
+        self.selection.remap(|i| old.get(i).and_then(|row| positions.get(&row.id)).copied());
+
+        self.rows = rows;
+        self.press = None;
+        self.drop_target = None;
+        self.update_layout();
+        context::request_redraw();
+    }
+
+    fn scroll_to_index(&mut self, index: usize) {
+        let top = index as f32 * ROW_HEIGHT;
+        if top < self.offset {
+            self.offset = top;
+        } else if top + ROW_HEIGHT > self.offset + self.viewport.height() {
+            self.offset = top + ROW_HEIGHT - self.viewport.height();
+        }
+        self.update_layout();
+    }
+
+    fn set_offset(&mut self, offset: f32) {
+        self.offset = offset;
+        self.update_layout();
+        context::request_redraw();
+    }
+
+    fn total_height(&self) -> f32 {
+        self.rows.len() as f32 * ROW_HEIGHT
+    }
+
+    fn update_layout(&mut self) {
+        let max = (self.total_height() - self.viewport.height()).max(0.0);
+        self.offset = self.offset.clamp(0.0, max);
+        self.scrollbar.update(
+            Rect::from_ltrb(self.viewport.right, self.rect.top, self.rect.right, self.rect.bottom),
+            self.viewport.height(),
+            self.total_height(),
+        );
+    }
+
+    fn row_rect(&self, index: usize) -> Rect {
+        Rect::from_xywh(
+            self.viewport.left,
+            self.viewport.top + index as f32 * ROW_HEIGHT - self.offset,
+            self.viewport.width(),
+            ROW_HEIGHT,
+        )
+    }
+
+    fn row_at(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.viewport.contains(Point::new(x, y)) {
+            return None;
+        }
+        let index = ((y - self.viewport.top + self.offset) / ROW_HEIGHT) as usize;
+        (index < self.rows.len()).then_some(index)
+    }
+
+    fn indent_of(&self, index: usize) -> f32 {
+        self.viewport.left + self.rows[index].depth as f32 * INDENT
+    }
+
+    fn selection_changed(&mut self) {
+        if let Some(on_selection_changed) = &mut self.on_selection_changed {
+            let ids: Vec<P::Id> = self.selection.selected().map(|i| self.rows[i].id.clone()).collect();
+            on_selection_changed(&ids);
+        }
+        context::request_redraw();
+    }
+
+    fn move_to(&mut self, index: usize, extend: bool) {
+        if self.selection.move_focus(index, extend) {
+            self.selection_changed();
+        }
+        self.scroll_to_index(index);
+    }
+
+    fn activate(&mut self, index: usize) {
+        if let Some(on_activate) = &mut self.on_activate {
+            on_activate(&self.rows[index].id);
+        }
+    }
+
+    fn on_key(&mut self, key: VirtualKeyCode, modifiers: ModifiersState) -> bool {
+        if self.rows.is_empty() {
+            return false;
+        }
+        let current = self.selection.focus();
+        let last = self.rows.len() - 1;
+        let page = (self.viewport.height() / ROW_HEIGHT).max(1.0) as usize;
+        let target = match key {
+            VirtualKeyCode::Up => current.map(|i| i.saturating_sub(1)).unwrap_or(0),
+            VirtualKeyCode::Down => current.map(|i| (i + 1).min(last)).unwrap_or(0),
+            VirtualKeyCode::PageUp => current.unwrap_or(0).saturating_sub(page),
+            VirtualKeyCode::PageDown => (current.unwrap_or(0) + page).min(last),
+            VirtualKeyCode::Home => 0,
+            VirtualKeyCode::End => last,
+            VirtualKeyCode::Left => {
+                let index = match current {
+                    Some(index) => index,
+                    None => return false,
+                };
+                let id = self.rows[index].id.clone();
+                if self.rows[index].expandable && self.is_expanded(&id) {
+                    self.collapse(&id);
+                } else if let Some(parent) = self.rows[index].parent {
+                    self.move_to(parent, false);
+                }
+                return true;
+            }
+            VirtualKeyCode::Right => {
+                let index = match current {
+                    Some(index) => index,
+                    None => return false,
+                };
+                let id = self.rows[index].id.clone();
+                if !self.rows[index].expandable {
+                    return true;
+                }
+                if !self.is_expanded(&id) {
+                    self.expand(&id);
+                } else if self.rows.get(index + 1).is_some_and(|row| row.parent == Some(index)) {
+                    self.move_to(index + 1, false);
+                }
+                return true;
+            }
+            VirtualKeyCode::A if modifiers.ctrl() => {
+                self.selection.select_all(self.rows.len());
+                self.selection_changed();
+                return true;
+            }
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                if let Some(index) = current {
+                    self.activate(index);
+                }
+                return current.is_some();
+            }
+            _ => return false,
+        };
+        self.move_to(target, modifiers.shift());
+        true
+    }
+
+    // Where dropping at `y` over row `index` would put the dragged nodes.
+    fn drop_target_at(&self, index: usize, y: f32) -> Option<(usize, DropPosition)> {
+        let rect = self.row_rect(index);
+        let fraction = (y - rect.top) / rect.height();
+        let position = if fraction < 0.25 {
+            DropPosition::Before
+        } else if fraction > 0.75 {
+            DropPosition::After
+        } else {
+            DropPosition::Into
+        };
+
+        // Nothing can be dropped onto or inside itself.
+        let dragged: Vec<usize> = self.selection.selected().collect();
+        if dragged.iter().any(|d| *d == index || self.is_descendant(index, *d)) {
+            return None;
+        }
+        let ids: Vec<P::Id> = dragged.iter().map(|i| self.rows[*i].id.clone()).collect();
+        self.provider
+            .can_drop(&ids, &self.rows[index].id, position)
+            .then_some((index, position))
+    }
+
+    fn finish_drag(&mut self) {
+        let (index, position) = match self.drop_target.take() {
+            Some(target) => target,
+            None => return,
+        };
+        let dragged = self.selected();
+        let target = self.rows[index].id.clone();
+        if self.provider.drop(dragged, &target, position) {
+            if position == DropPosition::Into {
+                self.expanded.insert(target);
+            }
+            self.reload();
+        }
+    }
+
+    fn draw_row(&self, canvas: &mut Canvas, index: usize, label: &str) {
+        let theme = theme::current();
+        let row = &self.rows[index];
+        let rect = self.row_rect(index);
+        let selected = self.selection.is_selected(index);
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+
+        if selected {
+            paint.set_color(if self.focused { theme.accent } else { theme.surface_pressed });
+            canvas.draw_rect(rect, &paint);
+        }
+
+        // Indentation guides, one per ancestor level.
+        paint.set_color(theme.surface);
+        paint.set_stroke_width(1.0);
+        for depth in 0..row.depth {
+            let x = (rect.left + depth as f32 * INDENT + INDENT / 2.0).floor() + 0.5;
+            canvas.draw_line((x, rect.top), (x, rect.bottom), &paint);
+        }
+
+        let indent = self.indent_of(index);
+        let foreground = if selected && self.focused { theme.accent_foreground } else { theme.foreground };
+        if row.expandable {
+            let (cx, cy) = (indent + INDENT / 2.0, rect.center_y());
+            let mut path = Path::new();
+            if self.expanded.contains(&row.id) {
+                path.move_to((cx - 4.0, cy - 2.0));
+                path.line_to((cx + 4.0, cy - 2.0));
+                path.line_to((cx, cy + 3.0));
+            } else {
+                path.move_to((cx - 2.0, cy - 4.0));
+                path.line_to((cx + 3.0, cy));
+                path.line_to((cx - 2.0, cy + 4.0));
+            }
+            path.close();
+            paint.set_color(foreground);
+            canvas.draw_path(&path, &paint);
+        }
+
+        let font = theme.font();
+        paint.set_color(foreground);
+        let baseline = rect.center_y() + font.size() / 3.0;
+        canvas.draw_str(label, (indent + INDENT + 2.0, baseline), &font, &paint);
+
+        if self.focused && self.selection.focus() == Some(index) {
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_color(theme.border);
+            canvas.draw_rect(rect.with_inset((0.5, 0.5)), &paint);
+        }
+    }
+
+    fn draw_drop_indicator(&self, canvas: &mut Canvas) {
+        let (index, position) = match self.drop_target {
+            Some(target) => target,
+            None => return,
+        };
+        let theme = theme::current();
+        let rect = self.row_rect(index);
+        let left = self.indent_of(index);
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(theme.accent);
+        paint.set_stroke_width(2.0);
+        paint.set_style(PaintStyle::Stroke);
+        match position {
+            DropPosition::Before => canvas.draw_line((left, rect.top + 1.0), (rect.right, rect.top + 1.0), &paint),
+            DropPosition::After => canvas.draw_line((left, rect.bottom - 1.0), (rect.right, rect.bottom - 1.0), &paint),
+            DropPosition::Into => canvas.draw_rect(rect.with_inset((1.0, 1.0)), &paint),
+        };
+    }
+}
+
+impl<P: TreeProvider> Widget for TreeView<P> {
+    fn draw(&mut self, canvas: &mut Canvas) {
+        canvas.save();
+        canvas.clip_rect(self.viewport, None, Some(true));
+
+        if !self.rows.is_empty() {
+            let first = (self.offset / ROW_HEIGHT) as usize;
+            let last = (((self.offset + self.viewport.height()) / ROW_HEIGHT).ceil() as usize).min(self.rows.len());
+            for index in first..last {
+                let label = self.provider.label(&self.rows[index].id);
+                self.draw_row(canvas, index, &label);
+            }
+        }
+        if self.dragging {
+            self.draw_drop_indicator(canvas);
+        }
+
+        canvas.restore();
+        self.scrollbar.draw(canvas, self.offset);
+    }
+
+    fn on_event(&mut self, event: &Event) -> bool {
+        let mut offset = self.offset;
+        if self.scrollbar.on_event(event, &mut offset) {
+            if offset != self.offset {
+                self.set_offset(offset);
+            }
+            return true;
+        }
+
+        match event {
+            Event::Scroll { x, y, delta, .. } => {
+                if !self.rect.contains(Point::new(*x, *y)) {
+                    return false;
+                }
+                let (_, dy) = scroll_amount(*delta);
+                self.set_offset(self.offset + dy);
+                true
+            }
+            Event::MouseDown { x, y, button, modifiers } => {
+                self.focused = self.rect.contains(Point::new(*x, *y));
+                let index = match self.row_at(*x, *y) {
+                    Some(index) => index,
+                    None => return self.focused,
+                };
+                if *button != MouseButton::Left {
+                    return true;
+                }
+
+                let indent = self.indent_of(index);
+                if self.rows[index].expandable && *x >= indent && *x < indent + INDENT {
+                    let id = self.rows[index].id.clone();
+                    self.toggle(&id);
+                    return true;
+                }
+
+                let deferred = self.selection.is_selected(index) && modifiers.is_empty();
+                if !deferred && self.selection.click(index, *modifiers) {
+                    self.selection_changed();
+                }
+                self.press = Some((index, *x, *y, deferred));
+
+                let now = Instant::now();
+                let double = matches!(self.last_click, Some((i, at)) if i == index && now - at < DOUBLE_CLICK);
+                self.last_click = if double { None } else { Some((index, now)) };
+                if double {
+                    self.press = None;
+                    if self.rows[index].expandable {
+                        let id = self.rows[index].id.clone();
+                        self.toggle(&id);
+                    }
+                    self.activate(index);
+                }
+                true
+            }
+            Event::MouseMove { x, y, .. } => {
+                let (_, px, py, _) = match self.press {
+                    Some(press) => press,
+                    None => return false,
+                };
+                if !self.dragging && ((x - px).abs() > DRAG_THRESHOLD || (y - py).abs() > DRAG_THRESHOLD) {
+                    self.dragging = true;
+                }
+                if self.dragging {
+                    self.drop_target = self.row_at(*x, *y).and_then(|index| self.drop_target_at(index, *y));
+                    context::request_redraw();
+                }
+                true
+            }
+            Event::MouseUp { button: MouseButton::Left, .. } => {
+                let (index, _, _, deferred) = match self.press.take() {
+                    Some(press) => press,
+                    None => return false,
+                };
+                if self.dragging {
+                    self.dragging = false;
+                    self.finish_drag();
+                    context::request_redraw();
+                } else if deferred && self.selection.click(index, ModifiersState::empty()) {
+                    self.selection_changed();
+                }
+                true
+            }
+            Event::KeyDown { key: Some(key), modifiers } if self.focused => self.on_key(*key, *modifiers),
+            Event::FocusLost => {
+                self.focused = false;
+                self.press = None;
+                self.dragging = false;
+                self.drop_target = None;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn layout(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.viewport = rect;
+        self.update_layout();
+        if self.scrollbar.is_needed() {
+            self.viewport.right -= SCROLLBAR_SIZE;
+            self.update_layout();
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    fn debug_properties(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("rows", self.rows.len().to_string()),
+            ("selected", self.selection.selected().count().to_string()),
+        ]
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (self.rect.width(), self.total_height())
+    }
+}